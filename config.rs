@@ -0,0 +1,221 @@
+// rustburn-gui/src/config.rs
+
+// Settings that survive between sessions, stored as JSON under the user's config dir.
+use crate::i18n::Language;
+use rustburn_core::{BootType, BurnOptions, ChecksumAlgo, VerifyMode, AUTO_BLOCK_SIZE};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// How byte capacities are shown: decimal GB, as printed on the packaging,
+/// or binary GiB, as most file managers report them.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum SizeUnits {
+    #[default]
+    Decimal,
+    Binary,
+}
+
+/// The persisted subset of the application state.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    /// Tunables only; session-specific paths are cleared before saving.
+    #[serde(with = "saved_options")]
+    pub burn_options: BurnOptions,
+    pub is_dark_mode: bool,
+    /// Shows text beside the toolbar icons.
+    pub toolbar_labels: bool,
+    /// Stacks the option grids for small screens.
+    pub compact_mode: bool,
+    /// Eases the progress bar between updates.
+    pub smooth_progress: bool,
+    /// Sends a desktop notification when a long operation finishes.
+    pub notifications: bool,
+    pub size_units: SizeUnits,
+    pub language: Language,
+    /// Inner window size in points, if it was recorded on the last exit.
+    pub window_size: Option<[f32; 2]>,
+    /// Recently selected image paths, newest first.
+    pub recent_isos: Vec<String>,
+    /// Working directory for downloads and Windows ISO packing; the system
+    /// temp dir when unset.
+    pub temp_dir: Option<String>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            burn_options: BurnOptions::default(),
+            is_dark_mode: true,
+            toolbar_labels: false,
+            compact_mode: false,
+            smooth_progress: false,
+            notifications: true,
+            size_units: SizeUnits::Decimal,
+            language: Language::English,
+            window_size: None,
+            recent_isos: Vec::new(),
+            temp_dir: None,
+        }
+    }
+}
+
+impl AppConfig {
+    /// Returns `~/.config/rustburn/config.json` (or the platform equivalent).
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("rustburn").join("config.json"))
+    }
+
+    /// Loads the saved config, falling back to defaults if it is missing or unreadable.
+    pub fn load() -> Self {
+        let mut config: Self = Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default();
+        config.clear_session_fields();
+        config
+    }
+
+    /// Writes the config to disk, creating the directory if needed.
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let mut config = Self {
+            burn_options: self.burn_options.clone(),
+            is_dark_mode: self.is_dark_mode,
+            toolbar_labels: self.toolbar_labels,
+            compact_mode: self.compact_mode,
+            smooth_progress: self.smooth_progress,
+            notifications: self.notifications,
+            size_units: self.size_units,
+            language: self.language,
+            window_size: self.window_size,
+            recent_isos: self.recent_isos.clone(),
+            temp_dir: self.temp_dir.clone(),
+        };
+        config.clear_session_fields();
+        let json = serde_json::to_string_pretty(&config)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+
+    /// Drops the fields that only make sense for the session they were set in.
+    fn clear_session_fields(&mut self) {
+        self.burn_options.iso_path.clear();
+        self.burn_options.device_path.clear();
+        self.burn_options.checksum = None;
+        // A forgotten dry run would make the next real burn silently write nothing.
+        self.burn_options.dry_run = false;
+        // Likewise a forgotten autounattend.xml could silently wipe the next
+        // machine the stick boots.
+        self.burn_options.extra_files.clear();
+        // A write mode forced for one image is wrong for most others.
+        self.burn_options.write_mode = None;
+    }
+}
+
+/// The tunable part of `BurnOptions`, which has no serde support of its own.
+/// Enums are stored by variant name; anything missing or unknown falls back
+/// to the default.
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct SavedOptions {
+    threads: usize,
+    block_size: usize,
+    max_retries: u32,
+    sync_every_mb: u64,
+    verify: bool,
+    hash_verify: bool,
+    verify_skip_trailing_zeros: bool,
+    bad_block_check: bool,
+    auto_unmount: bool,
+    make_bootable: bool,
+    boot_type: String,
+    auto_eject: bool,
+    max_write_bytes_per_sec: Option<u64>,
+    checksum_algo: String,
+    on_complete_command: Option<String>,
+}
+
+impl Default for SavedOptions {
+    fn default() -> Self {
+        Self::from(&BurnOptions::default())
+    }
+}
+
+impl From<&BurnOptions> for SavedOptions {
+    fn from(options: &BurnOptions) -> Self {
+        Self {
+            threads: options.threads,
+            block_size: options.block_size,
+            max_retries: options.max_retries,
+            sync_every_mb: options.sync_every_mb,
+            verify: options.verify,
+            hash_verify: options.verify_mode == VerifyMode::HashCompare,
+            verify_skip_trailing_zeros: options.verify_skip_trailing_zeros,
+            bad_block_check: options.bad_block_check,
+            auto_unmount: options.auto_unmount,
+            make_bootable: options.make_bootable,
+            boot_type: format!("{:?}", options.boot_type),
+            auto_eject: options.auto_eject,
+            max_write_bytes_per_sec: options.max_write_bytes_per_sec,
+            checksum_algo: format!("{:?}", options.checksum_algo),
+            on_complete_command: options.on_complete_command.clone(),
+        }
+    }
+}
+
+impl SavedOptions {
+    fn into_options(self) -> BurnOptions {
+        let mut options = BurnOptions::default();
+        options.threads = self.threads.max(1);
+        options.block_size = match self.block_size {
+            AUTO_BLOCK_SIZE => AUTO_BLOCK_SIZE,
+            block_size => block_size.max(512),
+        };
+        options.max_retries = self.max_retries.min(10);
+        options.sync_every_mb = self.sync_every_mb.clamp(1, 4096);
+        options.verify = self.verify;
+        if self.hash_verify {
+            options.verify_mode = VerifyMode::HashCompare;
+        }
+        options.verify_skip_trailing_zeros = self.verify_skip_trailing_zeros;
+        options.bad_block_check = self.bad_block_check;
+        options.auto_unmount = self.auto_unmount;
+        options.make_bootable = self.make_bootable;
+        options.auto_eject = self.auto_eject;
+        options.max_write_bytes_per_sec = self.max_write_bytes_per_sec;
+        options.on_complete_command = self.on_complete_command;
+        if let Some(boot_type) = [BootType::UEFI, BootType::Legacy, BootType::Hybrid]
+            .into_iter()
+            .find(|b| format!("{:?}", b) == self.boot_type)
+        {
+            options.boot_type = boot_type;
+        }
+        if let Some(algo) = [ChecksumAlgo::Sha256, ChecksumAlgo::Sha1, ChecksumAlgo::Md5]
+            .into_iter()
+            .find(|a| format!("{:?}", a) == self.checksum_algo)
+        {
+            options.checksum_algo = algo;
+        }
+        options
+    }
+}
+
+/// `#[serde(with)]` adapter that stores `BurnOptions` as `SavedOptions`.
+mod saved_options {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(options: &BurnOptions, s: S) -> Result<S::Ok, S::Error> {
+        SavedOptions::from(options).serialize(s)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(d: D) -> Result<BurnOptions, D::Error> {
+        Ok(SavedOptions::deserialize(d)?.into_options())
+    }
+}
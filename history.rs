@@ -0,0 +1,64 @@
+// rustburn-gui/src/history.rs
+
+// A persisted record of finished burns, for auditing which stick got which image.
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Oldest entries are dropped past this many, so the file stays small.
+const MAX_ENTRIES: usize = 1000;
+
+/// One finished (or failed) burn.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct HistoryEntry {
+    /// Seconds since the Unix epoch when the burn finished.
+    pub finished_at: u64,
+    pub iso_name: String,
+    pub device: String,
+    /// "OK", or the error the burn stopped with.
+    pub result: String,
+    pub duration_secs: u64,
+}
+
+/// All recorded burns, oldest first.
+#[derive(Serialize, Deserialize, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Returns `~/.config/rustburn/history.json` (or the platform equivalent).
+    pub fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("rustburn").join("history.json"))
+    }
+
+    /// Loads the saved history, starting empty if it is missing or unreadable.
+    pub fn load() -> Self {
+        Self::path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Appends an entry and rewrites the file.
+    pub fn record(&mut self, entry: HistoryEntry) -> io::Result<()> {
+        self.entries.push(entry);
+        if self.entries.len() > MAX_ENTRIES {
+            let excess = self.entries.len() - MAX_ENTRIES;
+            self.entries.drain(..excess);
+        }
+        self.save()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let path = Self::path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, json)
+    }
+}
@@ -0,0 +1,659 @@
+// rustburn-gui/src/i18n.rs
+
+// UI translations. The English text is the lookup key, so an untranslated
+// string simply shows in English.
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// The languages the UI can be shown in.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Debug, Default)]
+pub enum Language {
+    #[default]
+    English,
+    Hebrew,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::Hebrew];
+
+    /// The language's own name, for the language selector.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::Hebrew => "עברית",
+        }
+    }
+
+    /// Whether text in this language reads right to left.
+    pub fn is_rtl(self) -> bool {
+        matches!(self, Language::Hebrew)
+    }
+}
+
+/// The language `tr` translates into; see `set_language`.
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Switches every later `tr` call to `language`.
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Language::Hebrew,
+        _ => Language::English,
+    }
+}
+
+/// Translates `key`, fills its `{}` placeholders with `args` in order and,
+/// for right-to-left languages, puts the result in display order.
+/// Use it through the `tr!` macro.
+pub fn tr(key: &'static str, args: &[&str]) -> String {
+    let language = language();
+    let template = match language {
+        Language::English => key,
+        Language::Hebrew => hebrew().get(key).copied().unwrap_or(key),
+    };
+    let mut text = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut rest = template;
+    while let Some(i) = rest.find("{}") {
+        text.push_str(&rest[..i]);
+        text.push_str(args.next().copied().unwrap_or(""));
+        rest = &rest[i + 2..];
+    }
+    text.push_str(rest);
+    if language.is_rtl() {
+        visual_order(&text)
+    } else {
+        text
+    }
+}
+
+/// Translates a UI string: `tr!("Quit")`, or `tr!("Paused at {}%", percent)`
+/// with one argument per `{}`.
+macro_rules! tr {
+    ($key:literal) => {
+        $crate::i18n::tr($key, &[])
+    };
+    ($key:literal, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::tr($key, &[$(&$arg.to_string()),+])
+    };
+}
+
+/// Reorders a right-to-left line for display, since egui lays glyphs out
+/// strictly left to right. A simplified bidi pass: runs of Latin letters and
+/// digits (paths, sizes, the spaces and punctuation between them) keep their
+/// order, everything else is reversed, and brackets in reversed runs are
+/// mirrored.
+fn visual_order(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let strong: Vec<Option<bool>> = chars
+        .iter()
+        .map(|&c| {
+            if ('\u{0590}'..='\u{05FF}').contains(&c) {
+                Some(false)
+            } else if c.is_alphanumeric() {
+                Some(true)
+            } else {
+                None
+            }
+        })
+        .collect();
+    // Neutral characters are left-to-right only between two left-to-right ones.
+    let ltr: Vec<bool> = (0..chars.len())
+        .map(|i| match strong[i] {
+            Some(ltr) => ltr,
+            None => {
+                let before = strong[..i].iter().rev().find_map(|s| *s);
+                let after = strong[i + 1..].iter().find_map(|s| *s);
+                before == Some(true) && after == Some(true)
+            }
+        })
+        .collect();
+
+    let mut runs: Vec<(bool, Vec<char>)> = Vec::new();
+    for (&c, &is_ltr) in chars.iter().zip(&ltr) {
+        match runs.last_mut() {
+            Some((run_ltr, run)) if *run_ltr == is_ltr => run.push(c),
+            _ => runs.push((is_ltr, vec![c])),
+        }
+    }
+    runs.into_iter()
+        .rev()
+        .flat_map(|(is_ltr, run)| {
+            if is_ltr {
+                run
+            } else {
+                run.into_iter().rev().map(mirror).collect()
+            }
+        })
+        .collect()
+}
+
+fn mirror(c: char) -> char {
+    match c {
+        '(' => ')',
+        ')' => '(',
+        '[' => ']',
+        ']' => '[',
+        '<' => '>',
+        '>' => '<',
+        other => other,
+    }
+}
+
+/// Hebrew translations, keyed by the English text.
+fn hebrew() -> &'static HashMap<&'static str, &'static str> {
+    static TABLE: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        HashMap::from([
+            // Menus
+            ("File", "קובץ"),
+            ("Scan Devices", "סריקת התקנים"),
+            ("Select ISO...", "בחירת ISO..."),
+            ("Download ISO...", "הורדת ISO..."),
+            ("Recent ISOs", "קובצי ISO אחרונים"),
+            ("No recent ISOs", "אין קובצי ISO אחרונים"),
+            ("File no longer exists", "הקובץ כבר לא קיים"),
+            ("Clear list", "ניקוי הרשימה"),
+            ("Eject Device", "שליפת התקן"),
+            ("Compute Checksum", "חישוב סכום ביקורת"),
+            ("Verify Existing Device...", "אימות התקן קיים..."),
+            ("Format Device...", "פרמוט התקן..."),
+            ("Test Device Speed...", "בדיקת מהירות התקן..."),
+            ("Clone Device → Image...", "שכפול התקן לקובץ תמונה..."),
+            ("Test Boot in VM", "בדיקת אתחול במכונה וירטואלית"),
+            ("Create ISO from Folder...", "יצירת ISO מתיקייה..."),
+            ("Quit", "יציאה"),
+            ("View", "תצוגה"),
+            ("Toggle Theme", "החלפת ערכת נושא"),
+            ("Toolbar text labels", "תוויות טקסט בסרגל הכלים"),
+            ("Compact layout", "פריסה מצומצמת"),
+            ("Smooth progress bar", "פס התקדמות חלק"),
+            ("Notify when done", "התראה בסיום"),
+            ("Temp Directory...", "תיקייה זמנית..."),
+            ("Burn History", "היסטוריית צריבות"),
+            ("Show internal disks", "הצגת דיסקים פנימיים"),
+            ("Show partitions", "הצגת מחיצות"),
+            ("Capacity units:", "יחידות נפח:"),
+            ("Language:", "שפה:"),
+            ("Help", "עזרה"),
+            ("About", "אודות"),
+            // Status bar
+            ("Ready", "מוכן"),
+            ("Scanning for devices...", "מחפש התקנים..."),
+            ("Cancel scan", "ביטול סריקה"),
+            ("Checking ISO integrity... {}%", "בודק את תקינות ה-ISO... {}%"),
+            ("Burning... {}% (flushing buffers…)", "צורב... {}% (מרוקן מטמון…)"),
+            ("Burning... ({})", "צורב... ({})"),
+            ("Burning... {}% ({})", "צורב... {}% ({})"),
+            ("Burning... {}%", "צורב... {}%"),
+            ("Creating Windows ISO... {}% ({})", "יוצר ISO של Windows... {}% ({})"),
+            ("Creating Windows ISO... {}%", "יוצר ISO של Windows... {}%"),
+            ("Creating ISO... {}% ({})", "יוצר ISO... {}% ({})"),
+            ("Creating ISO... {}%", "יוצר ISO... {}%"),
+            ("Verifying written data... {}%", "מאמת את הנתונים שנכתבו... {}%"),
+            ("Making device bootable... {}%", "הופך את ההתקן לאתחולי... {}%"),
+            ("Ejecting device...", "שולף את ההתקן..."),
+            ("Erasing device... {}%", "מוחק את ההתקן... {}%"),
+            ("Erasing device...", "מוחק את ההתקן..."),
+            ("Formatting device... {}%", "מפרמט את ההתקן... {}%"),
+            ("Formatting device...", "מפרמט את ההתקן..."),
+            ("Testing device speed...", "בודק את מהירות ההתקן..."),
+            ("Computing checksum... {}%", "מחשב סכום ביקורת... {}%"),
+            ("Stopping writers...", "עוצר את הכתיבה..."),
+            ("Cloning device... {}% ({})", "משכפל את ההתקן... {}% ({})"),
+            ("Cloning device... {}%", "משכפל את ההתקן... {}%"),
+            ("Downloading ISO... ({})", "מוריד ISO... ({})"),
+            ("Downloading ISO... {}% ({})", "מוריד ISO... {}% ({})"),
+            ("Downloading ISO... {}%", "מוריד ISO... {}%"),
+            ("Operation completed successfully.", "הפעולה הושלמה בהצלחה."),
+            ("Error: {}", "שגיאה: {}"),
+            ("Paused at {}%", "מושהה ב-{}%"),
+            ("Burning {} devices... {} of {} finished", "צורב {} התקנים... {} מתוך {} הסתיימו"),
+            ("Insert next device…", "הכנס את ההתקן הבא…"),
+            ("{} elapsed", "עברו {}"),
+            // Dialogs
+            ("Confirm Burn", "אישור צריבה"),
+            ("All data on this device will be destroyed:", "כל הנתונים בהתקן הזה יימחקו:"),
+            (
+                "All data on these {} devices will be destroyed:",
+                "כל הנתונים ב-{} ההתקנים האלה יימחקו:",
+            ),
+            ("All data on these devices will be destroyed:", "כל הנתונים בהתקנים האלה יימחקו:"),
+            ("Image to write: {}", "קובץ לכתיבה: {}"),
+            (
+                "Already contains bootable media: {}. Overwrite?",
+                "כבר מכיל מדיה ניתנת לאתחול: {}. לדרוס?",
+            ),
+            ("Burn", "צריבה"),
+            ("Unmount and burn", "ניתוק וצריבה"),
+            ("Cancel", "ביטול"),
+            (
+                "A target is a large device. Type ERASE to confirm:",
+                "אחד היעדים הוא התקן גדול. הקלד ERASE לאישור:",
+            ),
+            ("Erase Device", "מחיקת התקן"),
+            (
+                "All data and partitions on these devices will be destroyed:",
+                "כל הנתונים והמחיצות בהתקנים האלה יימחקו:",
+            ),
+            ("Erase", "מחיקה"),
+            ("Abort All", "עצירת הכול"),
+            ("Abort {} in-progress burns?", "לעצור {} צריבות פעילות?"),
+            (
+                "Devices already being written are left incomplete and won't boot.",
+                "התקנים שכבר נכתבים יישארו חלקיים ולא יאתחלו.",
+            ),
+            ("Abort all", "עצירת הכול"),
+            ("Keep burning", "המשך צריבה"),
+            // Tooltips, buttons and windows
+            (
+                "Check that the selected devices still hold the selected image, without writing \
+                 anything",
+                "בדיקה שההתקנים שנבחרו עדיין מכילים את הקובץ שנבחר, בלי לכתוב דבר",
+            ),
+            (
+                "Boot the selected device, or the ISO if no single device is selected, in QEMU \
+                 (needs qemu-system-x86_64)",
+                "אתחול ההתקן שנבחר, או ה-ISO אם לא נבחר התקן יחיד, ב-QEMU (דורש \
+                 qemu-system-x86_64)",
+            ),
+            (
+                "Stack the options and fold the advanced ones away",
+                "סידור האפשרויות זו מתחת לזו וקיפול המתקדמות",
+            ),
+            (
+                "Send a desktop notification when an operation that ran for a while finishes or \
+                 fails",
+                "שליחת התראה לשולחן העבודה כשפעולה ארוכה מסתיימת או נכשלת",
+            ),
+            (
+                "Glide the bar between updates instead of jumping",
+                "החלקת הפס בין עדכונים במקום קפיצה",
+            ),
+            (
+                "List each device's partitions so an image can be written to one",
+                "הצגת המחיצות של כל התקן כדי שאפשר יהיה לכתוב לאחת מהן",
+            ),
+            ("Scan for devices (Ctrl+R)", "סריקת התקנים (Ctrl+R)"),
+            ("Select ISO file (Ctrl+O)", "בחירת קובץ ISO (Ctrl+O)"),
+            ("Clear selections", "ניקוי הבחירות"),
+            (
+                "Remove selected device(s) from the list (until the next scan)",
+                "הסרת ההתקנים שנבחרו מהרשימה (עד הסריקה הבאה)",
+            ),
+            ("Burn to device (Ctrl+Enter)", "צריבה להתקן (Ctrl+Enter)"),
+            ("Stop operation", "עצירת הפעולה"),
+            (
+                "Stop every burn in this run, after confirming",
+                "עצירת כל הצריבות בהרצה הזו, לאחר אישור",
+            ),
+            ("Resume burning", "המשך צריבה"),
+            ("Pause burning (keeps the device open)", "השהיית הצריבה (ההתקן נשאר פתוח)"),
+            ("Create Windows ISO", "יצירת ISO של Windows"),
+            ("Eject selected device(s)", "שליפת ההתקנים שנבחרו"),
+            ("Wipe the selected device(s)", "מחיקת ההתקנים שנבחרו"),
+            (
+                "Assembly line: eject after each successful burn, select the next stick as soon \
+                 as it is plugged in, and press Enter to burn it",
+                "פס ייצור: שליפה אחרי כל צריבה מוצלחת, בחירת ההתקן הבא מיד כשהוא מחובר, ו-Enter \
+                 לצריבתו",
+            ),
+            (
+                "Add a casper-rw/persistence partition in the leftover space (bootable \
+                 Ubuntu/Debian live images only)",
+                "הוספת מחיצת casper-rw/persistence במקום שנותר (רק לקובצי Ubuntu/Debian חיים \
+                 ניתנים לאתחול)",
+            ),
+            ("Destination path on the burned volume", "נתיב היעד בכרך הצרוב"),
+            ("Add file...", "הוספת קובץ..."),
+            (
+                "Copy a file such as autounattend.xml, a preseed or a kickstart onto the volume \
+                 after bootable setup",
+                "העתקת קובץ כמו autounattend.xml, preseed או kickstart לכרך אחרי הגדרת האתחול",
+            ),
+            ("Use the mode the image looks made for", "שימוש במצב שהקובץ נראה מיועד לו"),
+            (
+                "Copy the image block for block. Right for hybrid ISOs and most Linux images",
+                "העתקת הקובץ בלוק אחר בלוק. מתאים לקובצי ISO היברידיים ולרוב קובצי Linux",
+            ),
+            (
+                "Format the device as FAT32 and copy the image's files onto it; it boots on UEFI \
+                 only. Right for Windows installers",
+                "פרמוט ההתקן כ-FAT32 והעתקת הקבצים שבקובץ אליו; הוא יאתחל ב-UEFI בלבד. מתאים \
+                 להתקנות Windows",
+            ),
+            (
+                "Mount the new volume afresh and compare every copied file with the image's",
+                "עיגון הכרך החדש מחדש והשוואת כל קובץ שהועתק לזה שבקובץ",
+            ),
+            (
+                "Read back the bytes the image occupies; space past the end of the image is not \
+                 checked",
+                "קריאה חוזרת של הבתים שהקובץ תופס; המקום שאחרי סוף הקובץ לא נבדק",
+            ),
+            (
+                "Re-read the written region and compare every byte",
+                "קריאה חוזרת של האזור שנכתב והשוואת כל בית",
+            ),
+            (
+                "Hash the written region and compare it to the ISO hash taken while writing",
+                "חישוב גיבוב של האזור שנכתב והשוואתו לגיבוב ה-ISO שנלקח בזמן הכתיבה",
+            ),
+            (
+                "Read back a reproducible random subset of blocks spread across the image: \
+                 quicker, but weaker than a full check",
+                "קריאה חוזרת של מדגם אקראי וקבוע של בלוקים לאורך הקובץ: מהיר יותר, אך חלש מבדיקה \
+                 מלאה",
+            ),
+            (
+                "Stop at the end of the ISO's declared volume instead of checking the zero \
+                 padding after it",
+                "עצירה בסוף הכרך המוצהר של ה-ISO במקום לבדוק את ריפוד האפסים שאחריו",
+            ),
+            (
+                "Read back with direct IO (O_DIRECT / FILE_FLAG_NO_BUFFERING) so verify sees the \
+                 device, not the page cache. Falls back to dropping the cache where direct IO \
+                 isn't available",
+                "קריאה חוזרת ב-IO ישיר (O_DIRECT / FILE_FLAG_NO_BUFFERING) כדי שהאימות יראה את \
+                 ההתקן ולא את המטמון. כש-IO ישיר לא זמין, המטמון מרוקן במקום",
+            ),
+            (
+                "Write a test pattern across the whole device and read it back, to catch \
+                 fake-capacity sticks (slow)",
+                "כתיבת תבנית בדיקה על כל ההתקן וקריאתה חזרה, לאיתור התקנים עם נפח מזויף (איטי)",
+            ),
+            (
+                "Zero the first MB and the backup GPT at the end of the device, so a smaller \
+                 image doesn't leave the old layout's partitions behind",
+                "איפוס ה-MB הראשון וגיבוי ה-GPT בסוף ההתקן, כדי שקובץ קטן יותר לא ישאיר מאחור \
+                 מחיצות ישנות",
+            ),
+            (
+                "Time a few sizes on the device before writing and use the fastest",
+                "מדידת כמה גדלים על ההתקן לפני הכתיבה ושימוש במהיר ביותר",
+            ),
+            (
+                "Retry a failed block write this many times, with a short backoff, before giving \
+                 up on the burn",
+                "מספר הניסיונות החוזרים לכתיבת בלוק שנכשלה, בהפסקות קצרות, לפני ויתור על הצריבה",
+            ),
+            (
+                "Flush the device to disk after this much data. Smaller values lose less on a \
+                 crash or power cut, and resume checkpoints land on these sync points, at some \
+                 cost in speed",
+                "ריקון ההתקן לדיסק אחרי כמות נתונים זו. ערכים קטנים מאבדים פחות בקריסה או בהפסקת \
+                 חשמל, ונקודות ההמשך נקבעות בנקודות הסנכרון האלה, במחיר מסוים במהירות",
+            ),
+            (
+                "Cap the write rate so other devices on a shared hub keep working",
+                "הגבלת קצב הכתיבה כדי שהתקנים אחרים במפצל משותף ימשיכו לעבוד",
+            ),
+            (
+                "Unmount the target's filesystems before writing; otherwise a mounted target \
+                 stops the burn",
+                "ניתוק מערכות הקבצים של היעד לפני הכתיבה; אחרת יעד מחובר עוצר את הצריבה",
+            ),
+            (
+                "Flush, sync and eject once the burn (and verify) succeeds",
+                "ריקון, סנכרון ושליפה כשהצריבה (והאימות) מצליחים",
+            ),
+            (
+                "Wait for the ejected device to show up again and verify it from a fresh open, so \
+                 a USB bridge's cache can't pass a corrupt write",
+                "המתנה להופעה מחדש של ההתקן שנשלף ואימותו מפתיחה חדשה, כדי שמטמון של גשר USB לא \
+                 יעביר כתיבה פגומה",
+            ),
+            (
+                "Save <image>.burned.sha256 next to the image with its hash, the device serial \
+                 and the time, as an audit trail",
+                "שמירת <image>.burned.sha256 ליד הקובץ עם הגיבוב, המספר הסידורי של ההתקן והשעה, \
+                 לתיעוד",
+            ),
+            (
+                "Open the device, check size, hash the ISO and check permissions, but skip every \
+                 write and log what would have been done",
+                "פתיחת ההתקן, בדיקת הגודל, גיבוב ה-ISO ובדיקת ההרשאות, אך בלי לכתוב דבר ורישום מה \
+                 היה נעשה",
+            ),
+            (
+                "Make the device bootable with the type this image supports",
+                "הפיכת ההתקן לאתחולי בסוג שהקובץ תומך בו",
+            ),
+            ("Clear", "ניקוי"),
+            ("Remove from queue", "הסרה מהתור"),
+            (
+                "The already-written part is re-verified first; the burn starts over if it no \
+                 longer matches.",
+                "החלק שכבר נכתב מאומת שוב קודם; הצריבה מתחילה מחדש אם הוא כבר לא תואם.",
+            ),
+            (
+                "Back the partition up to the temp directory, burn, then re-create it in the \
+                 space left after the image and restore it",
+                "גיבוי המחיצה לתיקייה הזמנית, צריבה, ואז יצירתה מחדש במקום שנותר אחרי הקובץ \
+                 ושחזורה",
+            ),
+            ("Format Device", "פרמוט התקן"),
+            (
+                "Works everywhere; files up to 4 GB. Sticks over 32 GB are formatted too, unlike \
+                 with most OS tools",
+                "עובד בכל מקום; קבצים עד 4 GB. גם התקנים מעל 32 GB מפורמטים, בניגוד לרוב כלי \
+                 מערכת ההפעלה",
+            ),
+            (
+                "Large files; works on current Windows, macOS and Linux",
+                "קבצים גדולים; עובד ב-Windows, macOS ו-Linux עדכניים",
+            ),
+            (
+                "Large files; read-only on some macOS and Linux setups",
+                "קבצים גדולים; לקריאה בלבד בחלק מהתקנות macOS ו-Linux",
+            ),
+            ("Format", "פרמוט"),
+            ("Test Device Speed", "בדיקת מהירות התקן"),
+            ("Run test", "הרצת בדיקה"),
+            ("Checksum", "סכום ביקורת"),
+            ("Copy", "העתקה"),
+            ("Download ISO", "הורדת ISO"),
+            ("Download", "הורדה"),
+            ("Create ISO from Folder", "יצירת ISO מתיקייה"),
+            (
+                "Uses the boot loader files found in the folder",
+                "משתמש בקובצי מנהל האתחול שנמצאים בתיקייה",
+            ),
+            ("Create...", "יצירה..."),
+            ("About RustBurn Professional", "אודות RustBurn Professional"),
+            ("Temp Directory", "תיקייה זמנית"),
+            ("Browse...", "עיון..."),
+            ("Use system default", "ברירת המחדל של המערכת"),
+            ("📜 Logs", "📜 יומן"),
+            ("Show/Hide Logs", "הצגה/הסתרה של היומן"),
+            (
+                "Stop waiting and keep the previous device list",
+                "הפסקת ההמתנה ושמירת רשימת ההתקנים הקודמת",
+            ),
+            ("Run as administrator", "הרצה כמנהל"),
+            (
+                "Restart RustBurn with the rights to open raw devices",
+                "הפעלה מחדש של RustBurn עם הרשאה לפתוח התקנים ישירות",
+            ),
+            ("Show messages at this level and above", "הצגת הודעות ברמה זו ומעלה"),
+            ("Save logs...", "שמירת היומן..."),
+            (
+                "The device didn't answer in time during the scan. Re-plug it and scan again.",
+                "ההתקן לא ענה בזמן במהלך הסריקה. חבר אותו מחדש וסרוק שוב.",
+            ),
+            (
+                "Write-protected: turn off the lock switch on the stick to burn it",
+                "מוגן מפני כתיבה: כבה את מתג הנעילה בהתקן כדי לצרוב אותו",
+            ),
+            (
+                "The reported capacity looks suspicious for this device, a common sign of a \
+                 fake-capacity stick. Burn with \"Check for bad blocks\" on before trusting it.",
+                "הנפח המדווח נראה חשוד להתקן הזה, סימן נפוץ לנפח מזויף. צרוב עם \"Check for bad \
+                 blocks\" לפני שסומכים עליו.",
+            ),
+            (
+                "Reader threads that read and decompress upcoming blocks into a bounded queue. \
+                 Writes to the device stay serialized on a single writer, so this sets how far \
+                 reading runs ahead of the device, not how many blocks are written at once.",
+                "תהליכוני קריאה שקוראים ופורסים את הבלוקים הבאים לתור מוגבל. הכתיבה להתקן נשארת \
+                 סדרתית בכותב יחיד, כך שזה קובע כמה רחוק הקריאה מקדימה את ההתקן, לא כמה בלוקים \
+                 נכתבים בבת אחת.",
+            ),
+            (
+                "A shell command run after each successful burn, never after a failed one, with \
+                 RUSTBURN_DEVICE, RUSTBURN_ISO and RUSTBURN_RESULT set. Its output goes to the \
+                 log. It runs with RustBurn's own privileges, often root or Administrator, and is \
+                 saved in the config file, so anyone who can edit that file can run commands as \
+                 you.",
+                "פקודת מעטפת שרצה אחרי כל צריבה מוצלחת, אף פעם לא אחרי צריבה שנכשלה, עם \
+                 RUSTBURN_DEVICE, RUSTBURN_ISO ו-RUSTBURN_RESULT. הפלט שלה נכתב ליומן. היא רצה \
+                 בהרשאות של RustBurn עצמו, לרוב root או Administrator, ונשמרת בקובץ ההגדרות, כך \
+                 שכל מי שיכול לערוך את הקובץ יכול להריץ פקודות בשמך.",
+            ),
+            // Toolbar and options
+            ("Scan", "סריקה"),
+            ("Add ISO", "הוספת ISO"),
+            ("Remove", "הסרה"),
+            ("Stop", "עצירה"),
+            ("Pause", "השהיה"),
+            ("Resume", "המשך"),
+            ("Eject", "הוצאה"),
+            ("Windows ISO", "ISO של Windows"),
+            ("Next device", "ההתקן הבא"),
+            ("Advanced options", "אפשרויות מתקדמות"),
+            ("Threads:", "תהליכונים:"),
+            ("Bootable:", "אתחול:"),
+            ("Make bootable", "הפיכה לבר-אתחול"),
+            ("Partition scheme:", "טבלת מחיצות:"),
+            (
+                "Legacy BIOS boot needs an MBR partition table",
+                "אתחול Legacy BIOS דורש טבלת מחיצות MBR",
+            ),
+            ("Volume label:", "תווית כרך:"),
+            ("e.g. UBUNTU_2404", "למשל UBUNTU_2404"),
+            ("Persistence (MB):", "שמירת נתונים (MB):"),
+            ("Extra files:", "קבצים נוספים:"),
+            ("Write mode:", "מצב כתיבה:"),
+            ("Auto", "אוטומטי"),
+            ("Auto ({})", "אוטומטי ({})"),
+            ("DD (raw copy)", "DD (העתקה גולמית)"),
+            ("ISO (file copy)", "ISO (העתקת קבצים)"),
+            ("Verification:", "אימות:"),
+            ("Verify after burn", "אימות אחרי צריבה"),
+            ("Byte compare", "השוואת בתים"),
+            ("Hash compare", "השוואת גיבוב"),
+            ("Sample", "מדגם"),
+            ("Sample ({}%)", "מדגם ({}%)"),
+            ("Blocks checked:", "בלוקים שנבדקים:"),
+            ("Skip trailing padding", "דילוג על הריפוד בסוף"),
+            ("Bypass OS cache", "עקיפת המטמון של מערכת ההפעלה"),
+            ("Before burn:", "לפני הצריבה:"),
+            ("Check for bad blocks", "בדיקת בלוקים פגומים"),
+            ("Clear old partition table", "ניקוי טבלת המחיצות הישנה"),
+            ("Block Size:", "גודל בלוק:"),
+            ("Custom…", "מותאם אישית…"),
+            ("Write retries:", "ניסיונות כתיבה חוזרים:"),
+            ("Sync every (MB):", "סנכרון כל (MB):"),
+            ("Limit speed (MB/s):", "הגבלת מהירות (MB/s):"),
+            ("If mounted:", "אם מעוגן:"),
+            ("Unmount automatically", "ניתוק עיגון אוטומטי"),
+            ("When done:", "בסיום:"),
+            ("Eject device", "הוצאת ההתקן"),
+            ("Re-check after re-detect", "בדיקה חוזרת אחרי זיהוי מחדש"),
+            ("Write .sha256 record", "כתיבת רשומת sha256."),
+            ("On success run:", "בהצלחה להריץ:"),
+            ("shell command (optional)", "פקודת מעטפת (רשות)"),
+            ("Dry run:", "הרצת ניסיון:"),
+            ("Validate only", "בדיקה בלבד"),
+            ("Use {} boot", "שימוש באתחול {}"),
+            ("Select exactly one device to clone", "בחר בדיוק התקן אחד לשכפול"),
+            ("Waiting for a free writer...", "ממתין לכותב פנוי..."),
+            ("1. Selected ISO File", "1. קובץ ISO שנבחר"),
+            ("2. Select Target Device", "2. בחירת התקן יעד"),
+            ("Expected checksum:", "סכום ביקורת צפוי:"),
+            ("Logs", "יומן"),
+            ("Queued next ({}):", "הבאים בתור ({}):"),
+            // Dialogs and messages
+            (
+                "Partition targets get the raw image only: the partition table and bootable \
+                 setup are skipped, so they may not boot.",
+                "מחיצות יעד מקבלות את התמונה הגולמית בלבד: טבלת המחיצות והגדרת האתחול מדולגות, \
+                 ולכן ייתכן שלא יאתחלו.",
+            ),
+            (
+                "Device is mounted at {}. Unmount and continue?",
+                "ההתקן מעוגן ב-{}. לנתק את העיגון ולהמשיך?",
+            ),
+            (
+                "Dry run: the device is checked but nothing is written.",
+                "הרצת ניסיון: ההתקן נבדק אבל דבר לא נכתב.",
+            ),
+            ("Resume previous burn at {}?", "להמשיך את הצריבה הקודמת מ-{}?"),
+            ("{}: resume previous burn at {}?", "{}: להמשיך את הצריבה הקודמת מ-{}?"),
+            ("Keep a data partition", "שמירת מחיצת נתונים"),
+            (
+                "A partition can't be kept when burning a compressed image",
+                "אי אפשר לשמור מחיצה בצריבת תמונה דחוסה",
+            ),
+            (
+                "{} ({}) won't fit in the {} the burn leaves free",
+                "{} ({}) לא ייכנס ב-{} שהצריבה משאירה פנויים",
+            ),
+            (
+                "The temp directory has {} free, too little to back up {} ({})",
+                "בתיקייה הזמנית פנויים {}, מעט מדי לגיבוי {} ({})",
+            ),
+            ("Temp directory: {}", "תיקייה זמנית: {}"),
+            (
+                "Target holds the source image — writing it would destroy the image mid-burn",
+                "היעד מכיל את תמונת המקור — כתיבה אליו תהרוס את התמונה באמצע הצריבה",
+            ),
+            (
+                "A few hundred MB will be written to and read back from:",
+                "כמה מאות MB ייכתבו וייקראו בחזרה מ:",
+            ),
+            ("Data in that region will be overwritten.", "הנתונים באזור הזה יידרסו."),
+            ("Filesystem:", "מערכת קבצים:"),
+            ("Label:", "תווית:"),
+            ("URL:", "כתובת:"),
+            ("Expected {} (optional):", "{} צפוי (רשות):"),
+            ("Folder: {}", "תיקייה: {}"),
+            ("No burns recorded yet.", "עדיין לא נרשמו צריבות."),
+            ("Finished", "הסתיים"),
+            ("Image", "תמונה"),
+            ("Device", "התקן"),
+            ("Result", "תוצאה"),
+            ("Duration", "משך"),
+            (
+                "This is an {} image, but Legacy BIOS boot only exists on x86 PCs",
+                "זו תמונת {}, אבל אתחול Legacy BIOS קיים רק במחשבי x86",
+            ),
+            (
+                "This image has no BIOS boot loader; Legacy boot will likely fail",
+                "לתמונה אין מנהל אתחול BIOS; אתחול Legacy כנראה ייכשל",
+            ),
+            (
+                "This image has no UEFI boot loader; UEFI boot will likely fail",
+                "לתמונה אין מנהל אתחול UEFI; אתחול UEFI כנראה ייכשל",
+            ),
+            (
+                "The re-check after eject compares the device block for block; turn it off or \
+                 use DD mode",
+                "הבדיקה החוזרת אחרי ההוצאה משווה את ההתקן בלוק אחר בלוק; כבה אותה או השתמש במצב DD",
+            ),
+            ("Volume label: {}", "תווית כרך: {}"),
+            ("Block size: {}", "גודל בלוק: {}"),
+            ("ISO is {} but {} is only {}", "גודל ה-ISO הוא {} אבל {} הוא רק {}"),
+            (
+                "Wrote {} GB in {} ({} MB/s), verified OK.",
+                "נכתבו {} GB תוך {} ({} MB/s), האימות עבר.",
+            ),
+            ("Wrote {} GB in {} ({} MB/s).", "נכתבו {} GB תוך {} ({} MB/s)."),
+        ])
+    })
+}
@@ -0,0 +1,26 @@
+// rustburn-gui/src/labels.rs
+
+// Volume label rules, shared with rustburn-cli so both front ends turn away the same labels.
+use rustburn_core::FsType;
+
+/// Longest volume label FAT32 can store.
+pub const FAT32_LABEL_MAX: usize = 11;
+/// Characters FAT32 does not allow in a volume label.
+pub const FAT32_LABEL_FORBIDDEN: &str = "\"*+,./:;<=>?[\\]|";
+/// Longest volume label NTFS takes; FAT32 and exFAT allow `FAT32_LABEL_MAX`.
+pub const NTFS_LABEL_MAX: usize = 32;
+
+/// Why `label` can't name a fresh `fs` volume, if it breaks that filesystem's rules.
+pub fn format_label_error(fs: FsType, label: &str) -> Option<&'static str> {
+    match fs {
+        FsType::Fat32 | FsType::ExFat if label.chars().count() > FAT32_LABEL_MAX => {
+            Some("at most 11 characters")
+        }
+        FsType::Ntfs if label.chars().count() > NTFS_LABEL_MAX => Some("at most 32 characters"),
+        FsType::Fat32 if !label.is_ascii() => Some("ASCII characters only"),
+        FsType::Fat32 if label.chars().any(|c| FAT32_LABEL_FORBIDDEN.contains(c)) => {
+            Some("must not contain \" * + , . / : ; < = > ? [ \\ ] |")
+        }
+        _ => None,
+    }
+}
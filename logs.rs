@@ -0,0 +1,53 @@
+// rustburn-gui/src/logs.rs
+
+// The log panel's backing store; every line is stamped with the time since launch.
+use rustburn_core::LogLevel;
+use std::time::Instant;
+
+/// Log lines shown in the log panel, oldest first, each with its level.
+pub struct LogBuffer {
+    started: Instant,
+    lines: Vec<(LogLevel, String)>,
+}
+
+impl LogBuffer {
+    pub fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            lines: Vec::new(),
+        }
+    }
+
+    /// Appends a line, prefixed with a monotonic "[HH:MM:SS.mmm]" since launch.
+    pub fn push(&mut self, level: LogLevel, msg: impl Into<String>) {
+        let stamp = format_elapsed(self.started.elapsed().as_millis() as u64);
+        self.lines
+            .push((level, format!("[{}] {}", stamp, msg.into())));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Every line regardless of level, as written to a saved log file.
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.lines.iter().map(|(_, line)| line.as_str())
+    }
+
+    /// The lines at `min` or above, for the log panel's level filter.
+    pub fn lines_at(&self, min: LogLevel) -> impl Iterator<Item = &(LogLevel, String)> {
+        self.lines.iter().filter(move |(level, _)| *level >= min)
+    }
+}
+
+/// Formats milliseconds as HH:MM:SS.mmm.
+fn format_elapsed(millis: u64) -> String {
+    let secs = millis / 1000;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60,
+        millis % 1000
+    )
+}
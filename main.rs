@@ -1,13 +1,119 @@
 // rustburn-gui/src/main.rs
 
 // Import necessary crates and modules.
-use eframe::{NativeOptions, egui};
+#[macro_use]
+mod i18n;
+mod config;
+mod history;
+mod labels;
+mod logs;
+
+use config::{AppConfig, SizeUnits};
+use eframe::{egui, NativeOptions};
 use egui::{FontData, FontDefinitions, FontFamily, TextureHandle, Visuals};
+use history::{History, HistoryEntry};
+use i18n::Language;
+use labels::{format_label_error, FAT32_LABEL_MAX};
+use logs::LogBuffer;
 
 use rfd; // For file dialogs.
-use rustburn_core::{BootType, BurnOptions, RustBurn, UiProgress, UsbDevice};
-use std::sync::mpsc;
+use rustburn_core::{
+    BootType, BurnError, BurnOptions, ChecksumAlgo, DeviceEvent, EraseMode, FsType, IsoArch,
+    IsoInfo, LogLevel, OsFamily, PartitionScheme, RustBurn, UiProgress, UsbDevice, VerifyMode,
+    WriteMode, AUTO_BLOCK_SIZE,
+};
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
 use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// The window title while nothing is running.
+const APP_TITLE: &str = "RustBurn Professional";
+
+/// Image extensions we burn as raw block streams.
+const IMAGE_EXTENSIONS: &[&str] = &["iso", "img", "bin"];
+/// Compression suffixes the core can decode on the fly.
+const COMPRESSED_EXTENSIONS: &[&str] = &["gz", "xz", "zst"];
+
+/// Explains what `BurnOptions::threads` actually controls.
+const READ_AHEAD_HINT: &str = "Reader threads that read and decompress upcoming blocks \
+into a bounded queue. Writes to the device stay serialized on a single writer, so this \
+sets how far reading runs ahead of the device, not how many blocks are written at once.";
+
+/// How long a scan waits on one device's queries before listing it as unresponsive.
+const SCAN_DEVICE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Operations shorter than this finish without a desktop notification; the
+/// user is most likely still watching.
+const NOTIFY_AFTER: Duration = Duration::from_secs(30);
+
+/// Explains `BurnOptions::on_complete_command`, including what it exposes.
+const COMPLETE_COMMAND_HINT: &str = "A shell command run after each successful burn, never \
+after a failed one, with RUSTBURN_DEVICE, RUSTBURN_ISO and RUSTBURN_RESULT set. Its output \
+goes to the log. It runs with RustBurn's own privileges, often root or Administrator, and \
+is saved in the config file, so anyone who can edit that file can run commands as you.";
+
+/// How many recently used images File > Recent ISOs remembers.
+const MAX_RECENT_ISOS: usize = 10;
+
+/// Maximum number of devices written at once, so a shared hub controller isn't saturated.
+const MAX_CONCURRENT_BURNS: usize = 4;
+
+/// Starting value for the "Limit speed" field when it is first switched on.
+const DEFAULT_SPEED_LIMIT_MB: u64 = 20;
+
+/// Starting size of the persistence partition when it is first switched on.
+const DEFAULT_PERSISTENCE_MB: u64 = 4096;
+
+/// Room kept for the partition bootable setup adds after the image when
+/// checking that a preserved partition still fits.
+const BOOTABLE_PARTITION_RESERVE: u64 = 64 * 1024 * 1024;
+
+/// File name hints for Debian-family live images, which support a persistence partition.
+const DEBIAN_LIVE_HINTS: &[&str] = &[
+    "ubuntu", "kubuntu", "xubuntu", "lubuntu", "debian", "mint", "kali", "pop-os", "zorin",
+    "elementary",
+];
+
+/// Block sizes offered in the Block Size combo, in KB.
+const BLOCK_SIZE_PRESETS_KB: [usize; 4] = [512, 1024, 2048, 4096];
+
+/// Largest "Custom…" block size; past this a block only costs memory.
+const MAX_BLOCK_SIZE_KB: usize = 64 * 1024;
+
+/// Sector size assumed when no selected device reports one.
+const DEFAULT_SECTOR_SIZE: u64 = 512;
+
+/// Shown when a target is unplugged while its burn is still running.
+const DISCONNECTED_MESSAGE: &str = "Device disconnected during write — the burn is incomplete";
+
+/// Longest volume label an ISO9660 primary volume descriptor can hold.
+const ISO_LABEL_MAX: usize = 32;
+
+/// Share of blocks read back when "Sample" verification is first picked.
+const DEFAULT_SAMPLE_FRACTION: f32 = 0.05;
+
+/// How fast the smoothed progress bar closes the gap to the real value, per
+/// second: about 90% of it in a quarter second.
+const PROGRESS_EASE_RATE: f32 = 10.0;
+
+/// Why a burn onto the disk holding its own image is refused.
+const SOURCE_ON_TARGET_MESSAGE: &str =
+    "Target holds the source image — writing it would destroy the image mid-burn";
+
+/// Devices above this size (64 GB) need "ERASE" typed before a burn can start.
+const ERASE_CONFIRM_THRESHOLD: u64 = 64_000_000_000;
+
+/// System fonts with Hebrew coverage, tried in order.
+const HEBREW_FALLBACK_FONTS: &[&str] = &[
+    "/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/dejavu/DejaVuSans.ttf",
+    "/usr/share/fonts/truetype/noto/NotoSansHebrew-Regular.ttf",
+    "/System/Library/Fonts/Supplemental/Arial.ttf",
+    "C:\\Windows\\Fonts\\arial.ttf",
+];
 
 /// This struct holds the loaded image textures for our icons.
 struct AppIcons {
@@ -22,73 +128,555 @@ struct AppIcons {
 
 impl AppIcons {
     /// Creates a new instance of `AppIcons` by loading images from bytes.
-    fn new(ctx: &egui::Context) -> Self {
+    /// An icon that fails to decode is replaced by a placeholder and logged.
+    fn new(ctx: &egui::Context, logs: &mut LogBuffer) -> Self {
+        let mut icon = |name: &str, bytes: &[u8]| {
+            load_icon(ctx, name, bytes).unwrap_or_else(|e| {
+                logs.push(
+                    LogLevel::Warn,
+                    format!("Failed to load icon '{}': {}; using a placeholder", name, e),
+                );
+                placeholder_icon(ctx, name)
+            })
+        };
         Self {
-            add: load_icon(ctx, "add_icon", include_bytes!("../assets/fd.png")),
-            burn: load_icon(ctx, "burn_icon", include_bytes!("../assets/fl.png")),
-            scan: load_icon(ctx, "scan_icon", include_bytes!("../assets/rad.png")),
-            stop: load_icon(ctx, "stop_icon", include_bytes!("../assets/st.png")),
-            remove: load_icon(ctx, "remove_icon", include_bytes!("../assets/rm.png")),
-            clear: load_icon(ctx, "clear_icon", include_bytes!("../assets/cl.png")),
-            win_iso: load_icon(ctx, "win_iso_icon", include_bytes!("../assets/wi.png")),
+            add: icon("add_icon", include_bytes!("../assets/fd.png")),
+            burn: icon("burn_icon", include_bytes!("../assets/fl.png")),
+            scan: icon("scan_icon", include_bytes!("../assets/rad.png")),
+            stop: icon("stop_icon", include_bytes!("../assets/st.png")),
+            remove: icon("remove_icon", include_bytes!("../assets/rm.png")),
+            clear: icon("clear_icon", include_bytes!("../assets/cl.png")),
+            win_iso: icon("win_iso_icon", include_bytes!("../assets/wi.png")),
         }
     }
 }
 
 /// This enum represents the current state of the application.
-#[derive(PartialEq, Debug)]
+#[derive(PartialEq, Debug, Clone)]
 enum AppStatus {
     Idle,
     Scanning,
+    /// Hashing the image against the expected checksum before a burn.
+    CheckingIso,
     Burning,
     CreatingWinIso,
+    CreatingIso,
     Verifying,
     SettingUpBootable,
     Ejecting,
     Erasing,
+    Formatting,
+    Cloning,
+    Downloading,
+    Benchmarking,
+    Hashing,
+    /// A stopped burn waiting for its cancelled writers to exit.
+    Stopping,
     Done,
-    Error(String),
+    Error(BurnError),
+}
+
+/// Throughput details from the latest `UiProgress::WritingDetailed` update.
+struct WriteStats {
+    bytes_done: u64,
+    /// Zero when the final size isn't known up front (compressed input).
+    total: u64,
+    bytes_per_sec: f64,
+}
+
+/// Relative durations of the phases of a burn, used to fold them into one
+/// monotonic bar. Phases that are switched off weigh nothing.
+#[derive(Clone, Copy)]
+struct PhaseWeights {
+    hash: f32,
+    write: f32,
+    verify: f32,
+    bootable: f32,
+}
+
+impl PhaseWeights {
+    fn for_options(options: &BurnOptions) -> Self {
+        let verify = match (options.verify, &options.verify_mode) {
+            (false, _) => 0.0,
+            // Re-reading is about as slow as writing on most sticks.
+            (true, VerifyMode::ByteCompare) => 1.0,
+            // Only hashes the device side; the ISO hash was taken while writing.
+            (true, VerifyMode::HashCompare) => 0.8,
+            // Only the sampled blocks are read back.
+            (true, VerifyMode::Sample { fraction }) => *fraction,
+        };
+        Self {
+            // Only reads the image, which is quicker than writing it out.
+            hash: if options.checksum.is_some() { 0.3 } else { 0.0 },
+            write: 1.0,
+            verify,
+            bootable: if options.make_bootable { 0.05 } else { 0.0 },
+        }
+    }
+
+    /// Overall 0..1 progress given the current phase and its own 0..1 progress.
+    /// Phases run checksum check, write, verify, then bootable setup.
+    fn overall(&self, status: &AppStatus, phase_progress: f32) -> f32 {
+        let done_before = match status {
+            AppStatus::CheckingIso => 0.0,
+            AppStatus::Burning => self.hash,
+            AppStatus::Verifying => self.hash + self.write,
+            AppStatus::SettingUpBootable => self.hash + self.write + self.verify,
+            AppStatus::Done => return 1.0,
+            _ => return phase_progress,
+        };
+        let current = match status {
+            AppStatus::CheckingIso => self.hash,
+            AppStatus::Burning => self.write,
+            AppStatus::Verifying => self.verify,
+            _ => self.bootable,
+        };
+        let total = self.hash + self.write + self.verify + self.bootable;
+        ((done_before + current * phase_progress.clamp(0.0, 1.0)) / total).min(1.0)
+    }
+}
+
+/// A "Compute Checksum" run; `hash` is filled in by the core's `SourceHash` update.
+struct FileHash {
+    path: String,
+    algo: &'static str,
+    hash: Option<String>,
+}
+
+/// One device's share of a (possibly multi-device) burn run.
+struct BurnJob {
+    device: String,
+    /// The target's stable ID when the burn was confirmed, so a node that
+    /// now names a different stick is refused instead of written.
+    device_id: Option<String>,
+    /// `Idle` while the job waits in the queue for a free writer slot.
+    status: AppStatus,
+    /// Progress of the current phase alone; see `overall_progress`.
+    progress: f32,
+    weights: PhaseWeights,
+    write_stats: Option<WriteStats>,
+    /// The finished burn's one-line summary, from `UiProgress::Summary`.
+    summary: Option<String>,
+    receiver: Option<mpsc::Receiver<UiProgress>>,
+    thread: Option<thread::JoinHandle<()>>,
+    cancel: Arc<AtomicBool>,
+    /// When the writer thread was spawned, for the history's duration column.
+    started: Option<Instant>,
+    /// While set, the writer blocks between blocks with the device still open.
+    pause: Arc<AtomicBool>,
+    /// Offset of a checkpoint the user chose to resume this device from.
+    resume_from: Option<u64>,
+    /// A dry run never writes the device, so stopping it leaves nothing half-written.
+    dry_run: bool,
+}
+
+impl BurnJob {
+    fn queued(
+        device: String,
+        device_id: Option<String>,
+        weights: PhaseWeights,
+        dry_run: bool,
+    ) -> Self {
+        Self {
+            device,
+            device_id,
+            status: AppStatus::Idle,
+            progress: 0.0,
+            weights,
+            write_stats: None,
+            summary: None,
+            receiver: None,
+            thread: None,
+            cancel: Arc::new(AtomicBool::new(false)),
+            started: None,
+            pause: Arc::new(AtomicBool::new(false)),
+            resume_from: None,
+            dry_run,
+        }
+    }
+
+    fn is_queued(&self) -> bool {
+        self.receiver.is_none() && self.status == AppStatus::Idle
+    }
+
+    fn is_finished(&self) -> bool {
+        matches!(self.status, AppStatus::Done | AppStatus::Error(_))
+    }
+
+    fn is_running(&self) -> bool {
+        !self.is_queued() && !self.is_finished()
+    }
+
+    fn is_paused(&self) -> bool {
+        self.is_running() && self.pause.load(Ordering::SeqCst)
+    }
+
+    /// One line describing where this job is, for its progress bar.
+    fn status_line(&self) -> String {
+        if self.is_queued() {
+            tr!("Waiting for a free writer...")
+        } else if let (AppStatus::Done, Some(summary)) = (&self.status, &self.summary) {
+            summary.clone()
+        } else if self.is_paused() {
+            tr!("Paused at {}%", format!("{:.0}", self.overall_progress() * 100.0))
+        } else {
+            status_text(
+                &self.status,
+                self.overall_progress(),
+                self.write_stats.as_ref(),
+            )
+        }
+    }
+
+    /// Progress across every enabled phase, so the bar never jumps back to 0.
+    fn overall_progress(&self) -> f32 {
+        self.weights.overall(&self.status, self.progress)
+    }
+
+    /// Applies one progress update from this job's burn thread.
+    fn apply(&mut self, update: UiProgress, logs: &mut LogBuffer, tag: bool) {
+        let prefix = if tag {
+            format!("[{}] ", self.device)
+        } else {
+            String::new()
+        };
+        match update {
+            UiProgress::Log(level, msg) => logs.push(level, format!("{}{}", prefix, msg)),
+            UiProgress::SourceHash(hash) => {
+                logs.push(LogLevel::Info, format!("{}Source SHA-256: {}", prefix, hash))
+            }
+            UiProgress::StartingHashCheck => {
+                self.status = AppStatus::CheckingIso;
+                self.progress = 0.0;
+            }
+            UiProgress::Hashing(p) => self.progress = p,
+            UiProgress::StartingBurn => {
+                self.status = AppStatus::Burning;
+                self.progress = 0.0;
+            }
+            UiProgress::Writing(p) => self.progress = p,
+            UiProgress::WritingDetailed {
+                fraction,
+                bytes_done,
+                total,
+                bytes_per_sec,
+            } => {
+                self.progress = fraction;
+                self.write_stats = Some(WriteStats {
+                    bytes_done,
+                    total,
+                    bytes_per_sec,
+                });
+            }
+            UiProgress::StartingVerification => {
+                self.status = AppStatus::Verifying;
+                self.progress = 0.0;
+            }
+            UiProgress::Verifying(p) => self.progress = p,
+            UiProgress::StartingBootableSetup => {
+                self.status = AppStatus::SettingUpBootable;
+                self.progress = 0.0;
+            }
+            UiProgress::Summary {
+                bytes,
+                elapsed,
+                avg_mbps,
+                verified,
+            } => {
+                let summary = format_summary(bytes, elapsed, avg_mbps, verified);
+                logs.push(LogLevel::Info, format!("{}{}", prefix, summary));
+                self.summary = Some(summary);
+            }
+            // Not emitted by `burn_iso`.
+            UiProgress::StartingCreateWinIso
+            | UiProgress::StartingEject
+            | UiProgress::StartingErase => {}
+            // A stopped job stays Cancelled whatever its writer reports last,
+            // and keeps its thread so the stop can wait for it.
+            UiProgress::Done if self.cancel.load(Ordering::SeqCst) => {}
+            UiProgress::Error(e) if self.cancel.load(Ordering::SeqCst) => {
+                if e != BurnError::Cancelled {
+                    logs.push(LogLevel::Error, format!("{}ERROR: {}", prefix, e));
+                }
+            }
+            UiProgress::Done => {
+                self.status = AppStatus::Done;
+                self.thread = None;
+            }
+            UiProgress::Error(e) => {
+                logs.push(LogLevel::Error, format!("{}ERROR: {}", prefix, e));
+                // Whichever side failed, a write cut short leaves a device that
+                // may still look bootable.
+                if self.status == AppStatus::Burning && self.write_stats.is_some() && !self.dry_run
+                {
+                    logs.push(
+                        LogLevel::Warn,
+                        format!(
+                            "{}WARNING: {} was only partially written and is NOT bootable. Burn it again before use.",
+                            prefix, self.device
+                        ),
+                    );
+                }
+                self.status = AppStatus::Error(e);
+                self.thread = None;
+            }
+        }
+    }
 }
 
 /// This is the main struct that holds our application's state.
 struct RustBurnApp {
     is_dark_mode: bool,
+    /// Shows each toolbar icon's name beside it.
+    toolbar_labels: bool,
+    /// Stacks the option grids and folds the advanced one, for small screens.
+    compact_mode: bool,
+    /// Eases the status bar's progress toward the latest update each frame.
+    smooth_progress: bool,
+    /// What the status bar's progress bar shows while `smooth_progress` is on.
+    displayed_progress: f32,
+    /// Sends a desktop notification when a long operation finishes.
+    notifications: bool,
+    /// The UI language; `tr!` reads it from `i18n`, this copy is for the menu.
+    language: Language,
+    /// "Burn, eject, wait for next": every burn ejects, and a newly plugged
+    /// stick is selected for the same image.
+    next_device_mode: bool,
+    /// A next-device burn finished; the next hotplugged stick gets selected.
+    awaiting_next_device: bool,
+    /// The stick picked up in next-device mode, which Enter burns without
+    /// asking again while it is still the only selection.
+    next_device: Option<String>,
+    /// How device capacities and size-check messages are shown.
+    size_units: SizeUnits,
     icons: AppIcons,
     devices: Vec<UsbDevice>,
+    /// Result of the background scan started by `scan_devices`, while one is running.
+    scan_receiver: Option<mpsc::Receiver<Result<Vec<UsbDevice>, BurnError>>>,
+    /// A cancelled scan whose thread hasn't returned yet; no new scan starts
+    /// until it does, so stuck scan threads can't pile up.
+    abandoned_scan: Option<mpsc::Receiver<Result<Vec<UsbDevice>, BurnError>>>,
+    /// Device named by `--device` at launch, selected once the first scan finds it.
+    preselect_device: Option<String>,
+    /// Hotplug add/remove events from `RustBurn::watch_devices`.
+    device_events: mpsc::Receiver<DeviceEvent>,
     burn_options: BurnOptions,
-    selected_device: Option<String>,
+    /// What `RustBurn::inspect_iso` found in the selected image's headers.
+    iso_info: Option<IsoInfo>,
+    /// Result of the background `inspect_iso` started by `set_iso_path`, with
+    /// the path it read, while one is running.
+    iso_info_receiver: Option<mpsc::Receiver<(String, IsoInfo)>>,
+    /// Recently selected images, newest first, at most `MAX_RECENT_ISOS`.
+    recent_isos: Vec<String>,
+    /// Devices chosen as burn targets, in the order they were picked.
+    selected_devices: Vec<String>,
+    /// Lists non-removable (internal) disks alongside USB sticks.
+    show_internal_disks: bool,
+    /// Lists each device's partitions under it as burn targets of their own.
+    show_partitions: bool,
+    /// The user has acknowledged that system disks may be selected.
+    allow_system_disks: bool,
+    /// Expected ISO hash as typed by the user; empty means skip the check.
+    checksum_input: String,
+    /// Volume label as typed; trimmed and uppercased into
+    /// `burn_options.volume_label` when the burn starts.
+    volume_label_input: String,
     status: AppStatus,
     burn_progress: f32,
+    write_stats: Option<WriteStats>,
+    /// Summary line of the last finished operation, shown while the status is `Done`.
+    summary: Option<String>,
+    /// When the current operation began, for the status bar's stopwatch.
+    operation_started: Option<Instant>,
+    /// The launcher of an elevated copy, while its authentication prompt is up.
+    elevation: Option<std::process::Child>,
     /// The type here is now corrected to use the unified `UiProgress`.
     progress_receiver: Option<mpsc::Receiver<UiProgress>>,
     /// Use the correct field name for the background operation thread.
     operation_thread: Option<thread::JoinHandle<()>>,
+    /// Set by Stop while `run_for_selected_devices` works through its list;
+    /// the devices not yet started are skipped.
+    device_run_cancel: Option<Arc<AtomicBool>>,
+    /// Per-device burns of the current (or last) burn run.
+    burn_jobs: Vec<BurnJob>,
     show_about_window: bool,
+    show_temp_dir_window: bool,
+    /// Working directory for downloads and Windows ISO packing; the system
+    /// temp dir when unset.
+    temp_dir: Option<String>,
+    /// Free bytes in the working directory, or why it can't be used; refreshed
+    /// when the directory changes or the window opens, not every frame.
+    temp_dir_check: Option<Result<u64, String>>,
+    show_history_window: bool,
+    /// Past burns, loaded at launch and appended as jobs finish.
+    history: History,
+    show_burn_confirmation: bool,
+    /// The selected device the image is read from, found once when the burn
+    /// dialog opens rather than every frame.
+    source_disk: Option<String>,
+    show_erase_confirmation: bool,
+    show_benchmark_confirmation: bool,
+    show_abort_confirmation: bool,
+    show_format_window: bool,
+    format_fs: FsType,
+    /// Volume label for "Format Device"; empty leaves the volume unlabeled.
+    format_label: String,
+    show_download_window: bool,
+    download_url_input: String,
+    /// Expected hash of the download; handed to the burn's checksum check.
+    download_hash_input: String,
+    /// The running or finished "Compute Checksum" run, shown once it has a result.
+    file_hash: Option<FileHash>,
+    /// Folder picked for "Create ISO from Folder"; the options window is open while set.
+    create_iso_source: Option<PathBuf>,
+    create_iso_label: String,
+    create_iso_bootable: bool,
+    create_iso_boot_type: BootType,
+    /// Where the running download is written; becomes the ISO once it is `Done`.
+    download_dest: Option<String>,
+    erase_mode: EraseMode,
+    /// The user agreed to unmount mounted targets for the current burn run.
+    unmount_for_run: bool,
+    /// Valid `.rbresume` checkpoints for the pending burn, by device and offset.
+    checkpoints: Vec<(String, u64)>,
+    /// Resume the devices in `checkpoints` instead of starting them over.
+    resume_checkpoints: bool,
+    /// Partition of the single target to back up before the burn and re-create
+    /// after it; opted into per run from the burn confirmation.
+    preserve_partition: Option<String>,
+    /// The temp directory's free space, checked once when keeping a partition
+    /// is ticked, since the partition is backed up there during the burn.
+    preserve_temp_free: Option<Result<u64, String>>,
+    /// Text typed into the "Type ERASE to confirm" field.
+    erase_confirm_input: String,
     is_file_hovering: bool,
+    /// Images waiting their turn, oldest first. Each successful run loads the
+    /// next one as the selected image.
+    image_queue: VecDeque<String>,
     show_log_panel: bool,
-    logs: Vec<String>,
+    /// Lowest level shown in the log panel; saved log files keep everything.
+    log_level: LogLevel,
+    logs: LogBuffer,
+    /// Last title sent to the viewport, so it is only resent when it changes.
+    window_title: String,
+    /// Text of the "Custom…" block size field, in KB; `None` while a preset is chosen.
+    block_size_input: Option<String>,
 }
 
 impl RustBurnApp {
     /// This function is called once to create the application state.
-    fn new(cc: &eframe::CreationContext) -> Self {
+    fn new(cc: &eframe::CreationContext, config: AppConfig, launch: LaunchArgs) -> Self {
         setup_custom_fonts(&cc.egui_ctx);
-        Self {
-            is_dark_mode: true,
-            icons: AppIcons::new(&cc.egui_ctx),
+        i18n::set_language(config.language);
+        let mut logs = LogBuffer::new();
+        let icons = AppIcons::new(&cc.egui_ctx, &mut logs);
+        let mut app = Self {
+            is_dark_mode: config.is_dark_mode,
+            toolbar_labels: config.toolbar_labels,
+            compact_mode: config.compact_mode,
+            smooth_progress: config.smooth_progress,
+            displayed_progress: 0.0,
+            notifications: config.notifications,
+            language: config.language,
+            next_device_mode: false,
+            awaiting_next_device: false,
+            next_device: None,
+            size_units: config.size_units,
+            icons,
             devices: Vec::new(),
-            burn_options: BurnOptions::default(),
-            selected_device: None,
+            scan_receiver: None,
+            abandoned_scan: None,
+            preselect_device: launch.device,
+            device_events: watch_devices(cc.egui_ctx.clone()),
+            block_size_input: (config.burn_options.block_size != AUTO_BLOCK_SIZE
+                && !BLOCK_SIZE_PRESETS_KB.contains(&(config.burn_options.block_size / 1024)))
+            .then(|| (config.burn_options.block_size / 1024).to_string()),
+            burn_options: config.burn_options,
+            iso_info: None,
+            iso_info_receiver: None,
+            recent_isos: config.recent_isos,
+            selected_devices: Vec::new(),
+            show_internal_disks: false,
+            show_partitions: false,
+            allow_system_disks: false,
+            checksum_input: String::new(),
+            volume_label_input: String::new(),
             status: AppStatus::Idle,
             burn_progress: 0.0,
+            write_stats: None,
+            summary: None,
+            operation_started: None,
+            elevation: None,
             progress_receiver: None,
             operation_thread: None,
+            device_run_cancel: None,
+            burn_jobs: Vec::new(),
             show_about_window: false,
+            show_temp_dir_window: false,
+            temp_dir: config.temp_dir,
+            temp_dir_check: None,
+            show_history_window: false,
+            history: History::load(),
+            show_burn_confirmation: false,
+            source_disk: None,
+            show_erase_confirmation: false,
+            show_benchmark_confirmation: false,
+            show_abort_confirmation: false,
+            show_format_window: false,
+            format_fs: FsType::Fat32,
+            format_label: String::new(),
+            show_download_window: false,
+            download_url_input: String::new(),
+            download_hash_input: String::new(),
+            download_dest: None,
+            file_hash: None,
+            create_iso_source: None,
+            create_iso_label: String::new(),
+            create_iso_bootable: false,
+            create_iso_boot_type: BootType::Hybrid,
+            erase_mode: EraseMode::QuickZeroTable,
+            unmount_for_run: false,
+            preserve_partition: None,
+            preserve_temp_free: None,
+            checkpoints: Vec::new(),
+            resume_checkpoints: true,
+            erase_confirm_input: String::new(),
             is_file_hovering: false,
+            image_queue: VecDeque::new(),
             // The comma was missing after the line above this one.
             show_log_panel: false,
-            logs: Vec::new(),
+            log_level: LogLevel::Info,
+            window_title: APP_TITLE.to_string(),
+            logs,
+        };
+        if let Some(iso) = launch.iso_path {
+            app.set_iso_path(iso);
         }
+        app.scan_devices();
+        app
+    }
+}
+
+/// What was passed on the command line, e.g. by a file-manager "Open with".
+#[derive(Default)]
+struct LaunchArgs {
+    iso_path: Option<String>,
+    device: Option<String>,
+}
+
+impl LaunchArgs {
+    /// Reads `rustburn [IMAGE] [--device <PATH>]`; anything else is ignored.
+    fn parse(args: impl IntoIterator<Item = String>) -> Self {
+        let mut launch = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            if arg == "--device" {
+                launch.device = args.next();
+            } else if launch.iso_path.is_none() && is_supported_image(Path::new(&arg)) {
+                launch.iso_path = Some(arg);
+            }
+        }
+        launch
     }
 }
 
@@ -96,31 +684,107 @@ impl RustBurnApp {
 impl eframe::App for RustBurnApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         // Check for progress updates from the background thread.
+        let mut disconnected = false;
         if let Some(rx) = &self.progress_receiver {
-            while let Ok(update) = rx.try_recv() {
+            loop {
+                let update = match rx.try_recv() {
+                    Ok(update) => update,
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        disconnected = true;
+                        break;
+                    }
+                };
                 // This match block now covers all possible UiProgress variants.
                 match update {
-                    UiProgress::Log(msg) => self.logs.push(msg),
+                    UiProgress::Log(level, msg) => self.logs.push(level, msg),
+                    UiProgress::SourceHash(hash) => match &mut self.file_hash {
+                        Some(file_hash) => {
+                            self.logs.push(
+                                LogLevel::Info,
+                                format!("{} of {}: {}", file_hash.algo, file_hash.path, hash),
+                            );
+                            file_hash.hash = Some(hash);
+                        }
+                        None => self
+                            .logs
+                            .push(LogLevel::Info, format!("Source SHA-256: {}", hash)),
+                    },
+                    // Only `burn_iso` emits it, and burns report through their `BurnJob`.
+                    UiProgress::StartingHashCheck => {}
+                    UiProgress::Hashing(p) => self.burn_progress = p,
                     UiProgress::StartingBurn => self.status = AppStatus::Burning,
                     UiProgress::Writing(p) => self.burn_progress = p,
+                    UiProgress::WritingDetailed {
+                        fraction,
+                        bytes_done,
+                        total,
+                        bytes_per_sec,
+                    } => {
+                        self.burn_progress = fraction;
+                        self.write_stats = Some(WriteStats {
+                            bytes_done,
+                            total,
+                            bytes_per_sec,
+                        });
+                    }
                     UiProgress::StartingVerification => self.status = AppStatus::Verifying,
                     UiProgress::Verifying(p) => self.burn_progress = p,
                     UiProgress::StartingBootableSetup => self.status = AppStatus::SettingUpBootable,
                     UiProgress::StartingCreateWinIso => self.status = AppStatus::CreatingWinIso,
                     UiProgress::StartingEject => self.status = AppStatus::Ejecting,
                     UiProgress::StartingErase => self.status = AppStatus::Erasing,
+                    UiProgress::Summary {
+                        bytes,
+                        elapsed,
+                        avg_mbps,
+                        verified,
+                    } => {
+                        let summary = format_summary(bytes, elapsed, avg_mbps, verified);
+                        self.logs.push(LogLevel::Info, summary.clone());
+                        self.summary = Some(summary);
+                    }
                     UiProgress::Done => {
+                        if let Some(dest) = self.download_dest.take() {
+                            self.logs
+                                .push(LogLevel::Info, format!("Downloaded to {}", dest));
+                            self.set_iso_path(dest);
+                        }
                         self.status = AppStatus::Done;
                         self.operation_thread = None;
+                        self.device_run_cancel = None;
+                        self.notify_finished(None);
                     }
                     UiProgress::Error(e) => {
-                        self.logs.push(format!("ERROR: {}", e));
+                        self.logs.push(LogLevel::Error, format!("ERROR: {}", e));
                         self.status = AppStatus::Error(e);
                         self.operation_thread = None;
+                        self.device_run_cancel = None;
+                        self.download_dest = None;
+                        self.file_hash = None;
+                        self.notify_finished(None);
                     }
                 }
             }
         }
+        if disconnected {
+            self.progress_receiver = None;
+            self.device_run_cancel = None;
+            // Done and Error clear the thread, so one still set here means the
+            // sender was dropped without either: the thread panicked.
+            if self.operation_thread.take().is_some() {
+                let e = BurnError::Other("Operation thread terminated unexpectedly".to_string());
+                self.logs.push(LogLevel::Error, format!("ERROR: {}", e));
+                self.status = AppStatus::Error(e);
+                self.download_dest = None;
+                self.file_hash = None;
+            }
+        }
+        self.poll_burn_jobs();
+        self.poll_elevation(ctx);
+        self.poll_scan();
+        self.poll_iso_info(ctx);
+        self.poll_device_events();
 
         // Set the visual theme (dark/light).
         ctx.set_visuals(if self.is_dark_mode {
@@ -129,14 +793,34 @@ impl eframe::App for RustBurnApp {
             Visuals::light()
         });
 
+        self.detect_drag_and_drop(ctx);
+        self.handle_shortcuts(ctx);
+
+        if ctx.input(|i| i.viewport().close_requested()) {
+            self.save_config(ctx);
+        }
+
         // Render the different parts of the UI.
         self.render_top_panel(ctx);
         self.render_central_panel(ctx);
         self.render_bottom_panel(ctx);
         self.render_about_window(ctx);
+        self.render_temp_dir_window(ctx);
+        self.render_history_window(ctx);
+        self.render_burn_confirmation(ctx);
+        self.render_erase_confirmation(ctx);
+        self.render_benchmark_confirmation(ctx);
+        self.render_abort_confirmation(ctx);
+        self.render_format_window(ctx);
+        self.render_download_window(ctx);
+        self.render_create_iso_window(ctx);
+        self.render_file_hash_window(ctx);
         self.render_drag_and_drop_overlay(ctx);
         self.render_log_panel(ctx);
 
+        self.update_window_title(ctx);
+        self.ease_progress(ctx);
+
         // Keep redrawing the UI if an operation is active.
         if !self.is_idle() {
             ctx.request_repaint();
@@ -150,26 +834,205 @@ impl RustBurnApp {
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             // --- Menu Bar ---
             egui::menu::bar(ui, |ui| {
-                ui.menu_button("File", |ui| {
-                    if ui.button("Scan Devices").clicked() {
+                ui.menu_button(tr!("File"), |ui| {
+                    if ui
+                        .add_enabled(self.is_idle(), egui::Button::new(tr!("Scan Devices")))
+                        .clicked()
+                    {
                         self.scan_devices();
                     }
-                    if ui.button("Select ISO...").clicked() {
+                    if ui.button(tr!("Select ISO...")).clicked() {
                         self.select_iso_file();
                     }
+                    if ui
+                        .add_enabled(self.is_idle(), egui::Button::new(tr!("Download ISO...")))
+                        .clicked()
+                    {
+                        self.show_download_window = true;
+                    }
+                    ui.menu_button(tr!("Recent ISOs"), |ui| {
+                        if self.recent_isos.is_empty() {
+                            ui.label(tr!("No recent ISOs"));
+                        }
+                        let mut picked = None;
+                        for path in &self.recent_isos {
+                            let exists = Path::new(path).exists();
+                            if ui
+                                .add_enabled(exists, egui::Button::new(path.as_str()))
+                                .on_disabled_hover_text(tr!("File no longer exists"))
+                                .clicked()
+                            {
+                                picked = Some(path.clone());
+                            }
+                        }
+                        if let Some(path) = picked {
+                            self.set_iso_path(path);
+                            ui.close_menu();
+                        }
+                        if !self.recent_isos.is_empty() {
+                            ui.separator();
+                            if ui.button(tr!("Clear list")).clicked() {
+                                self.recent_isos.clear();
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                    if ui
+                        .add_enabled(
+                            self.is_idle() && !self.selected_devices.is_empty(),
+                            egui::Button::new(tr!("Eject Device")),
+                        )
+                        .clicked()
+                    {
+                        self.start_eject();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.is_idle()
+                                && !self.selected_devices.is_empty()
+                                && !self.burn_options.iso_path.is_empty(),
+                            egui::Button::new(tr!("Verify Existing Device...")),
+                        )
+                        .on_hover_text(tr!(
+                            "Check that the selected devices still hold the selected image, \
+                             without writing anything"
+                        ))
+                        .clicked()
+                    {
+                        ui.close_menu();
+                        self.start_compare();
+                    }
+                    ui.add_enabled_ui(self.is_idle(), |ui| {
+                        ui.menu_button(tr!("Compute Checksum"), |ui| {
+                            for (algo, name) in [
+                                (ChecksumAlgo::Sha256, "SHA-256"),
+                                (ChecksumAlgo::Sha1, "SHA-1"),
+                                (ChecksumAlgo::Md5, "MD5"),
+                            ] {
+                                if ui.button(format!("{}...", name)).clicked() {
+                                    ui.close_menu();
+                                    self.start_hash_file(algo, name);
+                                }
+                            }
+                        });
+                    });
+                    if ui
+                        .add_enabled(
+                            self.is_idle() && !self.selected_devices.is_empty(),
+                            egui::Button::new(tr!("Format Device...")),
+                        )
+                        .clicked()
+                    {
+                        self.erase_confirm_input.clear();
+                        self.show_format_window = true;
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.is_idle() && !self.selected_devices.is_empty(),
+                            egui::Button::new(tr!("Test Device Speed...")),
+                        )
+                        .clicked()
+                    {
+                        self.show_benchmark_confirmation = true;
+                    }
+                    if ui
+                        .add_enabled(
+                            self.is_idle()
+                                && (self.selected_devices.len() == 1
+                                    || !self.burn_options.iso_path.is_empty()),
+                            egui::Button::new(tr!("Test Boot in VM")),
+                        )
+                        .on_hover_text(tr!(
+                            "Boot the selected device, or the ISO if no single device is \
+                             selected, in QEMU (needs qemu-system-x86_64)"
+                        ))
+                        .clicked()
+                    {
+                        ui.close_menu();
+                        self.start_test_boot();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.is_idle(),
+                            egui::Button::new(tr!("Create ISO from Folder...")),
+                        )
+                        .clicked()
+                    {
+                        ui.close_menu();
+                        self.pick_create_iso_source();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.is_idle() && self.selected_devices.len() == 1,
+                            egui::Button::new(tr!("Clone Device → Image...")),
+                        )
+                        .on_disabled_hover_text(tr!("Select exactly one device to clone"))
+                        .clicked()
+                    {
+                        self.start_clone_to_image();
+                    }
                     ui.separator();
-                    if ui.button("Quit").clicked() {
+                    if ui.button(tr!("Quit")).clicked() {
                         ctx.send_viewport_cmd(egui::ViewportCommand::Close);
                     }
                 });
-                ui.menu_button("View", |ui| {
-                    if ui.button("Toggle Theme").clicked() {
+                ui.menu_button(tr!("View"), |ui| {
+                    if ui.button(tr!("Toggle Theme")).clicked() {
                         self.is_dark_mode = !self.is_dark_mode;
                     }
+                    ui.checkbox(&mut self.toolbar_labels, tr!("Toolbar text labels"));
+                    ui.checkbox(&mut self.compact_mode, tr!("Compact layout"))
+                        .on_hover_text(tr!("Stack the options and fold the advanced ones away"));
+                    ui.checkbox(&mut self.notifications, tr!("Notify when done"))
+                        .on_hover_text(tr!(
+                            "Send a desktop notification when an operation that ran for a \
+                             while finishes or fails"
+                        ));
+                    ui.checkbox(&mut self.smooth_progress, tr!("Smooth progress bar"))
+                        .on_hover_text(tr!("Glide the bar between updates instead of jumping"));
+                    if ui.button(tr!("Temp Directory...")).clicked() {
+                        self.temp_dir_check = Some(check_work_dir(&self.work_dir()));
+                        self.show_temp_dir_window = true;
+                        ui.close_menu();
+                    }
+                    if ui.button(tr!("Burn History")).clicked() {
+                        self.show_history_window = true;
+                        ui.close_menu();
+                    }
+                    if ui
+                        .checkbox(&mut self.show_internal_disks, tr!("Show internal disks"))
+                        .changed()
+                    {
+                        self.drop_hidden_selection();
+                    }
+                    if ui
+                        .checkbox(&mut self.show_partitions, tr!("Show partitions"))
+                        .on_hover_text(tr!(
+                            "List each device's partitions so an image can be written to one"
+                        ))
+                        .changed()
+                    {
+                        self.drop_hidden_selection();
+                    }
+                    ui.separator();
+                    ui.label(tr!("Capacity units:"));
+                    ui.radio_value(&mut self.size_units, SizeUnits::Decimal, "GB (1000³ bytes)");
+                    ui.radio_value(&mut self.size_units, SizeUnits::Binary, "GiB (1024³ bytes)");
+                    ui.separator();
+                    ui.label(tr!("Language:"));
+                    for language in Language::ALL {
+                        if ui
+                            .radio_value(&mut self.language, language, language.name())
+                            .changed()
+                        {
+                            i18n::set_language(language);
+                        }
+                    }
                 });
 
-                ui.menu_button("Help", |ui| {
-                    if ui.button("About").clicked() {
+                ui.menu_button(tr!("Help"), |ui| {
+                    if ui.button(tr!("About")).clicked() {
                         self.show_about_window = true;
                     }
                 });
@@ -177,198 +1040,1014 @@ impl RustBurnApp {
             ui.separator();
 
             // --- Toolbar with action buttons ---
-            ui.horizontal(|ui| {
-                let is_idle =
-                    self.status == AppStatus::Idle || matches!(self.status, AppStatus::Error(_));
-                if ui
-                    .add_enabled(is_idle, egui::ImageButton::new(&self.icons.scan))
-                    .on_hover_text("Scan for devices")
+            // Wraps onto a second row when the window is too narrow for it.
+            ui.horizontal_wrapped(|ui| {
+                let is_idle = self.is_idle();
+                let labels = self.toolbar_labels;
+                if toolbar_button(ui, is_idle, &self.icons.scan, &tr!("Scan"), labels)
+                    .on_hover_text(tr!("Scan for devices (Ctrl+R)"))
                     .clicked()
                 {
                     self.scan_devices();
                 }
                 ui.separator();
-                if ui
-                    .add_enabled(is_idle, egui::ImageButton::new(&self.icons.add))
-                    .on_hover_text("Select ISO file")
+                if toolbar_button(ui, is_idle, &self.icons.add, &tr!("Add ISO"), labels)
+                    .on_hover_text(tr!("Select ISO file (Ctrl+O)"))
                     .clicked()
                 {
                     self.select_iso_file();
                 }
-                if ui
-                    .add_enabled(is_idle, egui::ImageButton::new(&self.icons.clear))
-                    .on_hover_text("Clear selections")
+                if toolbar_button(ui, is_idle, &self.icons.clear, &tr!("Clear"), labels)
+                    .on_hover_text(tr!("Clear selections"))
                     .clicked()
                 {
                     self.burn_options.iso_path.clear();
-                    self.selected_device = None;
+                    self.iso_info = None;
+                    self.iso_info_receiver = None;
+                    self.selected_devices.clear();
                     self.devices.clear();
                 }
+                if toolbar_button(
+                    ui,
+                    is_idle && !self.selected_devices.is_empty(),
+                    &self.icons.remove,
+                    &tr!("Remove"),
+                    labels,
+                )
+                .on_hover_text(tr!("Remove selected device(s) from the list (until the next scan)"))
+                .clicked()
+                {
+                    self.remove_selected_from_list();
+                }
                 ui.separator();
-                let can_burn =
-                    self.selected_device.is_some() && !self.burn_options.iso_path.is_empty();
+                let blocker = self.burn_blocker();
+                if toolbar_button(
+                    ui,
+                    self.can_burn() && is_idle,
+                    &self.icons.burn,
+                    &tr!("Burn"),
+                    labels,
+                )
+                .on_hover_text(tr!("Burn to device (Ctrl+Enter)"))
+                .on_disabled_hover_text(blocker.unwrap_or_default())
+                .clicked()
+                {
+                    self.request_burn();
+                }
+                if toolbar_button(
+                    ui,
+                    !is_idle
+                        && (self.burn_jobs.iter().any(|j| !j.is_finished())
+                            || self.device_run_cancel.is_some()),
+                    &self.icons.stop,
+                    &tr!("Stop"),
+                    labels,
+                )
+                .on_hover_text(tr!("Stop operation"))
+                .clicked()
+                {
+                    if let Some(cancel) = &self.device_run_cancel {
+                        cancel.store(true, Ordering::SeqCst);
+                        self.logs.push(
+                            LogLevel::Warn,
+                            "Stopping after the current device; the rest are skipped",
+                        );
+                    } else if self.burn_jobs.len() > 1 {
+                        // Stopping a multi-device run cancels every device, so ask first.
+                        self.show_abort_confirmation = true;
+                    } else {
+                        self.stop_burn();
+                    }
+                }
+                let unfinished = self.burn_jobs.iter().filter(|j| !j.is_finished()).count();
                 if ui
                     .add_enabled(
-                        can_burn && is_idle,
-                        egui::ImageButton::new(&self.icons.burn),
+                        self.burn_jobs.len() > 1 && unfinished > 0,
+                        egui::Button::new(tr!("Abort all")),
                     )
-                    .on_hover_text("Burn to device")
+                    .on_hover_text(tr!("Stop every burn in this run, after confirming"))
                     .clicked()
                 {
-                    self.start_burn();
+                    self.show_abort_confirmation = true;
                 }
+                let paused = self.is_paused();
                 if ui
-                    .add_enabled(!is_idle, egui::ImageButton::new(&self.icons.stop))
-                    .on_hover_text("Stop operation (Not Implemented)")
+                    .add_enabled(
+                        self.status == AppStatus::Burning
+                            && self.burn_jobs.iter().any(BurnJob::is_running),
+                        egui::Button::new(if paused { "▶" } else { "⏸" }),
+                    )
+                    .with_accessible_name(&if paused { tr!("Resume") } else { tr!("Pause") })
+                    .on_hover_text(if paused {
+                        tr!("Resume burning")
+                    } else {
+                        tr!("Pause burning (keeps the device open)")
+                    })
                     .clicked()
                 {
-                    // TODO: Implement stopping logic
+                    self.toggle_pause();
+                }
+
+                if toolbar_button(
+                    ui,
+                    is_idle,
+                    &self.icons.win_iso,
+                    &tr!("Windows ISO"),
+                    labels,
+                )
+                .on_hover_text(tr!("Create Windows ISO"))
+                .clicked()
+                {
+                    self.start_create_win_iso();
                 }
 
+                // Enabled after a finished burn too, so the stick can be ejected right away.
                 if ui
-                    .add_enabled(is_idle, egui::ImageButton::new(&self.icons.win_iso))
-                    .on_hover_text("Create Windows ISO")
+                    .add_enabled(
+                        self.is_idle() && !self.selected_devices.is_empty(),
+                        egui::Button::new("⏏"),
+                    )
+                    .with_accessible_name(&tr!("Eject"))
+                    .on_hover_text(tr!("Eject selected device(s)"))
                     .clicked()
                 {
-                    self.start_create_win_iso();
+                    self.start_eject();
+                }
+                if ui
+                    .add_enabled(
+                        self.is_idle() && !self.selected_devices.is_empty(),
+                        egui::Button::new(tr!("Erase")),
+                    )
+                    .on_hover_text(tr!("Wipe the selected device(s)"))
+                    .clicked()
+                {
+                    self.erase_confirm_input.clear();
+                    self.show_erase_confirmation = true;
+                }
+                ui.separator();
+                if ui
+                    .toggle_value(&mut self.next_device_mode, tr!("Next device"))
+                    .on_hover_text(tr!(
+                        "Assembly line: eject after each successful burn, select the next \
+                         stick as soon as it is plugged in, and press Enter to burn it"
+                    ))
+                    .changed()
+                    && !self.next_device_mode
+                {
+                    self.awaiting_next_device = false;
+                    self.next_device = None;
                 }
             });
             ui.separator();
 
             // --- Main Options Panel ---
-            // We use a horizontal layout to contain our two grids.
-            ui.columns(2, |columns| {
-                // --- Left Section: Primary Options ---
-                egui::Grid::new("primary_options_grid")
-                    .num_columns(2)
-                    .spacing([20.0, 8.0])
-                    .show(&mut columns[0], |ui| {
-                        // Row 1: Threads
-                        ui.label("Threads:");
-                        ui.add(egui::Slider::new(&mut self.burn_options.threads, 1..=16));
-                        ui.end_row();
-
-                        // Row 2: Bootable Options
-                        ui.label("Bootable:");
-                        ui.vertical(|ui| {
-                            if ui
-                                .checkbox(&mut self.burn_options.make_bootable, "Make bootable")
-                                .clicked()
-                                && !self.burn_options.make_bootable
-                            {
-                                // Reset to default if unchecked
-                                self.burn_options.boot_type = BootType::Hybrid;
-                            }
-
-                            // Show ComboBox only if bootable is checked
-                            ui.add_enabled_ui(self.burn_options.make_bootable, |ui| {
-                                egui::ComboBox::from_id_source("boot_type_combo")
-                                    .selected_text(format!("{:?}", self.burn_options.boot_type))
-                                    .show_ui(ui, |ui| {
-                                        ui.selectable_value(
-                                            &mut self.burn_options.boot_type,
-                                            BootType::UEFI,
-                                            "UEFI",
-                                        );
-                                        ui.selectable_value(
-                                            &mut self.burn_options.boot_type,
-                                            BootType::Legacy,
-                                            "Legacy",
-                                        );
-                                        ui.selectable_value(
-                                            &mut self.burn_options.boot_type,
-                                            BootType::Hybrid,
-                                            "Hybrid",
-                                        );
-                                    });
-                            });
-                        });
-                        ui.end_row();
+            if self.compact_mode {
+                // Stacked for small screens, with the advanced grid folded away
+                // and scrolled so the options never push the device list off.
+                egui::ScrollArea::vertical()
+                    .max_height(ctx.screen_rect().height() * 0.4)
+                    .show(ui, |ui| {
+                        self.render_primary_options(ui);
+                        egui::CollapsingHeader::new(tr!("Advanced options"))
+                            .show(ui, |ui| self.render_advanced_options(ui));
                     });
+            } else {
+                // We use a horizontal layout to contain our two grids.
+                ui.columns(2, |columns| {
+                    self.render_primary_options(&mut columns[0]);
+                    self.render_advanced_options(&mut columns[1]);
+                });
+            }
+        });
+    }
 
-                // --- Right Section: Advanced Options ---
-                egui::Grid::new("advanced_options_grid")
-                    .num_columns(2)
-                    .spacing([20.0, 8.0])
-                    .show(&mut columns[1], |ui| {
-                        // Row 1: Verification
-                        ui.label("Verification:");
-                        ui.checkbox(&mut self.burn_options.verify, "Verify after burn");
-                        ui.end_row();
-
-                        // Row 2: Block Size
-                        ui.label("Block Size:");
-                        // A ComboBox is more user-friendly for predefined block sizes.
-                        egui::ComboBox::from_id_source("block_size_combo")
-                            .selected_text(format!("{} KB", self.burn_options.block_size / 1024))
+    /// Renders the primary options: threads and the bootable setup.
+    fn render_primary_options(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("primary_options_grid")
+            .num_columns(2)
+            .spacing([20.0, 8.0])
+            .show(ui, |ui| {
+                // Row 1: Threads (reader pool / read-ahead depth)
+                ui.label(tr!("Threads:")).on_hover_text(i18n::tr(READ_AHEAD_HINT, &[]));
+                ui.add(egui::Slider::new(&mut self.burn_options.threads, 1..=16))
+                    .on_hover_text(i18n::tr(READ_AHEAD_HINT, &[]));
+                ui.end_row();
+
+                // Row 2: Bootable Options
+                ui.label(tr!("Bootable:"));
+                ui.vertical(|ui| {
+                    if ui
+                        .checkbox(&mut self.burn_options.make_bootable, tr!("Make bootable"))
+                        .clicked()
+                        && !self.burn_options.make_bootable
+                    {
+                        // Reset to default if unchecked
+                        self.burn_options.boot_type = BootType::Hybrid;
+                    }
+
+                    // Show ComboBox only if bootable is checked
+                    ui.add_enabled_ui(self.burn_options.make_bootable, |ui| {
+                        egui::ComboBox::from_id_source("boot_type_combo")
+                            .selected_text(format!("{:?}", self.burn_options.boot_type))
                             .show_ui(ui, |ui| {
                                 ui.selectable_value(
-                                    &mut self.burn_options.block_size,
-                                    512 * 1024,
-                                    "512 KB",
-                                );
-                                ui.selectable_value(
-                                    &mut self.burn_options.block_size,
-                                    1024 * 1024,
-                                    "1 MB",
+                                    &mut self.burn_options.boot_type,
+                                    BootType::UEFI,
+                                    "UEFI",
                                 );
                                 ui.selectable_value(
-                                    &mut self.burn_options.block_size,
-                                    2048 * 1024,
-                                    "2 MB",
+                                    &mut self.burn_options.boot_type,
+                                    BootType::Legacy,
+                                    "Legacy",
                                 );
                                 ui.selectable_value(
-                                    &mut self.burn_options.block_size,
-                                    4096 * 1024,
-                                    "4 MB",
+                                    &mut self.burn_options.boot_type,
+                                    BootType::Hybrid,
+                                    "Hybrid",
                                 );
                             });
-                        ui.end_row();
                     });
-            });
-        });
-    }
+                });
+                ui.end_row();
 
-    /// Initiates the process of creating a Windows ISO in a background thread.
-    fn start_create_win_iso(&mut self) {
-        let source_folder = rfd::FileDialog::new().pick_folder();
-        let save_file = rfd::FileDialog::new()
-            .add_filter("ISO Image", &["iso"])
-            .save_file();
+                // Row 3: Partition table for the bootable setup
+                ui.label(tr!("Partition scheme:"));
+                ui.add_enabled_ui(self.burn_options.make_bootable, |ui| {
+                    egui::ComboBox::from_id_source("partition_scheme_combo")
+                        .selected_text(format!("{:?}", self.burn_options.partition_scheme))
+                        .show_ui(ui, |ui| {
+                            for (scheme, name) in
+                                [(PartitionScheme::Mbr, "MBR"), (PartitionScheme::Gpt, "GPT")]
+                            {
+                                ui.add_enabled_ui(
+                                    scheme.supports(&self.burn_options.boot_type),
+                                    |ui| {
+                                        ui.selectable_value(
+                                            &mut self.burn_options.partition_scheme,
+                                            scheme,
+                                            name,
+                                        )
+                                    },
+                                )
+                                .response
+                                .on_disabled_hover_text(tr!(
+                                    "Legacy BIOS boot needs an MBR partition table"
+                                ));
+                            }
+                        });
+                });
+                // Switching to Legacy leaves GPT behind; fall back to the one valid pick.
+                if !self
+                    .burn_options
+                    .partition_scheme
+                    .supports(&self.burn_options.boot_type)
+                {
+                    self.burn_options.partition_scheme = PartitionScheme::Mbr;
+                }
+                ui.end_row();
 
-        if let (Some(source), Some(output)) = (source_folder, save_file) {
-            let (tx, rx) = mpsc::channel();
-            self.progress_receiver = Some(rx);
-            // Spawn the operation in a new thread to prevent UI freezing.
-            self.operation_thread = Some(thread::spawn(move || {
-                RustBurn::create_win_iso(
-                    source.display().to_string(),
-                    output.display().to_string(),
-                    tx,
-                );
-            }));
-            self.status = AppStatus::CreatingWinIso;
-        }
+                // Row 4: Volume label for the filesystem created by bootable setup
+                ui.label(tr!("Volume label:"));
+                ui.add_enabled_ui(self.burn_options.make_bootable, |ui| {
+                    ui.vertical(|ui| {
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.volume_label_input)
+                                .hint_text(tr!("e.g. UBUNTU_2404"))
+                                .char_limit(FAT32_LABEL_MAX),
+                        );
+                        if let Some(e) = self.volume_label_error() {
+                            ui.colored_label(egui::Color32::RED, e);
+                        }
+                    });
+                });
+                ui.end_row();
+
+                // Row 5: Persistence partition for Debian-family live images
+                ui.label(tr!("Persistence (MB):"));
+                ui.add_enabled_ui(self.persistence_available(), |ui| {
+                    ui.horizontal(|ui| {
+                        let mut enabled = self.burn_options.persistence_mb.is_some();
+                        let mut mb = self
+                            .burn_options
+                            .persistence_mb
+                            .unwrap_or(DEFAULT_PERSISTENCE_MB);
+                        ui.checkbox(&mut enabled, "");
+                        ui.add_enabled(
+                            enabled,
+                            egui::DragValue::new(&mut mb)
+                                .clamp_range(64..=u64::MAX)
+                                .speed(64),
+                        );
+                        self.burn_options.persistence_mb = enabled.then_some(mb);
+                    })
+                })
+                .response
+                .on_hover_text(tr!(
+                    "Add a casper-rw/persistence partition in the leftover space \
+                     (bootable Ubuntu/Debian live images only)"
+                ));
+                ui.end_row();
+
+                // Row 6: Files copied onto the new volume, e.g. for unattended installs
+                ui.label(tr!("Extra files:"));
+                ui.add_enabled_ui(self.burn_options.make_bootable, |ui| {
+                    ui.vertical(|ui| {
+                        let mut remove = None;
+                        for (i, (source, dest)) in
+                            self.burn_options.extra_files.iter_mut().enumerate()
+                        {
+                            ui.horizontal(|ui| {
+                                let name = source
+                                    .file_name()
+                                    .map_or_else(String::new, |n| n.to_string_lossy().into_owned());
+                                ui.label(name)
+                                    .on_hover_text(source.display().to_string());
+                                ui.label("→");
+                                ui.add(egui::TextEdit::singleline(dest).desired_width(140.0))
+                                    .on_hover_text(tr!("Destination path on the burned volume"));
+                                if ui.small_button("✖").clicked() {
+                                    remove = Some(i);
+                                }
+                            });
+                        }
+                        if let Some(i) = remove {
+                            self.burn_options.extra_files.remove(i);
+                        }
+                        if ui
+                            .button(tr!("Add file..."))
+                            .on_hover_text(tr!(
+                                "Copy a file such as autounattend.xml, a preseed or a \
+                                 kickstart onto the volume after bootable setup"
+                            ))
+                            .clicked()
+                        {
+                            for source in rfd::FileDialog::new().pick_files().unwrap_or_default()
+                            {
+                                let dest = source
+                                    .file_name()
+                                    .map_or_else(String::new, |n| {
+                                        format!("/{}", n.to_string_lossy())
+                                    });
+                                self.burn_options.extra_files.push((source, dest));
+                            }
+                        }
+                    });
+                });
+                ui.end_row();
+            });
     }
 
-    // Add these two new functions inside the `impl RustBurnApp` block.
+    /// Renders the advanced options: write mode, verification, write tuning and
+    /// post-burn actions.
+    fn render_advanced_options(&mut self, ui: &mut egui::Ui) {
+        egui::Grid::new("advanced_options_grid")
+            .num_columns(2)
+            .spacing([20.0, 8.0])
+            .show(ui, |ui| {
+                // Row 1: Write mode (raw block copy or file copy)
+                ui.label(tr!("Write mode:"));
+                let inferred = self.iso_info.as_ref().map(|info| info.write_mode);
+                egui::ComboBox::from_id_source("write_mode_combo")
+                    .selected_text(match self.burn_options.write_mode {
+                        None => match inferred {
+                            Some(mode) => tr!("Auto ({})", write_mode_name(mode)),
+                            None => tr!("Auto"),
+                        },
+                        Some(mode) => write_mode_name(mode),
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.burn_options.write_mode, None, tr!("Auto"))
+                            .on_hover_text(tr!("Use the mode the image looks made for"));
+                        ui.selectable_value(
+                            &mut self.burn_options.write_mode,
+                            Some(WriteMode::RawDd),
+                            write_mode_name(WriteMode::RawDd),
+                        )
+                        .on_hover_text(tr!(
+                            "Copy the image block for block. Right for hybrid ISOs and \
+                             most Linux images"
+                        ));
+                        ui.selectable_value(
+                            &mut self.burn_options.write_mode,
+                            Some(WriteMode::FileCopy),
+                            write_mode_name(WriteMode::FileCopy),
+                        )
+                        .on_hover_text(tr!(
+                            "Format the device as FAT32 and copy the image's files onto it; \
+                             it boots on UEFI only. Right for Windows installers"
+                        ));
+                    });
+                ui.end_row();
 
-    /// Detects when files are hovered or dropped onto the window.
-    fn detect_drag_and_drop(&mut self, ctx: &egui::Context) {
-        // First, check for dropped files.
+                // Row 2: Verification
+                ui.label(tr!("Verification:"));
+                let file_copy = self.is_file_copy();
+                ui.vertical(|ui| {
+                    ui.checkbox(&mut self.burn_options.verify, tr!("Verify after burn"))
+                        .on_hover_text(if file_copy {
+                            tr!(
+                                "Mount the new volume afresh and compare every copied file \
+                                 with the image's"
+                            )
+                        } else {
+                            tr!(
+                                "Read back the bytes the image occupies; space past the \
+                                 end of the image is not checked"
+                            )
+                        });
+                    // A file copy is checked file by file, so none of these apply.
+                    ui.add_enabled_ui(self.burn_options.verify && !file_copy, |ui| {
+                        egui::ComboBox::from_id_source("verify_mode_combo")
+                            .selected_text(match self.burn_options.verify_mode {
+                                VerifyMode::ByteCompare => tr!("Byte compare"),
+                                VerifyMode::HashCompare => tr!("Hash compare"),
+                                VerifyMode::Sample { fraction } => {
+                                    tr!("Sample ({}%)", format!("{:.0}", fraction * 100.0))
+                                }
+                            })
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(
+                                    &mut self.burn_options.verify_mode,
+                                    VerifyMode::ByteCompare,
+                                    tr!("Byte compare"),
+                                )
+                                .on_hover_text(tr!(
+                                    "Re-read the written region and compare every byte"
+                                ));
+                                ui.selectable_value(
+                                    &mut self.burn_options.verify_mode,
+                                    VerifyMode::HashCompare,
+                                    tr!("Hash compare"),
+                                )
+                                .on_hover_text(tr!(
+                                    "Hash the written region and compare it to the ISO hash \
+                                     taken while writing"
+                                ));
+                                let sampling = matches!(
+                                    self.burn_options.verify_mode,
+                                    VerifyMode::Sample { .. }
+                                );
+                                if ui
+                                    .selectable_label(sampling, tr!("Sample"))
+                                    .on_hover_text(tr!(
+                                        "Read back a reproducible random subset of blocks spread \
+                                         across the image: quicker, but weaker than a full check"
+                                    ))
+                                    .clicked()
+                                    && !sampling
+                                {
+                                    self.burn_options.verify_mode = VerifyMode::Sample {
+                                        fraction: DEFAULT_SAMPLE_FRACTION,
+                                    };
+                                }
+                            });
+                        if let VerifyMode::Sample { fraction } =
+                            &mut self.burn_options.verify_mode
+                        {
+                            let mut percent = *fraction * 100.0;
+                            ui.horizontal(|ui| {
+                                ui.label(tr!("Blocks checked:"));
+                                ui.add(
+                                    egui::DragValue::new(&mut percent)
+                                        .clamp_range(1.0..=50.0)
+                                        .suffix("%"),
+                                );
+                            });
+                            *fraction = percent / 100.0;
+                        }
+                        ui.checkbox(
+                            &mut self.burn_options.verify_skip_trailing_zeros,
+                            tr!("Skip trailing padding"),
+                        )
+                        .on_hover_text(tr!(
+                            "Stop at the end of the ISO's declared volume instead of \
+                             checking the zero padding after it"
+                        ));
+                        ui.checkbox(&mut self.burn_options.direct_verify, tr!("Bypass OS cache"))
+                            .on_hover_text(tr!(
+                                "Read back with direct IO (O_DIRECT / FILE_FLAG_NO_BUFFERING) so \
+                                 verify sees the device, not the page cache. Falls back to \
+                                 dropping the cache where direct IO isn't available"
+                            ));
+                    });
+                });
+                ui.end_row();
+
+                // Row 3: Bad-block / fake-capacity pass and stale partition tables
+                ui.label(tr!("Before burn:"));
+                ui.vertical(|ui| {
+                    ui.checkbox(&mut self.burn_options.bad_block_check, tr!("Check for bad blocks"))
+                        .on_hover_text(tr!(
+                            "Write a test pattern across the whole device and read it back, \
+                             to catch fake-capacity sticks (slow)"
+                        ));
+                    ui.checkbox(
+                        &mut self.burn_options.wipe_table_first,
+                        tr!("Clear old partition table"),
+                    )
+                    .on_hover_text(tr!(
+                        "Zero the first MB and the backup GPT at the end of the device, so \
+                         a smaller image doesn't leave the old layout's partitions behind"
+                    ));
+                });
+                ui.end_row();
+
+                // Row 4: Block Size
+                ui.label(tr!("Block Size:"));
+                ui.horizontal(|ui| {
+                    // A ComboBox is more user-friendly for predefined block sizes.
+                    let selected_text = if self.block_size_input.is_some() {
+                        tr!("Custom…")
+                    } else if self.burn_options.block_size == AUTO_BLOCK_SIZE {
+                        tr!("Auto")
+                    } else {
+                        format!("{} KB", self.burn_options.block_size / 1024)
+                    };
+                    egui::ComboBox::from_id_source("block_size_combo")
+                        .selected_text(selected_text)
+                        .show_ui(ui, |ui| {
+                            let auto = self.block_size_input.is_none()
+                                && self.burn_options.block_size == AUTO_BLOCK_SIZE;
+                            if ui
+                                .selectable_label(auto, tr!("Auto"))
+                                .on_hover_text(tr!(
+                                    "Time a few sizes on the device before writing \
+                                     and use the fastest"
+                                ))
+                                .clicked()
+                            {
+                                self.burn_options.block_size = AUTO_BLOCK_SIZE;
+                                self.block_size_input = None;
+                            }
+                            for kb in BLOCK_SIZE_PRESETS_KB {
+                                let label = if kb >= 1024 {
+                                    format!("{} MB", kb / 1024)
+                                } else {
+                                    format!("{} KB", kb)
+                                };
+                                let selected = self.block_size_input.is_none()
+                                    && self.burn_options.block_size == kb * 1024;
+                                if ui.selectable_label(selected, label).clicked() {
+                                    self.burn_options.block_size = kb * 1024;
+                                    self.block_size_input = None;
+                                }
+                            }
+                            if ui
+                                .selectable_label(
+                                    self.block_size_input.is_some(),
+                                    tr!("Custom…"),
+                                )
+                                .clicked()
+                                && self.block_size_input.is_none()
+                            {
+                                // Auto has no size of its own to start from.
+                                let kb = match self.burn_options.block_size {
+                                    AUTO_BLOCK_SIZE => 1024,
+                                    size => size / 1024,
+                                };
+                                self.block_size_input = Some(kb.to_string());
+                            }
+                        });
+                    if let Some(input) = &mut self.block_size_input {
+                        ui.add(egui::TextEdit::singleline(input).desired_width(60.0));
+                        ui.label("KB");
+                    }
+                    // Only a valid size reaches the options; an invalid one
+                    // keeps the last good value and blocks the burn.
+                    match self.block_size_error() {
+                        Some(e) => {
+                            ui.colored_label(egui::Color32::RED, e);
+                        }
+                        None => {
+                            if let Some(bytes) = self
+                                .block_size_input
+                                .as_deref()
+                                .and_then(|i| i.trim().parse::<usize>().ok())
+                                .and_then(|kb| kb.checked_mul(1024))
+                            {
+                                self.burn_options.block_size = bytes;
+                            }
+                        }
+                    }
+                });
+                ui.end_row();
+
+                // Row 5: Retries for transient write errors
+                ui.label(tr!("Write retries:"));
+                ui.add(egui::DragValue::new(&mut self.burn_options.max_retries).clamp_range(0..=10))
+                    .on_hover_text(tr!(
+                        "Retry a failed block write this many times, with a short backoff, \
+                         before giving up on the burn"
+                    ));
+                ui.end_row();
+
+                // Row 6: Forced sync interval
+                ui.label(tr!("Sync every (MB):"));
+                ui.add(
+                    egui::DragValue::new(&mut self.burn_options.sync_every_mb)
+                        .clamp_range(1..=4096),
+                )
+                .on_hover_text(tr!(
+                    "Flush the device to disk after this much data. Smaller values lose \
+                     less on a crash or power cut, and resume checkpoints land on these \
+                     sync points, at some cost in speed"
+                ));
+                ui.end_row();
+
+                // Row 7: Write-speed throttle
+                ui.label(tr!("Limit speed (MB/s):"));
+                ui.horizontal(|ui| {
+                    let mut limited = self.burn_options.max_write_bytes_per_sec.is_some();
+                    let mut mb_per_sec = self
+                        .burn_options
+                        .max_write_bytes_per_sec
+                        .map_or(DEFAULT_SPEED_LIMIT_MB, |b| b / 1_000_000);
+                    ui.checkbox(&mut limited, "")
+                        .on_hover_text(tr!(
+                            "Cap the write rate so other devices on a shared hub keep working"
+                        ));
+                    ui.add_enabled(
+                        limited,
+                        egui::DragValue::new(&mut mb_per_sec).clamp_range(1..=1000),
+                    );
+                    self.burn_options.max_write_bytes_per_sec =
+                        limited.then(|| mb_per_sec * 1_000_000);
+                });
+                ui.end_row();
+
+                // Row 8: Mounted targets
+                ui.label(tr!("If mounted:"));
+                ui.checkbox(
+                    &mut self.burn_options.auto_unmount,
+                    tr!("Unmount automatically"),
+                )
+                .on_hover_text(tr!(
+                    "Unmount the target's filesystems before writing; otherwise a \
+                     mounted target stops the burn"
+                ));
+                ui.end_row();
+
+                // Row 9: Post-burn actions
+                ui.label(tr!("When done:"));
+                ui.vertical(|ui| {
+                    ui.checkbox(&mut self.burn_options.auto_eject, tr!("Eject device"))
+                        .on_hover_text(tr!(
+                            "Flush, sync and eject once the burn (and verify) succeeds"
+                        ));
+                    ui.add_enabled(
+                        self.burn_options.auto_eject,
+                        egui::Checkbox::new(
+                            &mut self.burn_options.reverify_after_eject,
+                            tr!("Re-check after re-detect"),
+                        ),
+                    )
+                    .on_hover_text(tr!(
+                        "Wait for the ejected device to show up again and verify it from a \
+                         fresh open, so a USB bridge's cache can't pass a corrupt write"
+                    ));
+                    ui.checkbox(
+                        &mut self.burn_options.write_sidecar,
+                        tr!("Write .sha256 record"),
+                    )
+                    .on_hover_text(tr!(
+                        "Save <image>.burned.sha256 next to the image with its hash, \
+                         the device serial and the time, as an audit trail"
+                    ));
+                });
+                ui.end_row();
+
+                // Row 10: Completion hook
+                ui.label(tr!("On success run:"))
+                    .on_hover_text(i18n::tr(COMPLETE_COMMAND_HINT, &[]));
+                let mut command = self
+                    .burn_options
+                    .on_complete_command
+                    .clone()
+                    .unwrap_or_default();
+                if ui
+                    .add(
+                        egui::TextEdit::singleline(&mut command)
+                            .hint_text(tr!("shell command (optional)"))
+                            .desired_width(220.0),
+                    )
+                    .on_hover_text(i18n::tr(COMPLETE_COMMAND_HINT, &[]))
+                    .changed()
+                {
+                    self.burn_options.on_complete_command =
+                        (!command.trim().is_empty()).then(|| command);
+                }
+                ui.end_row();
+
+                // Row 11: Dry run
+                ui.label(tr!("Dry run:"));
+                ui.checkbox(&mut self.burn_options.dry_run, tr!("Validate only"))
+                    .on_hover_text(tr!(
+                        "Open the device, check size, hash the ISO and check permissions, \
+                         but skip every write and log what would have been done"
+                    ));
+                ui.end_row();
+            });
+    }
+
+    /// Initiates the process of creating a Windows ISO in a background thread.
+    fn start_create_win_iso(&mut self) {
+        let source_folder = match rfd::FileDialog::new().pick_folder() {
+            Some(folder) => folder,
+            None => return,
+        };
+        // Checked before asking where to save, so a wrong pick costs one click, not a packing run.
+        if !looks_like_windows_media(&source_folder) {
+            let e = "Selected folder doesn't look like Windows installation media".to_string();
+            self.logs.push(
+                LogLevel::Error,
+                format!(
+                    "ERROR: {} (expected sources/install.wim or install.esd, boot/ and efi/ in {})",
+                    e,
+                    source_folder.display()
+                ),
+            );
+            self.status = AppStatus::Error(BurnError::Other(e));
+            return;
+        }
+        let source_folder = Some(source_folder);
+        let save_file = rfd::FileDialog::new()
+            .add_filter("ISO Image", &["iso"])
+            .save_file();
+
+        if let (Some(source), Some(output)) = (source_folder, save_file) {
+            let work_dir = self.work_dir().display().to_string();
+            let (tx, rx) = mpsc::channel();
+            self.progress_receiver = Some(rx);
+            // Spawn the operation in a new thread to prevent UI freezing.
+            self.operation_thread = Some(thread::spawn(move || {
+                RustBurn::create_win_iso(
+                    source.display().to_string(),
+                    output.display().to_string(),
+                    work_dir,
+                    tx,
+                );
+            }));
+            self.begin_operation(AppStatus::CreatingWinIso);
+        }
+    }
+
+    /// Launches QEMU on the selected device, or on the ISO when no single
+    /// device is selected, with firmware matching the chosen boot type. The
+    /// VM runs on its own; only a failure to start it is reported.
+    fn start_test_boot(&mut self) {
+        let target = match self.selected_devices.as_slice() {
+            [device] => device.clone(),
+            _ => self.burn_options.iso_path.clone(),
+        };
+        if target.is_empty() {
+            return;
+        }
+        match RustBurn::test_boot(&target, &self.burn_options.boot_type) {
+            Ok(()) => self.logs.push(
+                LogLevel::Info,
+                format!(
+                    "Started QEMU ({:?} boot) for {}",
+                    self.burn_options.boot_type, target
+                ),
+            ),
+            Err(e) => self
+                .logs
+                .push(LogLevel::Error, format!("ERROR: Could not start QEMU: {}", e)),
+        }
+    }
+
+    /// Asks for the folder to pack, then opens the ISO options window for it.
+    fn pick_create_iso_source(&mut self) {
+        if let Some(folder) = rfd::FileDialog::new().pick_folder() {
+            // ISO9660 labels are upper case; the folder name is a sensible default.
+            self.create_iso_label = folder
+                .file_name()
+                .map(|n| n.to_string_lossy().to_uppercase())
+                .unwrap_or_default()
+                .chars()
+                .take(ISO_LABEL_MAX)
+                .collect();
+            self.create_iso_source = Some(folder);
+        }
+    }
+
+    /// Packs the picked folder into an ISO9660/Joliet image in a background thread.
+    fn start_create_iso(&mut self) {
+        let source = match self.create_iso_source.take() {
+            Some(source) => source,
+            None => return,
+        };
+        let output = match rfd::FileDialog::new()
+            .add_filter("ISO Image", &["iso"])
+            .set_file_name(format!("{}.iso", self.create_iso_label.to_lowercase()))
+            .save_file()
+        {
+            Some(output) => output,
+            None => return,
+        };
+        let label = self.create_iso_label.trim().to_string();
+        let boot = self
+            .create_iso_bootable
+            .then(|| self.create_iso_boot_type.clone());
+        let (tx, rx) = mpsc::channel();
+        self.progress_receiver = Some(rx);
+        self.operation_thread = Some(thread::spawn(move || {
+            RustBurn::create_iso(
+                source.display().to_string(),
+                output.display().to_string(),
+                label,
+                boot,
+                tx,
+            );
+        }));
+        self.begin_operation(AppStatus::CreatingIso);
+    }
+
+    /// Hashes a picked file with `algo` in a background thread, using the same
+    /// streaming hasher as the pre-burn checksum.
+    fn start_hash_file(&mut self, algo: ChecksumAlgo, name: &'static str) {
+        if let Some(path) = rfd::FileDialog::new().pick_file() {
+            let path = path.display().to_string();
+            let (tx, rx) = mpsc::channel();
+            self.progress_receiver = Some(rx);
+            self.file_hash = Some(FileHash {
+                path: path.clone(),
+                algo: name,
+                hash: None,
+            });
+            self.operation_thread = Some(thread::spawn(move || {
+                RustBurn::hash_file(path, algo, tx);
+            }));
+            self.begin_operation(AppStatus::Hashing);
+        }
+    }
+
+    /// Downloads the ISO at the entered URL into the temp dir in a background thread.
+    /// Once it is `Done` the file becomes the selected ISO, and the entered hash
+    /// is checked by the burn's pre-burn checksum.
+    fn start_download(&mut self) {
+        let url = self.download_url_input.trim().to_string();
+        let dest = self
+            .work_dir()
+            .join("rustburn")
+            .join(download_file_name(&url))
+            .display()
+            .to_string();
+        // No hash entered leaves any checksum the user already typed in place.
+        let hash = self.download_hash_input.trim();
+        if !hash.is_empty() {
+            self.checksum_input = hash.to_string();
+        }
+
+        let (tx, rx) = mpsc::channel();
+        self.progress_receiver = Some(rx);
+        self.download_dest = Some(dest.clone());
+        self.operation_thread = Some(thread::spawn(move || {
+            RustBurn::fetch_iso(url, dest, tx);
+        }));
+        self.begin_operation(AppStatus::Downloading);
+    }
+
+    /// Reads the selected device into an image file in a background thread.
+    /// A `.gz` output name makes the core gzip the image as it goes.
+    fn start_clone_to_image(&mut self) {
+        let device = match self.selected_devices.as_slice() {
+            [device] => device.clone(),
+            _ => return,
+        };
+        if let Some(output) = rfd::FileDialog::new()
+            .add_filter("Raw Image", &["img"])
+            .add_filter("Compressed Image", &["gz"])
+            .set_file_name("device.img")
+            .save_file()
+        {
+            let (tx, rx) = mpsc::channel();
+            self.progress_receiver = Some(rx);
+            self.operation_thread = Some(thread::spawn(move || {
+                RustBurn::clone_to_image(device, output.display().to_string(), tx);
+            }));
+            self.begin_operation(AppStatus::Cloning);
+        }
+    }
+
+    /// Shows the selected image's volume label, OS family and boot support,
+    /// and offers the matching `BootType` when it differs from the chosen one.
+    fn render_iso_info(&mut self, ui: &mut egui::Ui) {
+        let info = match &self.iso_info {
+            Some(info) => info,
+            None => return,
+        };
+        let os = os_family_name(&info.os_family);
+        let arch = match info.arch {
+            IsoArch::X86 => " (x86)",
+            IsoArch::X86_64 => " (x86-64)",
+            IsoArch::Arm64 => " (ARM64)",
+            IsoArch::Unknown => "",
+        };
+        let mut boot = Vec::new();
+        if info.bios_bootable {
+            boot.push("BIOS");
+        }
+        if info.uefi_bootable {
+            boot.push("UEFI");
+        }
+        if info.is_hybrid {
+            boot.push("ISOhybrid");
+        }
+        let boot = if boot.is_empty() {
+            "not bootable".to_string()
+        } else {
+            boot.join(" + ")
+        };
+        let label = if info.volume_label.is_empty() {
+            "(no label)"
+        } else {
+            info.volume_label.as_str()
+        };
+        let suggested = match (info.bios_bootable, info.uefi_bootable) {
+            (true, true) => Some(BootType::Hybrid),
+            (false, true) => Some(BootType::UEFI),
+            (true, false) => Some(BootType::Legacy),
+            (false, false) => None,
+        };
+        let mut apply = None;
+        reading_row(ui, |ui| {
+            ui.label(format!("{} · {}{} · {}", label, os, arch, boot));
+            if let Some(boot_type) = suggested {
+                let differs = !self.burn_options.make_bootable
+                    || self.burn_options.boot_type != boot_type;
+                if differs
+                    && ui
+                        .small_button(tr!("Use {} boot", format!("{:?}", boot_type)))
+                        .on_hover_text(tr!(
+                            "Make the device bootable with the type this image supports"
+                        ))
+                        .clicked()
+                {
+                    apply = Some(boot_type);
+                }
+            }
+        });
+        if let Some(warning) = self.boot_mismatch_warning() {
+            ui.colored_label(egui::Color32::YELLOW, format!("⚠ {}", warning));
+        }
+        if let Some(boot_type) = apply {
+            self.burn_options.make_bootable = true;
+            self.burn_options.boot_type = boot_type;
+        }
+    }
+
+    /// Flags a chosen boot type the selected image likely can't use. Only a
+    /// warning: the image inspection can be wrong, and the user may know better.
+    fn boot_mismatch_warning(&self) -> Option<String> {
+        let info = self.iso_info.as_ref()?;
+        if !self.burn_options.make_bootable {
+            return None;
+        }
+        let arch = match info.arch {
+            IsoArch::Arm64 => Some("ARM64"),
+            _ => None,
+        };
+        match (&self.burn_options.boot_type, arch) {
+            (BootType::Legacy | BootType::Hybrid, Some(arch)) => Some(tr!(
+                "This is an {} image, but Legacy BIOS boot only exists on x86 PCs",
+                arch
+            )),
+            (BootType::Legacy, None) if !info.bios_bootable => Some(tr!(
+                "This image has no BIOS boot loader; Legacy boot will likely fail"
+            )),
+            (BootType::UEFI, _) if !info.uefi_bootable => Some(tr!(
+                "This image has no UEFI boot loader; UEFI boot will likely fail"
+            )),
+            _ => None,
+        }
+    }
+
+    // Add these two new functions inside the `impl RustBurnApp` block.
+
+    /// Detects when files are hovered or dropped onto the window.
+    fn detect_drag_and_drop(&mut self, ctx: &egui::Context) {
+        // First, check for dropped files.
         if !ctx.input(|i| i.raw.dropped_files.is_empty()) {
             let files = ctx.input(|i| i.raw.dropped_files.clone());
             self.is_file_hovering = false; // The hover is over once a file is dropped.
 
-            // Find the first valid .iso file from the dropped files.
-            if let Some(file) = files.iter().find(|f| {
-                if let Some(path) = &f.path {
-                    return path.extension().map_or(false, |ext| ext == "iso");
+            let mut paths = files
+                .iter()
+                .filter_map(|f| f.path.as_deref())
+                .filter(|p| is_supported_image(p))
+                .map(|p| p.display().to_string());
+            // While nothing runs the first image replaces the selected one and
+            // the rest wait their turn; during a run every image waits.
+            if !self.is_queue_active() {
+                if let Some(path) = paths.next() {
+                    self.set_iso_path(path);
                 }
-                false
-            }) {
-                if let Some(path) = &file.path {
-                    self.burn_options.iso_path = path.display().to_string();
+            }
+            for path in paths {
+                if path != self.burn_options.iso_path && !self.image_queue.contains(&path) {
+                    self.logs.push(LogLevel::Info, format!("Queued {}", path));
+                    self.image_queue.push_back(path);
                 }
             }
             return; // Stop processing to avoid flicker.
@@ -382,6 +2061,51 @@ impl RustBurnApp {
         }
     }
 
+    /// Whether dropped images go to the queue instead of replacing the
+    /// selected one: an operation is running.
+    fn is_queue_active(&self) -> bool {
+        !self.is_idle()
+    }
+
+    /// Lists the images waiting in the queue, with a way to drop them.
+    fn render_image_queue(&mut self, ui: &mut egui::Ui) {
+        if self.image_queue.is_empty() {
+            return;
+        }
+        reading_row(ui, |ui| {
+            ui.label(tr!("Queued next ({}):", self.image_queue.len()));
+            if ui.small_button(tr!("Clear")).clicked() {
+                self.image_queue.clear();
+            }
+        });
+        let mut remove = None;
+        for (i, path) in self.image_queue.iter().enumerate() {
+            reading_row(ui, |ui| {
+                let name = Path::new(path)
+                    .file_name()
+                    .map_or_else(|| path.clone(), |n| n.to_string_lossy().into_owned());
+                ui.label(format!("{}. {}", i + 1, name)).on_hover_text(path.as_str());
+                if ui.small_button("✖").on_hover_text(tr!("Remove from queue")).clicked() {
+                    remove = Some(i);
+                }
+            });
+        }
+        if let Some(i) = remove {
+            self.image_queue.remove(i);
+        }
+    }
+
+    /// Selects the next queued image once a run has finished successfully.
+    fn advance_image_queue(&mut self) {
+        if let Some(path) = self.image_queue.pop_front() {
+            self.logs
+                .push(LogLevel::Info, format!("Next image in queue: {}", path));
+            // The expected checksum belonged to the image just burned.
+            self.checksum_input.clear();
+            self.set_iso_path(path);
+        }
+    }
+
     /// Renders a semi-transparent overlay when a file is being hovered over the window.
     fn render_drag_and_drop_overlay(&mut self, ctx: &egui::Context) {
         if !self.is_file_hovering {
@@ -406,169 +2130,2283 @@ impl RustBurnApp {
         painter.text(
             screen_rect.center(),
             egui::Align2::CENTER_CENTER,
-            "Drop ISO file here",
+            if self.is_queue_active() {
+                "Drop ISOs to queue"
+            } else {
+                "Drop image file here"
+            },
             egui::FontId::proportional(40.0),
             egui::Color32::WHITE,
         );
     }
 
-    // In rustburn-gui/src/main.rs, replace the existing render_about_window function.
-    fn render_about_window(&mut self, ctx: &egui::Context) {
-        // The .open() method handles the closing logic for us,
-        // which resolves the double borrow error.
-        egui::Window::new("About RustBurn Professional")
-            .open(&mut self.show_about_window)
+    /// Renders the confirmation dialog that must be accepted before a burn starts.
+    fn render_burn_confirmation(&mut self, ctx: &egui::Context) {
+        if !self.show_burn_confirmation {
+            return;
+        }
+        let devices = self.selected_usb_devices();
+        if devices.is_empty() {
+            self.show_burn_confirmation = false;
+            return;
+        }
+        let device_lines: Vec<String> = devices
+            .iter()
+            .map(|d| device_label(d, self.size_units))
+            .collect();
+        let needs_typed_confirm = devices.iter().any(|d| d.size > ERASE_CONFIRM_THRESHOLD);
+        let has_partition_target = devices.iter().any(|d| self.is_partition(&d.device));
+        let mounted: Vec<String> = devices.iter().flat_map(|d| mount_points(d)).collect();
+        let source_disk = self.source_disk.clone();
+        let existing: Vec<String> = devices
+            .iter()
+            .filter_map(|d| Some(format!("{} ({})", d.device, existing_media(d)?)))
+            .collect();
+        // Keeping a partition is offered for a single whole-device target only,
+        // and only for a DD copy; a file copy reformats the whole device.
+        let preservable: Vec<(String, u64)> = match devices.as_slice() {
+            [device] if !self.burn_options.dry_run && !self.is_file_copy() => device
+                .partitions
+                .iter()
+                .map(|p| (p.device.clone(), p.size))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let preserve_error = self.preserve_partition.as_ref().and_then(|partition| {
+            let size = preservable.iter().find(|(p, _)| p == partition)?.1;
+            // A compressed image's written size is only known once it is
+            // decompressed, so there is no telling whether the partition fits.
+            let iso_size = match image_size(Path::new(&self.burn_options.iso_path)) {
+                Some(iso_size) => iso_size,
+                None => {
+                    return Some(tr!(
+                        "A partition can't be kept when burning a compressed image"
+                    ))
+                }
+            };
+            // The partitions the burn itself adds take their share of the space first.
+            let persistence = match self.burn_options.persistence_mb {
+                Some(mb) if self.persistence_available() => mb * 1024 * 1024,
+                _ => 0,
+            };
+            let bootable = if self.burn_options.make_bootable {
+                BOOTABLE_PARTITION_RESERVE
+            } else {
+                0
+            };
+            let free = devices[0]
+                .size
+                .saturating_sub(iso_size + persistence + bootable);
+            if size > free {
+                return Some(tr!(
+                    "{} ({}) won't fit in the {} the burn leaves free",
+                    partition,
+                    format_size(size, self.size_units),
+                    format_size(free, self.size_units)
+                ));
+            }
+            match &self.preserve_temp_free {
+                Some(Ok(temp_free)) if size > *temp_free => Some(tr!(
+                    "The temp directory has {} free, too little to back up {} ({})",
+                    format_size(*temp_free, self.size_units),
+                    partition,
+                    format_size(size, self.size_units)
+                )),
+                Some(Err(e)) => Some(tr!("Temp directory: {}", e)),
+                _ => None,
+            }
+        });
+        let iso_name = Path::new(&self.burn_options.iso_path)
+            .file_name()
+            .map_or_else(
+                || self.burn_options.iso_path.clone(),
+                |n| n.to_string_lossy().into_owned(),
+            );
+
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(tr!("Confirm Burn"))
             .collapsible(false)
             .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
             .show(ctx, |ui| {
-                ui.vertical_centered(|ui| {
-                    ui.heading("RustBurn Professional");
-                    ui.label(format!("Version: {}", env!("CARGO_PKG_VERSION")));
-                    ui.hyperlink("https://github.com/56tytt");
+                ui.with_layout(reading_layout(), |ui| {
+                    ui.label(if device_lines.len() == 1 {
+                        tr!("All data on this device will be destroyed:")
+                    } else {
+                        tr!("All data on these {} devices will be destroyed:", device_lines.len())
+                    });
+                    for line in &device_lines {
+                        ui.label(egui::RichText::new(line).strong());
+                    }
+                    if has_partition_target {
+                        ui.colored_label(
+                            egui::Color32::YELLOW,
+                            tr!(
+                                "Partition targets get the raw image only: the partition table \
+                                 and bootable setup are skipped, so they may not boot."
+                            ),
+                        );
+                    }
+                    if !mounted.is_empty() {
+                        ui.colored_label(
+                            egui::Color32::YELLOW,
+                            tr!(
+                                "Device is mounted at {}. Unmount and continue?",
+                                mounted.join(", ")
+                            ),
+                        );
+                    }
+                    ui.add_space(4.0);
+                    ui.label(tr!("Image to write: {}", iso_name));
+                    if self.burn_options.dry_run {
+                        ui.label(tr!(
+                            "Dry run: the device is checked but nothing is written."
+                        ));
+                    }
+                    if let Some(warning) = self.boot_mismatch_warning() {
+                        ui.colored_label(egui::Color32::YELLOW, warning);
+                    }
+                    if !self.checkpoints.is_empty() {
+                        ui.add_space(4.0);
+                        for (device, offset) in &self.checkpoints {
+                            let prompt = if self.checkpoints.len() == 1 {
+                                tr!(
+                                    "Resume previous burn at {}?",
+                                    format_size(*offset, self.size_units)
+                                )
+                            } else {
+                                tr!(
+                                    "{}: resume previous burn at {}?",
+                                    device,
+                                    format_size(*offset, self.size_units)
+                                )
+                            };
+                            ui.label(prompt);
+                        }
+                        ui.checkbox(&mut self.resume_checkpoints, tr!("Resume"))
+                            .on_hover_text(tr!(
+                                "The already-written part is re-verified first; \
+                                 the burn starts over if it no longer matches."
+                            ));
+                    }
+                    if let Some(device) = &source_disk {
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            format!("{}: {}", device, i18n::tr(SOURCE_ON_TARGET_MESSAGE, &[])),
+                        );
+                    }
+                    if !existing.is_empty() {
+                        ui.colored_label(
+                            egui::Color32::YELLOW,
+                            tr!(
+                                "Already contains bootable media: {}. Overwrite?",
+                                existing.join(", ")
+                            ),
+                        );
+                    }
+                    if !preservable.is_empty() {
+                        ui.add_space(4.0);
+                        let mut preserve = self.preserve_partition.is_some();
+                        if ui
+                            .checkbox(&mut preserve, tr!("Keep a data partition"))
+                            .on_hover_text(tr!(
+                                "Back the partition up to the temp directory, burn, then \
+                                 re-create it in the space left after the image and restore it"
+                            ))
+                            .changed()
+                        {
+                            self.preserve_partition =
+                                preserve.then(|| preservable[0].0.clone());
+                            self.preserve_temp_free =
+                                preserve.then(|| check_work_dir(&self.work_dir()));
+                        }
+                        if let Some(selected) = &mut self.preserve_partition {
+                            egui::ComboBox::from_id_source("preserve_partition_combo")
+                                .selected_text(selected.as_str())
+                                .show_ui(ui, |ui| {
+                                    for (partition, size) in &preservable {
+                                        ui.selectable_value(
+                                            selected,
+                                            partition.clone(),
+                                            format!(
+                                                "{} - {}",
+                                                partition,
+                                                format_size(*size, self.size_units)
+                                            ),
+                                        );
+                                    }
+                                });
+                        }
+                    }
+                    if let Some(e) = &preserve_error {
+                        ui.colored_label(egui::Color32::RED, e);
+                    }
+                    ui.separator();
+                    if needs_typed_confirm {
+                        ui.label(tr!("A target is a large device. Type ERASE to confirm:"));
+                        ui.text_edit_singleline(&mut self.erase_confirm_input);
+                    }
+                    let can_confirm = source_disk.is_none()
+                        && preserve_error.is_none()
+                        && (!needs_typed_confirm || self.erase_confirm_input.trim() == "ERASE");
+                    reading_row(ui, |ui| {
+                        let label = if mounted.is_empty() {
+                            tr!("Burn")
+                        } else {
+                            tr!("Unmount and burn")
+                        };
+                        if ui
+                            .add_enabled(can_confirm, egui::Button::new(label))
+                            .clicked()
+                        {
+                            confirmed = true;
+                        }
+                        if ui.button(tr!("Cancel")).clicked() {
+                            cancelled = true;
+                        }
+                    });
                 });
-                ui.separator();
-                ui.label("A professional, multi-threaded ISO burning utility,");
-                ui.label("engineered by our elite software team.");
-                ui.label("Shay Kadosh Software Engineering from Ashkelon")
             });
+
+        if confirmed || cancelled {
+            self.show_burn_confirmation = false;
+            self.erase_confirm_input.clear();
+        }
+        if confirmed {
+            // Agreeing to the dialog is consent to unmount, for this run only.
+            self.unmount_for_run = !mounted.is_empty();
+            self.start_burn();
+        }
     }
 
-    /// Renders the central panel, showing selected ISO and device list.
-    fn render_central_panel(&mut self, ctx: &egui::Context) {
-        egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("1. Selected ISO File");
-            ui.label(if self.burn_options.iso_path.is_empty() {
-                "No file selected."
-            } else {
-                &self.burn_options.iso_path
-            });
-            ui.add_space(10.0);
+    /// Renders the confirmation dialog for wiping the selected devices.
+    fn render_erase_confirmation(&mut self, ctx: &egui::Context) {
+        if !self.show_erase_confirmation {
+            return;
+        }
+        let devices = self.selected_usb_devices();
+        if devices.is_empty() {
+            self.show_erase_confirmation = false;
+            return;
+        }
+        let device_lines: Vec<String> = devices
+            .iter()
+            .map(|d| device_label(d, self.size_units))
+            .collect();
+        let needs_typed_confirm = devices.iter().any(|d| d.size > ERASE_CONFIRM_THRESHOLD);
 
-            ui.heading("2. Select Target Device");
-            ui.separator();
-            egui::ScrollArea::vertical().show(ui, |ui| {
-                for device in &self.devices {
-                    let is_selected = self.selected_device.as_deref() == Some(&device.device);
-                    let label = format!(
-                        "{}  ({} {}) - {:.1} GB",
-                        device.device,
-                        device.vendor,
-                        device.model,
-                        device.size as f64 / 1e9
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(tr!("Erase Device"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.with_layout(reading_layout(), |ui| {
+                    ui.label(tr!("All data and partitions on these devices will be destroyed:"));
+                    for line in &device_lines {
+                        ui.label(egui::RichText::new(line).strong());
+                    }
+                    ui.separator();
+                    ui.radio_value(
+                        &mut self.erase_mode,
+                        EraseMode::QuickZeroTable,
+                        "Quick: zero the partition table and the first/last few MB",
                     );
-                    if ui.selectable_label(is_selected, label).clicked() {
-                        self.selected_device = Some(device.device.clone());
+                    ui.radio_value(
+                        &mut self.erase_mode,
+                        EraseMode::FullZero,
+                        "Full: write zeros across the whole device (slow)",
+                    );
+                    ui.separator();
+                    if needs_typed_confirm {
+                        ui.label(tr!("A target is a large device. Type ERASE to confirm:"));
+                        ui.text_edit_singleline(&mut self.erase_confirm_input);
                     }
-                }
+                    let can_confirm =
+                        !needs_typed_confirm || self.erase_confirm_input.trim() == "ERASE";
+                    reading_row(ui, |ui| {
+                        if ui
+                            .add_enabled(can_confirm, egui::Button::new(tr!("Erase")))
+                            .clicked()
+                        {
+                            confirmed = true;
+                        }
+                        if ui.button(tr!("Cancel")).clicked() {
+                            cancelled = true;
+                        }
+                    });
+                });
             });
-        });
+
+        if confirmed || cancelled {
+            self.show_erase_confirmation = false;
+            self.erase_confirm_input.clear();
+        }
+        if confirmed {
+            self.start_erase();
+        }
     }
 
-    // In rustburn-gui/src/main.rs, replace the entire `render_bottom_panel` function.
+    /// Renders the filesystem choice and confirmation for reformatting the
+    /// selected devices with one clean partition.
+    fn render_format_window(&mut self, ctx: &egui::Context) {
+        if !self.show_format_window {
+            return;
+        }
+        let devices = self.selected_usb_devices();
+        if devices.is_empty() {
+            self.show_format_window = false;
+            return;
+        }
+        let device_lines: Vec<String> = devices
+            .iter()
+            .map(|d| device_label(d, self.size_units))
+            .collect();
+        let needs_typed_confirm = devices.iter().any(|d| d.size > ERASE_CONFIRM_THRESHOLD);
 
-    // In rustburn-gui/src/main.rs, replace the entire `render_bottom_panel` function.
-    fn render_bottom_panel(&mut self, ctx: &egui::Context) {
-        egui::TopBottomPanel::bottom("bottom_panel").show(ctx, |ui| {
-            ui.horizontal(|ui| {
-                let status_text = match &self.status {
-                    AppStatus::Idle => "Ready".to_string(),
-                    AppStatus::Scanning => "Scanning for devices...".to_string(),
-                    AppStatus::Burning => format!("Burning... {:.0}%", self.burn_progress * 100.0),
-                    AppStatus::CreatingWinIso => "Creating Windows ISO...".to_string(),
-                    AppStatus::Verifying => {
-                        format!("Verifying... {:.0}%", self.burn_progress * 100.0)
-                    }
-                    AppStatus::SettingUpBootable => "Making device bootable...".to_string(),
-                    AppStatus::Ejecting => "Ejecting device...".to_string(),
-                    AppStatus::Erasing => "Erasing device...".to_string(),
-                    AppStatus::Done => "Operation completed successfully.".to_string(),
-                    AppStatus::Error(e) => format!("Error: {}", e),
-                };
-                ui.label(status_text);
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(tr!("Format Device"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.with_layout(reading_layout(), |ui| {
+                    ui.label(tr!("All data on these devices will be destroyed:"));
+                    for line in &device_lines {
+                        ui.label(egui::RichText::new(line).strong());
+                    }
+                    ui.separator();
+                    reading_row(ui, |ui| {
+                        ui.label(tr!("Filesystem:"));
+                        ui.radio_value(&mut self.format_fs, FsType::Fat32, "FAT32")
+                            .on_hover_text(tr!(
+                                "Works everywhere; files up to 4 GB. Sticks over 32 GB are \
+                                 formatted too, unlike with most OS tools"
+                            ));
+                        ui.radio_value(&mut self.format_fs, FsType::ExFat, "exFAT")
+                            .on_hover_text(tr!(
+                                "Large files; works on current Windows, macOS and Linux"
+                            ));
+                        ui.radio_value(&mut self.format_fs, FsType::Ntfs, "NTFS")
+                            .on_hover_text(tr!(
+                                "Large files; read-only on some macOS and Linux setups"
+                            ));
+                    });
+                    reading_row(ui, |ui| {
+                        ui.label(tr!("Label:"));
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.format_label).desired_width(200.0),
+                        );
+                    });
+                    let label_error = format_label_error(self.format_fs, self.format_label.trim());
+                    if let Some(e) = label_error {
+                        ui.colored_label(egui::Color32::RED, e);
+                    }
+                    ui.separator();
+                    if needs_typed_confirm {
+                        ui.label(tr!("A target is a large device. Type ERASE to confirm:"));
+                        ui.text_edit_singleline(&mut self.erase_confirm_input);
+                    }
+                    let can_confirm = label_error.is_none()
+                        && (!needs_typed_confirm || self.erase_confirm_input.trim() == "ERASE");
+                    reading_row(ui, |ui| {
+                        if ui
+                            .add_enabled(can_confirm, egui::Button::new(tr!("Format")))
+                            .clicked()
+                        {
+                            confirmed = true;
+                        }
+                        if ui.button(tr!("Cancel")).clicked() {
+                            cancelled = true;
+                        }
+                    });
+                });
+            });
 
-                if matches!(self.status, AppStatus::Burning | AppStatus::Verifying) {
-                    ui.add(egui::ProgressBar::new(self.burn_progress).animate(true));
-                } else if !self.is_idle() && self.status != AppStatus::Done {
-                    // This is the corrected way to add a spinner.
-                    ui.spinner();
-                }
+        if confirmed || cancelled {
+            self.show_format_window = false;
+            self.erase_confirm_input.clear();
+        }
+        if confirmed {
+            self.start_format();
+        }
+    }
 
-                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                    if ui
-                        .button("📜 Logs")
-                        .on_hover_text("Show/Hide Logs")
-                        .clicked()
-                    {
-                        self.show_log_panel = !self.show_log_panel;
+    /// Renders the warning shown before a speed test overwrites part of the selected devices.
+    fn render_benchmark_confirmation(&mut self, ctx: &egui::Context) {
+        if !self.show_benchmark_confirmation {
+            return;
+        }
+        let devices = self.selected_usb_devices();
+        if devices.is_empty() {
+            self.show_benchmark_confirmation = false;
+            return;
+        }
+        let device_lines: Vec<String> = devices
+            .iter()
+            .map(|d| device_label(d, self.size_units))
+            .collect();
+
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(tr!("Test Device Speed"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.with_layout(reading_layout(), |ui| {
+                    ui.label(tr!(
+                        "A few hundred MB will be written to and read back from:"
+                    ));
+                    for line in &device_lines {
+                        ui.label(egui::RichText::new(line).strong());
                     }
+                    ui.label(tr!("Data in that region will be overwritten."));
+                    ui.separator();
+                    reading_row(ui, |ui| {
+                        if ui.button(tr!("Run test")).clicked() {
+                            confirmed = true;
+                        }
+                        if ui.button(tr!("Cancel")).clicked() {
+                            cancelled = true;
+                        }
+                    });
                 });
             });
-        });
+
+        if confirmed || cancelled {
+            self.show_benchmark_confirmation = false;
+        }
+        if confirmed {
+            self.start_benchmark();
+        }
     }
 
-    // --- Action Methods ---
+    /// Asks before stopping every burn of a multi-device run.
+    fn render_abort_confirmation(&mut self, ctx: &egui::Context) {
+        if !self.show_abort_confirmation {
+            return;
+        }
+        let unfinished = self.burn_jobs.iter().filter(|j| !j.is_finished()).count();
+        if unfinished == 0 {
+            self.show_abort_confirmation = false;
+            return;
+        }
 
-    fn render_log_panel(&mut self, ctx: &egui::Context) {
+        let mut confirmed = false;
+        let mut cancelled = false;
+        egui::Window::new(tr!("Abort All"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.with_layout(reading_layout(), |ui| {
+                    ui.label(tr!("Abort {} in-progress burns?", unfinished));
+                    ui.label(tr!(
+                        "Devices already being written are left incomplete and won't boot."
+                    ));
+                    ui.separator();
+                    reading_row(ui, |ui| {
+                        if ui.button(tr!("Abort all")).clicked() {
+                            confirmed = true;
+                        }
+                        if ui.button(tr!("Keep burning")).clicked() {
+                            cancelled = true;
+                        }
+                    });
+                });
+            });
+
+        if confirmed || cancelled {
+            self.show_abort_confirmation = false;
+        }
+        if confirmed {
+            self.stop_burn();
+        }
+    }
+
+    /// Shows a finished "Compute Checksum" result in a copyable field.
+    fn render_file_hash_window(&mut self, ctx: &egui::Context) {
+        if self.status == AppStatus::Hashing {
+            return;
+        }
+        let (path, algo, hash) = match &self.file_hash {
+            Some(FileHash {
+                path,
+                algo,
+                hash: Some(hash),
+            }) => (path.clone(), *algo, hash.clone()),
+            _ => return,
+        };
+        let mut open = true;
+        egui::Window::new(tr!("Checksum"))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.with_layout(reading_layout(), |ui| {
+                    ui.label(path);
+                    reading_row(ui, |ui| {
+                        ui.label(format!("{}:", algo));
+                        let mut text = hash.as_str();
+                        ui.add(
+                            egui::TextEdit::singleline(&mut text)
+                                .font(egui::TextStyle::Monospace)
+                                .desired_width(480.0),
+                        );
+                        if ui.button(tr!("Copy")).clicked() {
+                            ui.output_mut(|o| o.copied_text = hash.clone());
+                        }
+                    });
+                });
+            });
+        if !open {
+            self.file_hash = None;
+        }
+    }
+
+    /// Renders the dialog that asks for an ISO URL and an optional expected hash.
+    fn render_download_window(&mut self, ctx: &egui::Context) {
+        if !self.show_download_window {
+            return;
+        }
+        let mut start = false;
+        let mut cancelled = false;
+        egui::Window::new(tr!("Download ISO"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.with_layout(reading_layout(), |ui| {
+                    ui.label(tr!("URL:"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.download_url_input)
+                            .hint_text("https://...")
+                            .desired_width(400.0),
+                    );
+                    ui.label(tr!(
+                        "Expected {} (optional):",
+                        format!("{:?}", self.burn_options.checksum_algo)
+                    ));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.download_hash_input)
+                            .desired_width(400.0),
+                    );
+                    ui.separator();
+                    let url = self.download_url_input.trim();
+                    let valid = url.starts_with("http://") || url.starts_with("https://");
+                    reading_row(ui, |ui| {
+                        if ui
+                            .add_enabled(valid, egui::Button::new(tr!("Download")))
+                            .clicked()
+                        {
+                            start = true;
+                        }
+                        if ui.button(tr!("Cancel")).clicked() {
+                            cancelled = true;
+                        }
+                    });
+                });
+            });
+
+        if start || cancelled {
+            self.show_download_window = false;
+        }
+        if start {
+            self.start_download();
+        }
+    }
+
+    /// Renders the volume label and boot options for "Create ISO from Folder".
+    fn render_create_iso_window(&mut self, ctx: &egui::Context) {
+        let source = match &self.create_iso_source {
+            Some(source) => source.display().to_string(),
+            None => return,
+        };
+        let mut start = false;
+        let mut cancelled = false;
+        egui::Window::new(tr!("Create ISO from Folder"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.with_layout(reading_layout(), |ui| {
+                    ui.label(tr!("Folder: {}", source));
+                    ui.label(tr!("Volume label:"));
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.create_iso_label)
+                            .char_limit(ISO_LABEL_MAX)
+                            .desired_width(300.0),
+                    );
+                    ui.checkbox(&mut self.create_iso_bootable, tr!("Make bootable"))
+                        .on_hover_text(tr!("Uses the boot loader files found in the folder"));
+                    ui.add_enabled_ui(self.create_iso_bootable, |ui| {
+                        egui::ComboBox::from_id_source("create_iso_boot_combo")
+                            .selected_text(format!("{:?}", self.create_iso_boot_type))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(
+                                    &mut self.create_iso_boot_type,
+                                    BootType::UEFI,
+                                    "UEFI",
+                                );
+                                ui.selectable_value(
+                                    &mut self.create_iso_boot_type,
+                                    BootType::Legacy,
+                                    "Legacy",
+                                );
+                                ui.selectable_value(
+                                    &mut self.create_iso_boot_type,
+                                    BootType::Hybrid,
+                                    "Hybrid",
+                                );
+                            });
+                    });
+                    ui.separator();
+                    reading_row(ui, |ui| {
+                        if ui.button(tr!("Create...")).clicked() {
+                            start = true;
+                        }
+                        if ui.button(tr!("Cancel")).clicked() {
+                            cancelled = true;
+                        }
+                    });
+                });
+            });
+
+        if cancelled {
+            self.create_iso_source = None;
+        }
+        if start {
+            self.start_create_iso();
+        }
+    }
+
+    /// Lists past burns, most recent first.
+    fn render_history_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(tr!("Burn History"))
+            .open(&mut self.show_history_window)
+            .default_width(640.0)
+            .show(ctx, |ui| {
+                ui.with_layout(reading_layout(), |ui| {
+                    if self.history.entries.is_empty() {
+                        ui.label(tr!("No burns recorded yet."));
+                        return;
+                    }
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        egui::Grid::new("history_grid")
+                            .num_columns(5)
+                            .striped(true)
+                            .spacing([12.0, 4.0])
+                            .show(ui, |ui| {
+                                let headers = [
+                                    tr!("Finished"),
+                                    tr!("Image"),
+                                    tr!("Device"),
+                                    tr!("Result"),
+                                    tr!("Duration"),
+                                ];
+                                for header in headers {
+                                    ui.strong(header);
+                                }
+                                ui.end_row();
+                                for entry in self.history.entries.iter().rev() {
+                                    ui.label(format_utc(
+                                        UNIX_EPOCH + Duration::from_secs(entry.finished_at),
+                                    ));
+                                    ui.label(&entry.iso_name);
+                                    ui.label(&entry.device);
+                                    if entry.result == "OK" {
+                                        ui.label(&entry.result);
+                                    } else {
+                                        ui.colored_label(egui::Color32::RED, &entry.result);
+                                    }
+                                    ui.label(format_duration(entry.duration_secs));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                });
+            });
+    }
+
+    // In rustburn-gui/src/main.rs, replace the existing render_about_window function.
+    fn render_about_window(&mut self, ctx: &egui::Context) {
+        // The .open() method handles the closing logic for us,
+        // which resolves the double borrow error.
+        egui::Window::new(tr!("About RustBurn Professional"))
+            .open(&mut self.show_about_window)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.with_layout(reading_layout(), |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.heading("RustBurn Professional");
+                        ui.label(format!("Version: {}", env!("CARGO_PKG_VERSION")));
+                        ui.hyperlink("https://github.com/56tytt");
+                    });
+                    ui.separator();
+                    ui.label("A professional, multi-threaded ISO burning utility,");
+                    ui.label("engineered by our elite software team.");
+                    ui.label("Shay Kadosh Software Engineering from Ashkelon")
+                });
+            });
+    }
+
+    /// Lets the user pick the working directory, showing whether it is
+    /// writable and how much space it has left.
+    fn render_temp_dir_window(&mut self, ctx: &egui::Context) {
+        if !self.show_temp_dir_window {
+            return;
+        }
+        let mut picked = None;
+        let mut reset = false;
+        egui::Window::new(tr!("Temp Directory"))
+            .open(&mut self.show_temp_dir_window)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.with_layout(reading_layout(), |ui| {
+                    ui.label("Downloads and Windows ISO packing work in:");
+                    ui.label(
+                        egui::RichText::new(match &self.temp_dir {
+                            Some(dir) => dir.clone(),
+                            None => format!("{} (system default)", std::env::temp_dir().display()),
+                        })
+                        .strong(),
+                    );
+                    match &self.temp_dir_check {
+                        Some(Ok(free)) => {
+                            ui.label(format!("{} free", format_size(*free, self.size_units)));
+                        }
+                        Some(Err(e)) => {
+                            ui.colored_label(egui::Color32::RED, e);
+                        }
+                        None => {}
+                    }
+                    ui.separator();
+                    reading_row(ui, |ui| {
+                        if ui.button(tr!("Browse...")).clicked() {
+                            picked = rfd::FileDialog::new().pick_folder();
+                        }
+                        if ui
+                            .add_enabled(
+                                self.temp_dir.is_some(),
+                                egui::Button::new(tr!("Use system default")),
+                            )
+                            .clicked()
+                        {
+                            reset = true;
+                        }
+                    });
+                });
+            });
+
+        if let Some(dir) = picked {
+            // Only a usable directory replaces the current one.
+            let check = check_work_dir(&dir);
+            if check.is_ok() {
+                self.temp_dir = Some(dir.display().to_string());
+            }
+            self.temp_dir_check = Some(check);
+        }
+        if reset {
+            self.temp_dir = None;
+            self.temp_dir_check = Some(check_work_dir(&self.work_dir()));
+        }
+    }
+
+    /// The directory downloads and Windows ISO packing write their files to.
+    fn work_dir(&self) -> PathBuf {
+        self.temp_dir
+            .as_ref()
+            .map_or_else(std::env::temp_dir, PathBuf::from)
+    }
+
+    /// Renders the central panel, showing selected ISO and device list.
+    fn render_central_panel(&mut self, ctx: &egui::Context) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.with_layout(reading_layout(), |ui| {
+                ui.heading(tr!("1. Selected ISO File"));
+                ui.label(if self.burn_options.iso_path.is_empty() {
+                    "No file selected."
+                } else {
+                    &self.burn_options.iso_path
+                });
+                self.render_iso_info(ui);
+                self.render_image_queue(ui);
+                reading_row(ui, |ui| {
+                    ui.label(tr!("Expected checksum:"));
+                    egui::ComboBox::from_id_source("checksum_algo_combo")
+                        .selected_text(format!("{:?}", self.burn_options.checksum_algo))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.burn_options.checksum_algo,
+                                ChecksumAlgo::Sha256,
+                                "SHA-256",
+                            );
+                            ui.selectable_value(
+                                &mut self.burn_options.checksum_algo,
+                                ChecksumAlgo::Sha1,
+                                "SHA-1",
+                            );
+                            ui.selectable_value(
+                                &mut self.burn_options.checksum_algo,
+                                ChecksumAlgo::Md5,
+                                "MD5",
+                            );
+                        });
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.checksum_input)
+                            .hint_text("Paste hash to verify the ISO (optional)")
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+                ui.add_space(10.0);
+
+                ui.heading(tr!("2. Select Target Device"));
+                ui.separator();
+                if self
+                    .devices
+                    .iter()
+                    .any(|d| d.is_system && self.is_device_listed(d))
+                {
+                    if ui
+                        .checkbox(
+                            &mut self.allow_system_disks,
+                            egui::RichText::new(
+                                "I understand that system disks hold my OS and allow \
+                                 selecting them",
+                            )
+                            .color(egui::Color32::RED),
+                        )
+                        .changed()
+                    {
+                        self.drop_hidden_selection();
+                    }
+                }
+                let mut clicked_device = None;
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for device in self.devices.iter().filter(|d| self.is_device_listed(d)) {
+                        let selectable = !device.unresponsive
+                            && (!device.is_system || self.allow_system_disks);
+                        let is_selected = self.selected_devices.contains(&device.device);
+                        let job = self.burn_jobs.iter().find(|j| j.device == device.device);
+                        if device_row(ui, device, self.size_units, is_selected, selectable, job)
+                            .clicked()
+                        {
+                            clicked_device = Some(device.device.clone());
+                        }
+                        if !self.show_partitions {
+                            continue;
+                        }
+                        ui.indent(&device.device, |ui| {
+                            for partition in &device.partitions {
+                                let is_selected = self.selected_devices.contains(&partition.device);
+                                let job = self
+                                    .burn_jobs
+                                    .iter()
+                                    .find(|j| j.device == partition.device);
+                                if device_row(
+                                    ui,
+                                    partition,
+                                    self.size_units,
+                                    is_selected,
+                                    selectable,
+                                    job,
+                                )
+                                .clicked()
+                                {
+                                    clicked_device = Some(partition.device.clone());
+                                }
+                            }
+                        });
+                    }
+                });
+                // Clicking a device toggles it in or out of the target set. A whole
+                // device and one of its own partitions can't both be targets, so
+                // picking one drops the other.
+                if let Some(device) = clicked_device {
+                    if let Some(index) = self.selected_devices.iter().position(|d| *d == device) {
+                        self.selected_devices.remove(index);
+                    } else {
+                        let devices = &self.devices;
+                        self.selected_devices.retain(|d| !nested(devices, d, &device));
+                        self.selected_devices.push(device);
+                    }
+                }
+            });
+        });
+    }
+
+    // In rustburn-gui/src/main.rs, replace the entire `render_bottom_panel` function.
+
+    // In rustburn-gui/src/main.rs, replace the entire `render_bottom_panel` function.
+    fn render_bottom_panel(&mut self, ctx: &egui::Context) {
+        egui::TopBottomPanel::bottom("bottom_panel").show(ctx, |ui| {
+            // Each listed device shows its job's progress in its own row; only
+            // jobs whose device is no longer listed (unplugged, or hidden by
+            // the view settings) are shown down here.
+            let unlisted: Vec<&BurnJob> = self
+                .burn_jobs
+                .iter()
+                .filter(|j| !self.is_target_listed(&j.device))
+                .collect();
+            if self.burn_jobs.len() > 1 && !unlisted.is_empty() {
+                egui::Grid::new("burn_jobs_grid")
+                    .num_columns(2)
+                    .spacing([12.0, 4.0])
+                    .show(ui, |ui| {
+                        for job in unlisted {
+                            ui.label(&job.device);
+                            ui.add(
+                                egui::ProgressBar::new(job.overall_progress())
+                                    .text(job.status_line()),
+                            );
+                            ui.end_row();
+                        }
+                    });
+                ui.separator();
+            }
+            ui.horizontal(|ui| {
+                let multi_run = self.burn_jobs.len() > 1 && self.status == AppStatus::Burning;
+                let (status, progress, stats) = self.status_snapshot();
+                let permission_denied =
+                    matches!(status, AppStatus::Error(BurnError::PermissionDenied(_)));
+                let text = if self.awaiting_next_device {
+                    tr!("Insert next device…")
+                } else if let (AppStatus::Done, Some(summary)) = (status, &self.summary) {
+                    summary.clone()
+                } else if self.is_paused() {
+                    tr!("Paused at {}%", format!("{:.0}", progress * 100.0))
+                } else if multi_run {
+                    let done = self.burn_jobs.iter().filter(|j| j.is_finished()).count();
+                    tr!(
+                        "Burning {} devices... {} of {} finished",
+                        self.burn_jobs.len(),
+                        done,
+                        self.burn_jobs.len()
+                    )
+                } else {
+                    status_text(status, progress, stats)
+                };
+                let text = match self.operation_started {
+                    Some(started) if !self.is_idle() && !self.awaiting_next_device => {
+                        // Keep the stopwatch ticking between progress updates.
+                        ctx.request_repaint_after(Duration::from_secs(1));
+                        let elapsed =
+                            tr!("{} elapsed", format_duration(started.elapsed().as_secs()));
+                        // Both halves are already in display order.
+                        if i18n::language().is_rtl() {
+                            format!("{} — {}", elapsed, text)
+                        } else {
+                            format!("{} — {}", text, elapsed)
+                        }
+                    }
+                    _ => text,
+                };
+                let response = ui.label(text);
+                if let AppStatus::Error(e) = status {
+                    response.on_hover_text(e.to_string());
+                }
+
+                let size_unknown = stats.map_or(false, |s| s.total == 0);
+                let bar = if self.smooth_progress {
+                    self.displayed_progress
+                } else {
+                    progress
+                };
+                if multi_run {
+                    ui.add(egui::ProgressBar::new(bar).animate(true));
+                } else if *status == AppStatus::Burning && size_unknown {
+                    ui.spinner();
+                } else if matches!(
+                    status,
+                    AppStatus::CheckingIso
+                        | AppStatus::Burning
+                        | AppStatus::Verifying
+                        | AppStatus::SettingUpBootable
+                        | AppStatus::CreatingWinIso
+                        | AppStatus::CreatingIso
+                        | AppStatus::Cloning
+                        | AppStatus::Downloading
+                        | AppStatus::Hashing
+                ) || (matches!(status, AppStatus::Erasing | AppStatus::Formatting)
+                    && progress > 0.0)
+                {
+                    ui.add(egui::ProgressBar::new(bar).animate(true));
+                } else if !self.is_idle() && self.status != AppStatus::Done {
+                    // This is the corrected way to add a spinner.
+                    ui.spinner();
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .button(tr!("📜 Logs"))
+                        .on_hover_text(tr!("Show/Hide Logs"))
+                        .clicked()
+                    {
+                        self.show_log_panel = !self.show_log_panel;
+                    }
+                    if self.status == AppStatus::Scanning
+                        && ui
+                            .button(tr!("Cancel scan"))
+                            .on_hover_text(tr!("Stop waiting and keep the previous device list"))
+                            .clicked()
+                    {
+                        self.cancel_scan();
+                    }
+                    if permission_denied
+                        && self.elevation.is_none()
+                        && ui
+                            .button(tr!("Run as administrator"))
+                            .on_hover_text(tr!(
+                                "Restart RustBurn with the rights to open raw devices"
+                            ))
+                            .clicked()
+                    {
+                        self.relaunch_elevated(ctx);
+                    }
+                });
+            });
+        });
+    }
+
+    // --- Action Methods ---
+
+    fn render_log_panel(&mut self, ctx: &egui::Context) {
         if self.show_log_panel {
             egui::TopBottomPanel::bottom("log_panel")
                 .resizable(true)
                 .default_height(150.0)
                 .min_height(50.0)
                 .show(ctx, |ui| {
-                    ui.vertical_centered(|ui| {
-                        ui.label("Logs");
+                    ui.horizontal(|ui| {
+                        ui.label(tr!("Logs"));
+                        egui::ComboBox::from_id_source("log_level_combo")
+                            .selected_text(format!("{:?}", self.log_level))
+                            .show_ui(ui, |ui| {
+                                for level in
+                                    [LogLevel::Debug, LogLevel::Info, LogLevel::Warn, LogLevel::Error]
+                                {
+                                    ui.selectable_value(
+                                        &mut self.log_level,
+                                        level,
+                                        format!("{:?}", level),
+                                    );
+                                }
+                            })
+                            .response
+                            .on_hover_text(tr!("Show messages at this level and above"));
+                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                            if ui
+                                .add_enabled(
+                                    !self.logs.is_empty(),
+                                    egui::Button::new(tr!("Save logs...")),
+                                )
+                                .clicked()
+                            {
+                                self.save_logs();
+                            }
+                        });
                     });
                     ui.separator();
                     egui::ScrollArea::vertical()
                         .stick_to_bottom(true)
                         .show(ui, |ui| {
-                            for log in &self.logs {
-                                ui.monospace(log);
+                            for (level, log) in self.logs.lines_at(self.log_level) {
+                                let text = egui::RichText::new(log).monospace();
+                                ui.label(match level {
+                                    LogLevel::Debug => text.weak(),
+                                    LogLevel::Info => text,
+                                    LogLevel::Warn => text.color(egui::Color32::YELLOW),
+                                    LogLevel::Error => text.color(egui::Color32::RED),
+                                });
                             }
                         });
                 });
         }
     }
 
-    /// Scans for available USB devices.
-    fn scan_devices(&mut self) {
-        self.status = AppStatus::Scanning;
-        self.devices = RustBurn::scan_devices().unwrap_or_else(|e| {
-            self.status = AppStatus::Error(e.to_string());
-            Vec::new()
+    /// Writes the log panel to a file, headed by the time and the `BurnOptions` in use,
+    /// so it can be attached to a bug report as-is.
+    fn save_logs(&mut self) {
+        let path = match rfd::FileDialog::new()
+            .add_filter("Log File", &["log", "txt"])
+            .set_file_name("rustburn.log")
+            .save_file()
+        {
+            Some(path) => path,
+            None => return,
+        };
+        // BurnOptions has no serde support; its Debug form names every field.
+        let options = format!("{:#?}", self.burn_options);
+        let contents = format!(
+            "RustBurn Professional {} log\nSaved: {}\n\nBurn options:\n{}\n\n{}\n",
+            env!("CARGO_PKG_VERSION"),
+            format_utc(SystemTime::now()),
+            options,
+            self.logs.lines().collect::<Vec<_>>().join("\n")
+        );
+        match std::fs::write(&path, contents) {
+            Ok(()) => self
+                .logs
+                .push(LogLevel::Info, format!("Logs saved to {}", path.display())),
+            Err(e) => self
+                .logs
+                .push(LogLevel::Error, format!("ERROR: Failed to save logs: {}", e)),
+        }
+    }
+
+    /// Scans for available USB devices.
+    /// Enumeration can take a while with many drives, so it runs in a background
+    /// thread; `poll_scan` picks up the result.
+    fn scan_devices(&mut self) {
+        if self.scan_receiver.is_some() {
+            return;
+        }
+        if let Some(rx) = &self.abandoned_scan {
+            if let Err(mpsc::TryRecvError::Empty) = rx.try_recv() {
+                self.logs.push(
+                    LogLevel::Warn,
+                    "The cancelled scan is still waiting on a device; unplug it or try again later",
+                );
+                return;
+            }
+            self.abandoned_scan = None;
+        }
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send(RustBurn::scan_devices_with_timeout(SCAN_DEVICE_TIMEOUT));
+        });
+        self.scan_receiver = Some(rx);
+        self.status = AppStatus::Scanning;
+        // The stopwatch times the scan, not whatever ran before it.
+        self.operation_started = Some(Instant::now());
+    }
+
+    /// Gives up on a running scan and keeps the previous device list. The scan
+    /// thread may still be stuck in the kernel; its result is dropped when it
+    /// returns, and `scan_devices` won't start another until then.
+    fn cancel_scan(&mut self) {
+        if let Some(rx) = self.scan_receiver.take() {
+            self.abandoned_scan = Some(rx);
+            self.logs.push(
+                LogLevel::Warn,
+                "Scan cancelled; the device list is from the previous scan",
+            );
+            self.status = AppStatus::Idle;
+        }
+    }
+
+    /// Applies the result of a finished background scan.
+    fn poll_scan(&mut self) {
+        let result = match self.scan_receiver.as_ref().map(|rx| rx.try_recv()) {
+            Some(Ok(result)) => result,
+            Some(Err(mpsc::TryRecvError::Empty)) | None => return,
+            Some(Err(mpsc::TryRecvError::Disconnected)) => Err(BurnError::Other(
+                "device scan terminated unexpectedly".to_string(),
+            )),
+        };
+        self.scan_receiver = None;
+        match result {
+            Ok(devices) => {
+                let previous = std::mem::replace(&mut self.devices, devices);
+                self.remap_selection(&previous);
+                self.status = AppStatus::Idle;
+            }
+            Err(e) => {
+                // The core's message carries the fix (sudo/disk group, or elevation on
+                // Windows); the log panel shows it in full where the status bar can't.
+                // A permission error also brings up "Run as administrator".
+                self.logs.push(LogLevel::Error, format!("ERROR: {}", e));
+                self.devices.clear();
+                self.status = AppStatus::Error(e);
+            }
+        }
+        if let Some(device) = self.preselect_device.take() {
+            if self.is_unresponsive(&device) {
+                self.logs.push(
+                    LogLevel::Warn,
+                    format!("Device {} from the command line is not responding", device),
+                );
+            } else if self.find_device(&device).is_some() {
+                self.selected_devices.push(device);
+            } else {
+                self.logs.push(
+                    LogLevel::Warn,
+                    format!("Device {} from the command line was not found", device),
+                );
+            }
+        }
+    }
+
+    /// Applies the result of a finished background `inspect_iso`, unless a
+    /// different image has been picked since.
+    fn poll_iso_info(&mut self, ctx: &egui::Context) {
+        let received = match self.iso_info_receiver.as_ref().map(|rx| rx.try_recv()) {
+            Some(Ok(received)) => Some(received),
+            Some(Err(mpsc::TryRecvError::Empty)) => {
+                // Nothing else may be repainting while the app is idle.
+                ctx.request_repaint_after(Duration::from_millis(100));
+                return;
+            }
+            Some(Err(mpsc::TryRecvError::Disconnected)) => None,
+            None => return,
+        };
+        self.iso_info_receiver = None;
+        if let Some((path, info)) = received {
+            if path == self.burn_options.iso_path {
+                self.iso_info = Some(info);
+            }
+        }
+    }
+
+    /// Applies hotplug events so the device list follows sticks being plugged in and out.
+    fn poll_device_events(&mut self) {
+        while let Ok(event) = self.device_events.try_recv() {
+            match event {
+                DeviceEvent::Added(device) => {
+                    self.logs.push(
+                        LogLevel::Info,
+                        format!("Device connected: {}", device_label(&device, self.size_units)),
+                    );
+                    let path = device.device.clone();
+                    let is_next = self.awaiting_next_device
+                        && self.is_idle()
+                        && device.is_removable
+                        && !device.is_system
+                        && !device.write_protected;
+                    let previous = self.devices.clone();
+                    self.devices.retain(|d| d.device != path);
+                    self.devices.push(device);
+                    // The node may have belonged to a selected stick until now.
+                    self.remap_selection(&previous);
+                    if is_next {
+                        self.logs.push(
+                            LogLevel::Info,
+                            format!("Next device: {}. Press Enter to burn.", path),
+                        );
+                        self.selected_devices = vec![path.clone()];
+                        self.next_device = Some(path);
+                        self.awaiting_next_device = false;
+                        self.burn_jobs.clear();
+                        self.status = AppStatus::Idle;
+                    }
+                }
+                DeviceEvent::Removed(path) => {
+                    self.logs
+                        .push(LogLevel::Info, format!("Device removed: {}", path));
+                    self.fail_disconnected_burns(&path);
+                    // A stick re-plugged into the same port may be a different one.
+                    RustBurn::forget_device(&path);
+                    self.devices.retain(|d| d.device != path);
+                    self.selected_devices.retain(|d| *d != path);
+                }
+            }
+        }
+    }
+
+    /// Fails running burns on a device that was just unplugged, or on one of
+    /// its partitions. The writer usually reports the disconnect itself, but
+    /// one stuck in a write to a vanished node may never return, so the job
+    /// is detached from its thread rather than waited on.
+    fn fail_disconnected_burns(&mut self, path: &str) {
+        let partitions: Vec<String> = self
+            .find_device(path)
+            .map(|d| d.partitions.iter().map(|p| p.device.clone()).collect())
+            .unwrap_or_default();
+        let tag = self.burn_jobs.len() > 1;
+        let mut failed = Vec::new();
+        for (i, job) in self.burn_jobs.iter_mut().enumerate() {
+            if job.is_running() && (job.device == path || partitions.contains(&job.device)) {
+                job.cancel.store(true, Ordering::SeqCst);
+                job.pause.store(false, Ordering::SeqCst);
+                job.apply(
+                    UiProgress::Error(BurnError::Other(DISCONNECTED_MESSAGE.to_string())),
+                    &mut self.logs,
+                    tag,
+                );
+                job.receiver = None;
+                failed.push(i);
+            }
+        }
+        for i in failed {
+            self.record_history(i);
+        }
+    }
+
+    /// Hides the selected devices from the list without touching them; a rescan brings them back.
+    fn remove_selected_from_list(&mut self) {
+        let selected = std::mem::take(&mut self.selected_devices);
+        self.devices.retain(|d| !selected.contains(&d.device));
+    }
+
+    /// Whether a burn target currently has a row in the device list.
+    fn is_target_listed(&self, path: &str) -> bool {
+        self.devices.iter().filter(|d| self.is_device_listed(d)).any(|d| {
+            d.device == path
+                || (self.show_partitions && d.partitions.iter().any(|p| p.device == path))
+        })
+    }
+
+    /// Whether a device should appear in the device list under the current view settings.
+    fn is_device_listed(&self, device: &UsbDevice) -> bool {
+        device.is_removable || self.show_internal_disks
+    }
+
+    /// Deselects devices that are hidden or no longer selectable.
+    fn drop_hidden_selection(&mut self) {
+        let valid: Vec<String> = self
+            .devices
+            .iter()
+            .filter(|d| {
+                self.is_device_listed(d)
+                    && !d.unresponsive
+                    && (!d.is_system || self.allow_system_disks)
+            })
+            .flat_map(|d| {
+                let partitions = if self.show_partitions {
+                    d.partitions.as_slice()
+                } else {
+                    &[]
+                };
+                std::iter::once(d).chain(partitions)
+            })
+            .map(|d| d.device.clone())
+            .collect();
+        self.selected_devices.retain(|d| valid.contains(d));
+    }
+
+    /// Selects an image and moves it to the front of the recent list.
+    fn set_iso_path(&mut self, path: String) {
+        self.recent_isos.retain(|p| *p != path);
+        self.recent_isos.insert(0, path.clone());
+        self.recent_isos.truncate(MAX_RECENT_ISOS);
+        // Only the ISO9660/El Torito headers are read, but an image on a slow or
+        // network drive can still stall, so it is done off the UI thread.
+        self.iso_info = None;
+        let (tx, rx) = mpsc::channel();
+        let inspected = path.clone();
+        thread::spawn(move || {
+            let info = RustBurn::inspect_iso(&inspected);
+            let _ = tx.send((inspected, info));
+        });
+        self.iso_info_receiver = Some(rx);
+        self.burn_options.iso_path = path;
+        // A mode forced for the last image goes back to Auto for the new one.
+        self.burn_options.write_mode = None;
+    }
+
+    /// Opens a file dialog to select an ISO file.
+    fn select_iso_file(&mut self) {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Disk Image", &[IMAGE_EXTENSIONS, COMPRESSED_EXTENSIONS].concat())
+            .pick_file()
+        {
+            self.set_iso_path(path.display().to_string());
+        }
+    }
+
+    /// Returns the `UsbDevice` entries for the current selection that are still listed.
+    fn selected_usb_devices(&self) -> Vec<&UsbDevice> {
+        self.selected_devices
+            .iter()
+            .filter_map(|selected| self.find_device(selected))
+            .collect()
+    }
+
+    /// Looks up a whole device or one of its partitions by node path.
+    fn find_device(&self, path: &str) -> Option<&UsbDevice> {
+        find_in(&self.devices, path)
+    }
+
+    /// Carries the selection over to a new device list by stable ID (serial
+    /// and port), so a renumbered stick stays selected under its new node and
+    /// a node now taken by a different stick is dropped, not silently kept.
+    fn remap_selection(&mut self, previous: &[UsbDevice]) {
+        let mut selection = Vec::new();
+        for path in std::mem::take(&mut self.selected_devices) {
+            let current = match find_in(previous, &path).and_then(UsbDevice::stable_id) {
+                Some(id) => self
+                    .devices
+                    .iter()
+                    .flat_map(|d| std::iter::once(d).chain(&d.partitions))
+                    .find(|d| d.stable_id().as_ref() == Some(&id))
+                    .map(|d| d.device.clone()),
+                // Without a serial or port there is nothing to follow.
+                None => find_in(&self.devices, &path).map(|d| d.device.clone()),
+            };
+            match current {
+                Some(current) if self.is_unresponsive(&current) => self.logs.push(
+                    LogLevel::Warn,
+                    format!("Selected device {} is not responding; deselected", current),
+                ),
+                Some(current) => {
+                    if current != path {
+                        self.logs.push(
+                            LogLevel::Info,
+                            format!("Selected device {} is now {}", path, current),
+                        );
+                    }
+                    selection.push(current);
+                }
+                None => self.logs.push(
+                    LogLevel::Warn,
+                    format!("Selected device {} is no longer present; deselected", path),
+                ),
+            }
+        }
+        self.selected_devices = selection;
+    }
+
+    /// Whether the last scan timed out on `path`, or on the device it is a partition of.
+    fn is_unresponsive(&self, path: &str) -> bool {
+        self.devices.iter().any(|d| {
+            d.unresponsive
+                && (d.device == path || d.partitions.iter().any(|p| p.device == path))
+        })
+    }
+
+    /// Whether a node path names a partition rather than a whole device.
+    fn is_partition(&self, path: &str) -> bool {
+        self.devices
+            .iter()
+            .any(|d| d.partitions.iter().any(|p| p.device == path))
+    }
+
+    /// Whether a persistence partition can be added: the burn is a bootable
+    /// DD copy and the image looks like a Debian-family live ISO.
+    fn persistence_available(&self) -> bool {
+        self.burn_options.make_bootable
+            && !self.is_file_copy()
+            && is_debian_live_image(&self.burn_options.iso_path)
+    }
+
+    /// Whether the burn formats the device and copies the image's files, either
+    /// picked or inferred by Auto, rather than copying it block for block.
+    fn is_file_copy(&self) -> bool {
+        let mode = self
+            .burn_options
+            .write_mode
+            .or_else(|| self.iso_info.as_ref().map(|info| info.write_mode));
+        mode == Some(WriteMode::FileCopy)
+    }
+
+    /// The typed volume label as bootable setup writes it, or None when the
+    /// field is empty. Bootable setup only labels FAT volumes (the image's
+    /// own, or the FAT32 volume a file copy creates), which store labels
+    /// uppercase.
+    fn volume_label(&self) -> Option<String> {
+        let label = self.volume_label_input.trim();
+        (!label.is_empty()).then(|| label.to_uppercase())
+    }
+
+    /// Why the volume label can't be used, if it is set for a bootable burn
+    /// and breaks the rules of the FAT volume it is written to.
+    fn volume_label_error(&self) -> Option<&'static str> {
+        if !self.burn_options.make_bootable {
+            return None;
+        }
+        format_label_error(FsType::Fat32, &self.volume_label()?)
+    }
+
+    /// Why the "Custom…" block size can't be used: it must be a power of two
+    /// from 1 KB to 64 MB and a whole number of sectors on every selected device.
+    fn block_size_error(&self) -> Option<String> {
+        let input = self.block_size_input.as_deref()?;
+        let kb: usize = match input.trim().parse() {
+            Ok(kb) => kb,
+            Err(_) => return Some("enter a size in KB".to_string()),
+        };
+        if kb == 0 {
+            return Some("must be at least 1 KB".to_string());
+        }
+        let bytes = match kb.checked_mul(1024) {
+            Some(bytes) if kb <= MAX_BLOCK_SIZE_KB => bytes,
+            _ => return Some(format!("must be at most {} KB", MAX_BLOCK_SIZE_KB)),
+        };
+        if !bytes.is_power_of_two() {
+            return Some("must be a power of two".to_string());
+        }
+        let sector_size = self
+            .selected_usb_devices()
+            .iter()
+            .map(|d| d.sector_size)
+            .max()
+            .unwrap_or(DEFAULT_SECTOR_SIZE);
+        if bytes as u64 % sector_size != 0 {
+            return Some(format!(
+                "must be a multiple of the {} byte sector size",
+                sector_size
+            ));
+        }
+        None
+    }
+
+    /// Ctrl+R scans, Ctrl+O opens the ISO dialog and Ctrl+Enter asks to burn,
+    /// with the same gating as the toolbar buttons.
+    /// In next-device mode a bare Enter also burns, unless a text field has focus.
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        let bare_enter = self.next_device_mode && !ctx.wants_keyboard_input();
+        let (scan, open, burn, next) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::R),
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::O),
+                i.consume_key(egui::Modifiers::COMMAND, egui::Key::Enter),
+                bare_enter && i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+            )
+        });
+        // A confirmation window is already waiting on the user.
+        let dialog_open = self.show_burn_confirmation
+            || self.show_erase_confirmation
+            || self.show_benchmark_confirmation
+            || self.show_abort_confirmation
+            || self.show_format_window
+            || self.show_download_window;
+        if !self.is_idle() || dialog_open {
+            return;
+        }
+        if scan {
+            self.scan_devices();
+        } else if open {
+            self.select_iso_file();
+        } else if burn && self.can_burn() {
+            self.request_burn();
+        } else if next && self.can_burn() {
+            self.burn_next_device();
+        }
+    }
+
+    /// Burns the same image to the stick picked up in next-device mode. The
+    /// confirmation was given for the first burn of the run, so it is only
+    /// shown again when the selection isn't that stick alone, or when the
+    /// stick needs more than a click: a large device that needs ERASE typed,
+    /// one that is mounted, or one that already holds bootable media.
+    fn burn_next_device(&mut self) {
+        let picked = self.next_device.take();
+        let needs_confirmation = picked.as_ref() != self.selected_devices.first()
+            || self.selected_devices.len() != 1
+            || self
+                .selected_usb_devices()
+                .iter()
+                .any(|d| {
+                    d.size > ERASE_CONFIRM_THRESHOLD
+                        || !mount_points(d).is_empty()
+                        || existing_media(d).is_some()
+                });
+        if needs_confirmation {
+            self.request_burn();
+        } else {
+            self.unmount_for_run = false;
+            self.checkpoints.clear();
+            self.start_burn();
+        }
+    }
+
+    /// Why the current selection can't be burned, for the Burn button's hover text.
+    fn burn_blocker(&self) -> Option<String> {
+        self.image_too_large_message()
+            .or_else(|| {
+                let recheck =
+                    self.burn_options.auto_eject && self.burn_options.reverify_after_eject;
+                (self.is_file_copy() && recheck).then(|| {
+                    tr!(
+                        "The re-check after eject compares the device block for block; turn \
+                         it off or use DD mode"
+                    )
+                })
+            })
+            .or_else(|| {
+                self.volume_label_error()
+                    .map(|e| tr!("Volume label: {}", e))
+            })
+            .or_else(|| self.block_size_error().map(|e| tr!("Block size: {}", e)))
+    }
+
+    /// Whether an image and device are picked and nothing blocks burning them.
+    fn can_burn(&self) -> bool {
+        !self.selected_devices.is_empty()
+            && !self.burn_options.iso_path.is_empty()
+            && self.burn_blocker().is_none()
+    }
+
+    /// Explains why the selected image can't fit, if it is larger than any selected device.
+    /// Compressed images have no known size up front and are left to `burn_iso` to check.
+    /// This holds in file copy mode too: the image's files never take more than the image.
+    fn image_too_large_message(&self) -> Option<String> {
+        let iso_size = image_size(Path::new(&self.burn_options.iso_path))?;
+        self.selected_usb_devices()
+            .into_iter()
+            .find(|d| iso_size > d.size)
+            .map(|d| {
+                tr!(
+                    "ISO is {} but {} is only {}",
+                    format_size(iso_size, self.size_units),
+                    d.device,
+                    format_size(d.size, self.size_units)
+                )
+            })
+    }
+
+    /// Flushes and safely powers down every selected device in a background thread.
+    fn start_eject(&mut self) {
+        if self.selected_devices.is_empty() {
+            return;
+        }
+        self.run_for_selected_devices(RustBurn::eject);
+        self.begin_operation(AppStatus::Ejecting);
+    }
+
+    /// Wipes every selected device with the chosen `EraseMode` in a background thread.
+    fn start_erase(&mut self) {
+        if self.selected_devices.is_empty() {
+            return;
+        }
+        let mode = self.erase_mode;
+        self.run_for_selected_devices(move |device, tx| RustBurn::erase(device, mode, tx));
+        self.begin_operation(AppStatus::Erasing);
+    }
+
+    /// Reads back every selected device in turn and compares it with the selected
+    /// image; the match or mismatch is reported in the log.
+    fn start_compare(&mut self) {
+        if self.selected_devices.is_empty() || self.burn_options.iso_path.is_empty() {
+            return;
+        }
+        let iso = self.burn_options.iso_path.clone();
+        self.run_for_selected_devices(move |device, tx| {
+            RustBurn::compare(device, iso.clone(), tx)
         });
-        // If scanning was successful, revert to Idle status.
-        if self.status == AppStatus::Scanning {
-            self.status = AppStatus::Idle;
+        self.begin_operation(AppStatus::Verifying);
+        self.show_log_panel = true;
+    }
+
+    /// Formats every selected device in turn with one partition of the chosen filesystem.
+    fn start_format(&mut self) {
+        if self.selected_devices.is_empty() {
+            return;
         }
+        let fs = self.format_fs;
+        let label = self.format_label.trim().to_string();
+        self.run_for_selected_devices(move |device, tx| {
+            RustBurn::format(device, fs, label.clone(), tx)
+        });
+        self.begin_operation(AppStatus::Formatting);
     }
 
-    /// Opens a file dialog to select an ISO file.
-    fn select_iso_file(&mut self) {
-        if let Some(path) = rfd::FileDialog::new()
-            .add_filter("ISO Image", &["iso"])
-            .pick_file()
-        {
-            self.burn_options.iso_path = path.display().to_string();
+    /// Measures sustained write and read speed of every selected device in turn;
+    /// the results arrive as log lines.
+    fn start_benchmark(&mut self) {
+        if self.selected_devices.is_empty() {
+            return;
+        }
+        self.run_for_selected_devices(RustBurn::benchmark);
+        self.begin_operation(AppStatus::Benchmarking);
+        self.show_log_panel = true;
+    }
+
+    /// Runs a per-device core operation on each selected device in turn, on one
+    /// background thread. A failed device doesn't stop the rest; each one's
+    /// result is logged, and the run ends with a single `Done` or `Error`.
+    /// Stop skips the devices not yet started.
+    fn run_for_selected_devices<F>(&mut self, op: F)
+    where
+        F: Fn(String, mpsc::Sender<UiProgress>) + Send + 'static,
+    {
+        let devices = self.selected_devices.clone();
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.device_run_cancel = Some(cancel.clone());
+        let (tx, rx) = mpsc::channel();
+        self.progress_receiver = Some(rx);
+        self.operation_thread = Some(thread::spawn(move || {
+            let count = devices.len();
+            let mut failures = Vec::new();
+            let mut skipped = 0;
+            for device in devices {
+                if cancel.load(Ordering::SeqCst) {
+                    let _ = tx.send(UiProgress::Log(
+                        LogLevel::Warn,
+                        format!("{}: skipped", device),
+                    ));
+                    skipped += 1;
+                    continue;
+                }
+                let (device_tx, device_rx) = mpsc::channel();
+                op(device.clone(), device_tx);
+                let mut result = Err(BurnError::Other(
+                    "Operation thread terminated unexpectedly".to_string(),
+                ));
+                for update in device_rx {
+                    match update {
+                        UiProgress::Done => result = Ok(()),
+                        UiProgress::Error(e) => result = Err(e),
+                        update => {
+                            let _ = tx.send(update);
+                        }
+                    }
+                }
+                if count > 1 {
+                    let _ = tx.send(match &result {
+                        Ok(()) => UiProgress::Log(LogLevel::Info, format!("{}: done", device)),
+                        Err(e) => UiProgress::Log(LogLevel::Error, format!("{}: {}", device, e)),
+                    });
+                }
+                if let Err(e) = result {
+                    failures.push(e);
+                }
+            }
+            let _ = tx.send(match (failures.len(), skipped) {
+                (0, 0) => UiProgress::Done,
+                (0, _) => UiProgress::Error(BurnError::Cancelled),
+                // One device's error keeps its type, e.g. for "Run as administrator".
+                (1, 0) => UiProgress::Error(failures.remove(0)),
+                (n, _) => UiProgress::Error(BurnError::Other(format!(
+                    "{} of {} devices failed; see the log",
+                    n, count
+                ))),
+            });
+        }));
+    }
+
+    /// Asks the user to confirm before `start_burn` is allowed to run.
+    fn request_burn(&mut self) {
+        if !self.selected_usb_devices().is_empty() {
+            self.erase_confirm_input.clear();
+            // A dry run writes nothing, so there is nothing to resume.
+            self.checkpoints = if self.burn_options.dry_run {
+                Vec::new()
+            } else {
+                self.selected_devices
+                    .iter()
+                    .filter_map(|device| {
+                        RustBurn::find_checkpoint(&self.burn_options.iso_path, device)
+                            .map(|offset| (device.clone(), offset))
+                    })
+                    .collect()
+            };
+            self.resume_checkpoints = true;
+            self.preserve_partition = None;
+            self.preserve_temp_free = None;
+            self.source_disk = self
+                .selected_usb_devices()
+                .into_iter()
+                .find(|d| holds_file(d, &self.burn_options.iso_path))
+                .map(|d| d.device.clone());
+            self.show_burn_confirmation = true;
         }
     }
 
-    /// Starts the ISO burning process in a background thread.
+    /// Queues the ISO for every selected device and starts the first writers.
     fn start_burn(&mut self) {
-        if let Some(device) = self.selected_device.clone() {
-            self.burn_options.device_path = device;
+        if self.selected_devices.is_empty() {
+            return;
+        }
+        let checksum = self.checksum_input.trim();
+        self.burn_options.checksum = (!checksum.is_empty()).then(|| checksum.to_lowercase());
+        self.burn_options.volume_label = self.volume_label();
+        if let Some(warning) = self.boot_mismatch_warning() {
+            self.logs.push(LogLevel::Warn, format!("WARNING: {}", warning));
+        }
+        let weights = PhaseWeights::for_options(&self.burn_options);
+        self.burn_jobs = self
+            .selected_devices
+            .iter()
+            .cloned()
+            .map(|device| {
+                let id = self.find_device(&device).and_then(UsbDevice::stable_id);
+                BurnJob::queued(device, id, weights, self.burn_options.dry_run)
+            })
+            .collect();
+        if self.resume_checkpoints {
+            for job in &mut self.burn_jobs {
+                job.resume_from = self
+                    .checkpoints
+                    .iter()
+                    .find(|(device, _)| *device == job.device)
+                    .map(|(_, offset)| *offset);
+            }
+        }
+        // Fail write-protected targets, the disk the image is read from, and a
+        // device selected together with its own partition, up front instead of
+        // partway through the write.
+        let refused: Vec<(String, BurnError)> = self
+            .selected_usb_devices()
+            .into_iter()
+            .filter_map(|d| {
+                let overlap = self
+                    .selected_devices
+                    .iter()
+                    .find(|other| nested(&self.devices, &d.device, other));
+                if let Some(other) = overlap {
+                    Some((
+                        d.device.clone(),
+                        BurnError::Other(format!("{} is also selected on the same disk", other)),
+                    ))
+                } else if d.write_protected {
+                    Some((d.device.clone(), BurnError::WriteProtected))
+                } else if holds_file(d, &self.burn_options.iso_path) {
+                    Some((d.device.clone(), BurnError::Other(SOURCE_ON_TARGET_MESSAGE.to_string())))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let tag = self.burn_jobs.len() > 1;
+        for i in 0..self.burn_jobs.len() {
+            let reason = refused
+                .iter()
+                .find(|(device, _)| *device == self.burn_jobs[i].device)
+                .map(|(_, reason)| reason.clone());
+            if let Some(reason) = reason {
+                self.burn_jobs[i].apply(UiProgress::Error(reason), &mut self.logs, tag);
+                self.record_history(i);
+            }
+        }
+        self.begin_operation(AppStatus::Burning);
+        self.start_queued_burns();
+    }
+
+    /// Switches to a newly started operation, dropping the last one's progress and summary.
+    fn begin_operation(&mut self, status: AppStatus) {
+        self.status = status;
+        self.burn_progress = 0.0;
+        self.write_stats = None;
+        self.summary = None;
+        self.operation_started = Some(Instant::now());
+    }
+
+    /// Spawns burn threads for queued jobs until `MAX_CONCURRENT_BURNS` are running.
+    fn start_queued_burns(&mut self) {
+        let persistence_available = self.persistence_available();
+        // The partition to keep, with the device it belongs to.
+        let preserve = self.preserve_partition.as_ref().and_then(|partition| {
+            self.devices
+                .iter()
+                .find(|d| d.partitions.iter().any(|p| p.device == *partition))
+                .map(|d| (d.device.clone(), partition.clone()))
+        });
+        // What each target's node is now, to compare against the ID taken
+        // when the burn was confirmed.
+        let current_ids: Vec<(String, Option<String>)> = self
+            .burn_jobs
+            .iter()
+            .map(|j| {
+                let id = self.find_device(&j.device).and_then(UsbDevice::stable_id);
+                (j.device.clone(), id)
+            })
+            .collect();
+        let mut replaced = Vec::new();
+        let mut running = self.burn_jobs.iter().filter(|j| j.is_running()).count();
+        for (i, job) in self.burn_jobs.iter_mut().enumerate() {
+            if !job.is_queued() {
+                continue;
+            }
+            if running >= MAX_CONCURRENT_BURNS {
+                break;
+            }
+            if job.device_id.is_some() {
+                let current = current_ids
+                    .iter()
+                    .find(|(device, _)| *device == job.device)
+                    .and_then(|(_, id)| id.as_ref());
+                if current != job.device_id.as_ref() {
+                    replaced.push(i);
+                    continue;
+                }
+            }
+            let mut options = self.burn_options.clone(); // Clone for the thread
+            options.device_path = job.device.clone();
+            if !persistence_available {
+                options.persistence_mb = None;
+            }
+            options.auto_unmount |= self.unmount_for_run;
+            options.auto_eject |= self.next_device_mode;
+            options.resume_from = job.resume_from;
+            // burn_iso checks the ID again once it opens the node.
+            options.device_id = job.device_id.clone();
+            options.preserve_partition = preserve
+                .as_ref()
+                .filter(|(device, _)| *device == job.device)
+                .map(|(_, partition)| partition.clone());
             let (tx, rx) = mpsc::channel();
-            self.progress_receiver = Some(rx);
-            let burn_options_clone = self.burn_options.clone(); // Clone for the thread
+            let cancel = job.cancel.clone();
+            let pause = job.pause.clone();
             // Spawn the operation in a new thread to prevent UI freezing.
-            self.operation_thread = Some(thread::spawn(move || {
-                RustBurn::burn_iso(burn_options_clone, tx);
+            job.thread = Some(thread::spawn(move || {
+                // The outcome also arrives as Done/Error on the channel, which is
+                // what drives the UI, so the returned report isn't needed here.
+                let _ = RustBurn::burn_iso(options, tx, cancel, pause);
             }));
-            self.status = AppStatus::Burning;
-            self.burn_progress = 0.0;
+            job.receiver = Some(rx);
+            job.started = Some(Instant::now());
+            job.status = AppStatus::Burning;
+            running += 1;
+        }
+        let tag = self.burn_jobs.len() > 1;
+        for i in replaced {
+            let reason = BurnError::Other(format!(
+                "{} is no longer the device that was selected",
+                self.burn_jobs[i].device
+            ));
+            self.burn_jobs[i].apply(UiProgress::Error(reason), &mut self.logs, tag);
+            self.record_history(i);
+        }
+    }
+
+    /// Drains every burn job's channel, refills free writer slots and
+    /// settles the overall status once the whole run has finished.
+    fn poll_burn_jobs(&mut self) {
+        let tag = self.burn_jobs.len() > 1;
+        let mut newly_finished = Vec::new();
+        for (i, job) in self.burn_jobs.iter_mut().enumerate() {
+            let rx = match &job.receiver {
+                Some(rx) => rx,
+                None => continue,
+            };
+            let mut updates = Vec::new();
+            let mut disconnected = false;
+            loop {
+                match rx.try_recv() {
+                    Ok(update) => updates.push(update),
+                    Err(mpsc::TryRecvError::Empty) => break,
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        disconnected = true;
+                        break;
+                    }
+                }
+            }
+            let was_finished = job.is_finished();
+            for update in updates {
+                job.apply(update, &mut self.logs, tag);
+            }
+            // The writer dropped its sender without a Done or Error: it panicked.
+            if disconnected && !job.is_finished() {
+                job.apply(
+                    UiProgress::Error(BurnError::Other(
+                        "Burn thread terminated unexpectedly".to_string(),
+                    )),
+                    &mut self.logs,
+                    tag,
+                );
+            }
+            if !was_finished && job.is_finished() {
+                newly_finished.push(i);
+            }
+        }
+        for i in newly_finished {
+            self.record_history(i);
+        }
+
+        if self.status == AppStatus::Stopping {
+            self.finish_stopping();
+            return;
+        }
+        if self.status != AppStatus::Burning || self.burn_jobs.is_empty() {
+            return;
+        }
+        self.start_queued_burns();
+        if self.burn_jobs.iter().all(BurnJob::is_finished) {
+            let failed: Vec<&BurnJob> = self
+                .burn_jobs
+                .iter()
+                .filter(|j| matches!(j.status, AppStatus::Error(_)))
+                .collect();
+            if let [job] = self.burn_jobs.as_slice() {
+                self.summary = job.summary.clone();
+            }
+            self.status = match failed.as_slice() {
+                [] => AppStatus::Done,
+                [job] if self.burn_jobs.len() == 1 => job.status.clone(),
+                _ => AppStatus::Error(BurnError::Other(format!(
+                    "{} of {} burns failed",
+                    failed.len(),
+                    self.burn_jobs.len()
+                ))),
+            };
+            let iso_name = Path::new(&self.burn_options.iso_path)
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned());
+            self.notify_finished(iso_name);
+            if self.status == AppStatus::Done {
+                self.advance_image_queue();
+            }
+            if self.next_device_mode && self.status == AppStatus::Done {
+                self.awaiting_next_device = true;
+                self.logs.push(LogLevel::Info, "Insert next device…");
+            }
+        }
+    }
+
+    /// Asks every running burn to stop after its current block and drops queued
+    /// ones. The app stays `Stopping` until every writer has exited.
+    fn stop_burn(&mut self) {
+        self.logs.push(LogLevel::Warn, "Cancelled by user");
+        let mut cancelled = Vec::new();
+        for (i, job) in self.burn_jobs.iter_mut().enumerate() {
+            if job.is_finished() {
+                continue;
+            }
+            if job.is_queued() {
+                job.status =
+                    AppStatus::Error(BurnError::Other("Cancelled before starting".to_string()));
+                continue;
+            }
+            cancelled.push(i);
+            job.cancel.store(true, Ordering::SeqCst);
+            // Wake a paused writer so it can see the cancel.
+            job.pause.store(false, Ordering::SeqCst);
+            // Only a job that has started writing leaves a device half-written.
+            if job.status == AppStatus::Burning && job.write_stats.is_some() && !job.dry_run {
+                self.logs.push(
+                    LogLevel::Warn,
+                    format!(
+                        "WARNING: {} was only partially written and is NOT bootable. Burn it again before use.",
+                        job.device
+                    ),
+                );
+            }
+            // The worker flushes and exits on its own; its thread and receiver
+            // stay until then, so its final log lines still reach the panel.
+            job.status = AppStatus::Error(BurnError::Cancelled);
+        }
+        for i in cancelled {
+            self.record_history(i);
+        }
+        self.status = AppStatus::Stopping;
+    }
+
+    /// Ends a stop once every cancelled writer has exited: joins them, then
+    /// drops their channels so nothing they sent late can reach the jobs.
+    fn finish_stopping(&mut self) {
+        let exited = self
+            .burn_jobs
+            .iter()
+            .all(|j| j.thread.as_ref().map_or(true, thread::JoinHandle::is_finished));
+        if !exited {
+            return;
+        }
+        for job in &mut self.burn_jobs {
+            if let Some(handle) = job.thread.take() {
+                let _ = handle.join();
+            }
+            job.receiver = None;
+        }
+        self.logs.push(LogLevel::Info, "All writers stopped");
+        self.status = AppStatus::Idle;
+    }
+
+    /// Appends a finished burn job to the persisted history.
+    fn record_history(&mut self, index: usize) {
+        let job = &self.burn_jobs[index];
+        let result = match &job.status {
+            AppStatus::Error(e) => e.to_string(),
+            _ => "OK".to_string(),
+        };
+        let entry = HistoryEntry {
+            finished_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            iso_name: Path::new(&self.burn_options.iso_path)
+                .file_name()
+                .map_or_else(
+                    || self.burn_options.iso_path.clone(),
+                    |n| n.to_string_lossy().into_owned(),
+                ),
+            device: job.device.clone(),
+            result,
+            duration_secs: job.started.map_or(0, |t| t.elapsed().as_secs()),
+        };
+        if let Err(e) = self.history.record(entry) {
+            self.logs
+                .push(LogLevel::Warn, format!("Failed to save burn history: {}", e));
+        }
+    }
+
+    /// Pauses every running burn, or resumes them all if any is paused. Queued
+    /// jobs take the flag too, so one started from the queue mid-pause
+    /// starts paused.
+    fn toggle_pause(&mut self) {
+        let pause = !self.is_paused();
+        for job in self.burn_jobs.iter().filter(|j| !j.is_finished()) {
+            job.pause.store(pause, Ordering::SeqCst);
+        }
+        self.logs.push(
+            LogLevel::Info,
+            if pause { "Burn paused" } else { "Burn resumed" },
+        );
+    }
+
+    /// Whether the current burn run is paused.
+    fn is_paused(&self) -> bool {
+        self.status == AppStatus::Burning && self.burn_jobs.iter().any(BurnJob::is_paused)
+    }
+
+    /// Tells the desktop that the operation just ended, if notifications are on
+    /// and it ran long enough that the user has likely looked away. `image`
+    /// names the burned image, when there is one.
+    fn notify_finished(&self, image: Option<String>) {
+        let long = self
+            .operation_started
+            .map_or(false, |started| started.elapsed() >= NOTIFY_AFTER);
+        if !self.notifications || !long {
+            return;
+        }
+        let (outcome, body) = match &self.status {
+            AppStatus::Done => (
+                "finished",
+                self.summary
+                    .clone()
+                    .unwrap_or_else(|| "Operation completed successfully.".to_string()),
+            ),
+            AppStatus::Error(e) => ("failed", e.to_string()),
+            _ => return,
+        };
+        let summary = match image {
+            Some(image) => format!("{} {}", image, outcome),
+            None => format!("{}: operation {}", APP_TITLE, outcome),
+        };
+        // Showing can block on the desktop's notification daemon, so keep it
+        // off the UI thread; a notification that fails to show is just skipped.
+        thread::spawn(move || {
+            let _ = notify_rust::Notification::new()
+                .appname(APP_TITLE)
+                .summary(&summary)
+                .body(&body)
+                .show();
+        });
+    }
+
+    /// Restarts the app with administrator rights after a permission error.
+    /// This window stays open until `poll_elevation` sees the new copy start.
+    fn relaunch_elevated(&mut self, ctx: &egui::Context) {
+        match spawn_elevated() {
+            Ok(child) => {
+                self.logs
+                    .push(LogLevel::Info, "Waiting for administrator authentication...");
+                self.elevation = Some(child);
+                ctx.request_repaint_after(Duration::from_millis(250));
+            }
+            Err(e) => self.logs.push(
+                LogLevel::Error,
+                format!("ERROR: Could not restart as administrator: {}", e),
+            ),
+        }
+    }
+
+    /// Closes this window (saving its settings) once the elevated copy has
+    /// started, or keeps it if the authentication prompt was cancelled.
+    fn poll_elevation(&mut self, ctx: &egui::Context) {
+        let child = match &mut self.elevation {
+            Some(child) => child,
+            None => return,
+        };
+        match child.try_wait() {
+            Ok(None) => ctx.request_repaint_after(Duration::from_millis(250)),
+            Ok(Some(status)) if status.success() => {
+                self.elevation = None;
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+            Ok(Some(_)) => {
+                self.elevation = None;
+                self.logs.push(
+                    LogLevel::Warn,
+                    "Restart as administrator was cancelled or refused",
+                );
+            }
+            Err(e) => {
+                self.elevation = None;
+                self.logs.push(
+                    LogLevel::Error,
+                    format!("ERROR: Could not restart as administrator: {}", e),
+                );
+            }
+        }
+    }
+
+    /// Persists the tunable options, theme and window size for the next launch.
+    fn save_config(&mut self, ctx: &egui::Context) {
+        let window_size = ctx
+            .input(|i| i.viewport().inner_rect)
+            .map(|rect| [rect.width(), rect.height()]);
+        let config = AppConfig {
+            burn_options: self.burn_options.clone(),
+            is_dark_mode: self.is_dark_mode,
+            toolbar_labels: self.toolbar_labels,
+            compact_mode: self.compact_mode,
+            smooth_progress: self.smooth_progress,
+            notifications: self.notifications,
+            size_units: self.size_units,
+            language: self.language,
+            window_size,
+            recent_isos: self.recent_isos.clone(),
+            temp_dir: self.temp_dir.clone(),
+        };
+        if let Err(e) = config.save() {
+            self.logs
+                .push(LogLevel::Error, format!("ERROR: Failed to save config: {}", e));
+        }
+    }
+
+    /// What the status bar reflects: a lone burn job's own phase, the slowest
+    /// device's progress in a multi-device run, or otherwise the app-wide state.
+    fn status_snapshot(&self) -> (&AppStatus, f32, Option<&WriteStats>) {
+        match self.burn_jobs.as_slice() {
+            [job] if self.status == AppStatus::Burning => {
+                (&job.status, job.overall_progress(), job.write_stats.as_ref())
+            }
+            jobs if jobs.len() > 1 && self.status == AppStatus::Burning => {
+                // Follow the slowest device: the run is only done when it is.
+                let slowest = jobs
+                    .iter()
+                    .map(|j| if j.is_finished() { 1.0 } else { j.overall_progress() })
+                    .fold(1.0, f32::min);
+                (&self.status, slowest, None)
+            }
+            _ => (&self.status, self.burn_progress, self.write_stats.as_ref()),
+        }
+    }
+
+    /// Puts burn or verify progress in the window title, e.g.
+    /// "RustBurn — 63% (ETA 00:24)", so it shows while minimized.
+    fn update_window_title(&mut self, ctx: &egui::Context) {
+        let (status, progress, stats) = self.status_snapshot();
+        let title = match status {
+            AppStatus::CheckingIso | AppStatus::Burning | AppStatus::Verifying => {
+                let eta = stats
+                    .filter(|_| *status == AppStatus::Burning)
+                    .and_then(eta_secs)
+                    .map(|secs| format!(" (ETA {})", format_duration(secs)))
+                    .unwrap_or_default();
+                format!("RustBurn — {:.0}%{}", progress * 100.0, eta)
+            }
+            _ => APP_TITLE.to_string(),
+        };
+        if title != self.window_title {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Title(title.clone()));
+            self.window_title = title;
+        }
+    }
+
+    /// Moves `displayed_progress` part of the way toward the real progress,
+    /// framerate-independently. It snaps on a drop (a new operation) so the
+    /// bar never slides backwards.
+    fn ease_progress(&mut self, ctx: &egui::Context) {
+        let (_, target, _) = self.status_snapshot();
+        if !self.smooth_progress || target < self.displayed_progress {
+            self.displayed_progress = target;
+            return;
         }
+        let dt = ctx.input(|i| i.stable_dt).min(0.1);
+        let step = 1.0 - (-dt * PROGRESS_EASE_RATE).exp();
+        self.displayed_progress += (target - self.displayed_progress) * step;
     }
 
+    /// Whether nothing is running, so the toolbar's start buttons and their
+    /// shortcuts are enabled. A finished operation leaves the app ready for
+    /// the next one.
     fn is_idle(&self) -> bool {
         matches!(
             self.status,
@@ -579,16 +4417,554 @@ impl RustBurnApp {
 
 // --- Helper Functions ---
 
+/// Adds a toolbar icon button, with `label` beside the icon when `with_label`
+/// is set. Screen readers announce `label` either way.
+fn toolbar_button(
+    ui: &mut egui::Ui,
+    enabled: bool,
+    icon: &TextureHandle,
+    label: &str,
+    with_label: bool,
+) -> egui::Response {
+    let button = if with_label {
+        egui::Button::image_and_text(icon, label)
+    } else {
+        egui::Button::image(icon)
+    };
+    ui.add_enabled(enabled, button).with_accessible_name(label)
+}
+
+/// Gives a button whose visible text is a glyph or an icon a spoken name.
+trait AccessibleName {
+    fn with_accessible_name(self, name: &str) -> Self;
+}
+
+impl AccessibleName for egui::Response {
+    fn with_accessible_name(self, name: &str) -> Self {
+        self.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Button, name));
+        self
+    }
+}
+
 /// Loads an image from bytes and converts it into an egui `TextureHandle`.
-fn load_icon(ctx: &egui::Context, name: &str, bytes: &[u8]) -> TextureHandle {
-    let image = image::load_from_memory(bytes).expect("Failed to load icon");
+fn load_icon(
+    ctx: &egui::Context,
+    name: &str,
+    bytes: &[u8],
+) -> Result<TextureHandle, image::ImageError> {
+    let image = image::load_from_memory(bytes)?;
     let size = [image.width() as _, image.height() as _];
     let image_buffer = image.to_rgba8();
     let pixels = image_buffer.as_flat_samples();
     let color_image = egui::ColorImage::from_rgba_unmultiplied(size, pixels.as_slice());
+    Ok(ctx.load_texture(name, color_image, Default::default()))
+}
+
+/// A solid 32x32 square standing in for an icon that failed to load. The colour is
+/// derived from the icon name so missing icons stay distinguishable; the buttons'
+/// hover text still says what each one does.
+fn placeholder_icon(ctx: &egui::Context, name: &str) -> TextureHandle {
+    let hash = name
+        .bytes()
+        .fold(0u32, |h, b| h.wrapping_mul(31).wrapping_add(b as u32));
+    let [r, g, b, _] = hash.to_le_bytes();
+    let color = egui::Color32::from_rgb(r | 0x40, g | 0x40, b | 0x40);
+    let color_image = egui::ColorImage::new([32, 32], color);
     ctx.load_texture(name, color_image, Default::default())
 }
 
+/// Starts the core's hotplug watcher and relays its events, waking the UI for each one
+/// so the list updates even while nothing else is repainting.
+fn watch_devices(ctx: egui::Context) -> mpsc::Receiver<DeviceEvent> {
+    let (core_tx, core_rx) = mpsc::channel();
+    let (tx, rx) = mpsc::channel();
+    RustBurn::watch_devices(core_tx);
+    thread::spawn(move || {
+        for event in core_rx {
+            if tx.send(event).is_err() {
+                break;
+            }
+            ctx.request_repaint();
+        }
+    });
+    rx
+}
+
+/// Looks up a whole device or one of its partitions by node path in `devices`.
+fn find_in<'a>(devices: &'a [UsbDevice], path: &str) -> Option<&'a UsbDevice> {
+    devices
+        .iter()
+        .flat_map(|d| std::iter::once(d).chain(&d.partitions))
+        .find(|d| d.device == path)
+}
+
+/// Whether `a` and `b` are a whole device and one of its own partitions, in either order.
+fn nested(devices: &[UsbDevice], a: &str, b: &str) -> bool {
+    devices.iter().any(|d| {
+        (d.device == a && d.partitions.iter().any(|p| p.device == b))
+            || (d.device == b && d.partitions.iter().any(|p| p.device == a))
+    })
+}
+
+/// Where a device or any of its partitions is mounted, as found by the last scan.
+fn mount_points(device: &UsbDevice) -> Vec<String> {
+    std::iter::once(device)
+        .chain(&device.partitions)
+        .flat_map(|d| d.mount_points.iter().cloned())
+        .collect()
+}
+
+/// Whether `path` lives on a filesystem mounted from `device` or one of its
+/// partitions, so writing the device would destroy the file.
+fn holds_file(device: &UsbDevice, path: &str) -> bool {
+    let path = match std::fs::canonicalize(path) {
+        Ok(path) => strip_verbatim(path),
+        Err(_) => return false,
+    };
+    mount_points(device)
+        .iter()
+        .any(|mount| path.starts_with(mount))
+}
+
+/// Drops the `\\?\` prefix canonicalize adds on Windows, so the path compares
+/// with the plain drive-letter and UNC mount points a scan reports.
+fn strip_verbatim(path: PathBuf) -> PathBuf {
+    let stripped = {
+        let text = path.to_string_lossy();
+        if let Some(rest) = text.strip_prefix(r"\\?\UNC\") {
+            Some(PathBuf::from(format!(r"\\{}", rest)))
+        } else {
+            text.strip_prefix(r"\\?\").map(PathBuf::from)
+        }
+    };
+    stripped.unwrap_or(path)
+}
+
+/// Top-down layout aligned to the side the UI language reads from.
+fn reading_layout() -> egui::Layout {
+    if i18n::language().is_rtl() {
+        egui::Layout::top_down(egui::Align::Max)
+    } else {
+        egui::Layout::top_down(egui::Align::Min)
+    }
+}
+
+/// Like `ui.horizontal`, but placed right to left for right-to-left languages.
+fn reading_row<R>(
+    ui: &mut egui::Ui,
+    add_contents: impl FnOnce(&mut egui::Ui) -> R,
+) -> egui::InnerResponse<R> {
+    if i18n::language().is_rtl() {
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), add_contents)
+    } else {
+        ui.horizontal(add_contents)
+    }
+}
+
+/// Renders one selectable row of the device list, flagging system and
+/// write-protected devices, with the progress of `job` when it has one.
+fn device_row(
+    ui: &mut egui::Ui,
+    device: &UsbDevice,
+    units: SizeUnits,
+    is_selected: bool,
+    selectable: bool,
+    job: Option<&BurnJob>,
+) -> egui::Response {
+    let mut label = device_label(device, units);
+    if device.unresponsive {
+        label.push_str("  (unresponsive)");
+    } else if device.is_system {
+        label.push_str("  [SYSTEM DISK]");
+    } else if let Some(media) = existing_media(device) {
+        label.push_str(&format!("  [{}]", media));
+    }
+    if device.write_protected {
+        label = format!("🔒 {}", label);
+    }
+    if device.suspected_fake {
+        label = format!("⚠ {}", label);
+    }
+    let text = if device.is_system {
+        egui::RichText::new(label).color(egui::Color32::RED)
+    } else {
+        egui::RichText::new(label)
+    };
+    let response = ui
+        .horizontal(|ui| {
+            let response =
+                ui.add_enabled(selectable, egui::SelectableLabel::new(is_selected, text));
+            // A thin bar beside the device keeps each stick next to its own progress.
+            if let Some(job) = job {
+                ui.add(
+                    egui::ProgressBar::new(job.overall_progress())
+                        .desired_width(160.0)
+                        .desired_height(8.0),
+                )
+                .on_hover_text(job.status_line());
+            }
+            response
+        })
+        .inner;
+    if device.unresponsive {
+        response.on_hover_text(tr!(
+            "The device didn't answer in time during the scan. Re-plug it and scan again."
+        ))
+    } else if device.write_protected {
+        response.on_hover_text(tr!(
+            "Write-protected: turn off the lock switch on the stick to burn it"
+        ))
+    } else if device.suspected_fake {
+        response.on_hover_text(tr!(
+            "The reported capacity looks suspicious for this device, a common sign of a \
+             fake-capacity stick. Burn with \"Check for bad blocks\" on before trusting it."
+        ))
+    } else {
+        response
+    }
+}
+
+/// Formats a device as "/dev/sdb  (Kingston DataTraveler) - 32.0 GB  [SN 0123, port 1-2.3]".
+fn device_label(device: &UsbDevice, units: SizeUnits) -> String {
+    let mut label = format!(
+        "{}  ({} {}) - {}",
+        device.device,
+        device.vendor,
+        device.model,
+        format_size(device.size, units)
+    );
+    let ids: Vec<String> = [("SN", &device.serial), ("port", &device.bus_path)]
+        .iter()
+        .filter(|(_, value)| !value.is_empty())
+        .map(|(name, value)| format!("{} {}", name, value))
+        .collect();
+    if !ids.is_empty() {
+        label.push_str(&format!("  [{}]", ids.join(", ")));
+    }
+    label
+}
+
+/// Display name of a `WriteMode`, for the write mode picker.
+fn write_mode_name(mode: WriteMode) -> String {
+    match mode {
+        WriteMode::RawDd => tr!("DD (raw copy)"),
+        WriteMode::FileCopy => tr!("ISO (file copy)"),
+    }
+}
+
+/// Display name of an OS family, as detected in an image or on a device.
+fn os_family_name(os: &OsFamily) -> &'static str {
+    match os {
+        OsFamily::Windows => "Windows",
+        OsFamily::Linux => "Linux",
+        OsFamily::Unknown => "Unknown OS",
+    }
+}
+
+/// Describes the bootable media the last scan found on a device, e.g.
+/// `Windows "CCCOMA_X64"`. A plain data volume doesn't count.
+fn existing_media(device: &UsbDevice) -> Option<String> {
+    let os = os_family_name(device.detected_os.as_ref()?);
+    Some(match &device.detected_label {
+        Some(label) => format!("{} \"{}\"", os, label),
+        None => os.to_string(),
+    })
+}
+
+/// Formats a capacity as "32.0 GB" (decimal) or "29.8 GiB" (binary).
+fn format_size(bytes: u64, units: SizeUnits) -> String {
+    match units {
+        SizeUnits::Decimal => format!("{:.1} GB", bytes as f64 / 1e9),
+        SizeUnits::Binary => format!("{:.1} GiB", bytes as f64 / (1u64 << 30) as f64),
+    }
+}
+
+/// Returns true for `.iso`/`.img`/`.bin` files, optionally compressed (`.img.xz` etc.).
+fn is_supported_image(path: &Path) -> bool {
+    let name = match path.file_name().and_then(|n| n.to_str()) {
+        Some(name) => name.to_lowercase(),
+        None => return false,
+    };
+    let name = COMPRESSED_EXTENSIONS
+        .iter()
+        .find_map(|ext| name.strip_suffix(&format!(".{}", ext)))
+        .unwrap_or(name.as_str());
+    IMAGE_EXTENSIONS
+        .iter()
+        .any(|ext| name.ends_with(&format!(".{}", ext)))
+}
+
+/// Guesses from the file name whether an image is an Ubuntu/Debian-style live ISO.
+fn is_debian_live_image(path: &str) -> bool {
+    let name = Path::new(path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    name.contains(".iso") && DEBIAN_LIVE_HINTS.iter().any(|hint| name.contains(hint))
+}
+
+/// Whether a folder has the layout of extracted Windows install media:
+/// `sources/install.wim` (or `.esd`), `boot/` and `efi/`.
+fn looks_like_windows_media(folder: &Path) -> bool {
+    let sources = folder.join("sources");
+    (sources.join("install.wim").is_file() || sources.join("install.esd").is_file())
+        && folder.join("boot").is_dir()
+        && folder.join("efi").is_dir()
+}
+
+/// The name to save a download under: the last path segment of `url`, or
+/// `download.iso` if that is empty or could name a file outside the folder.
+fn download_file_name(url: &str) -> String {
+    url.split(['?', '#'])
+        .next()
+        .and_then(|path| path.rsplit('/').next())
+        .filter(|name| {
+            *name != ".."
+                && !name.contains(['\\', ':'])
+                && Path::new(name).file_name() == Some(std::ffi::OsStr::new(name))
+        })
+        .unwrap_or("download.iso")
+        .to_string()
+}
+
+/// Size in bytes of an uncompressed image, or `None` if it is compressed or unreadable.
+fn image_size(path: &Path) -> Option<u64> {
+    let name = path.file_name()?.to_str()?.to_lowercase();
+    if COMPRESSED_EXTENSIONS
+        .iter()
+        .any(|ext| name.ends_with(&format!(".{}", ext)))
+    {
+        return None;
+    }
+    std::fs::metadata(path).ok().map(|m| m.len())
+}
+
+/// Human-readable status line for an operation phase.
+fn status_text(status: &AppStatus, progress: f32, stats: Option<&WriteStats>) -> String {
+    let percent = format!("{:.0}", progress * 100.0);
+    match status {
+        AppStatus::Idle => tr!("Ready"),
+        AppStatus::Scanning => tr!("Scanning for devices..."),
+        AppStatus::CheckingIso => tr!("Checking ISO integrity... {}%", percent),
+        AppStatus::Burning => match stats {
+            // Every byte is handed to the OS; the writer is flushing its caches.
+            Some(stats) if stats.total > 0 && stats.bytes_done >= stats.total => {
+                tr!("Burning... {}% (flushing buffers…)", percent)
+            }
+            Some(stats) if stats.total == 0 => {
+                tr!("Burning... ({})", format_write_stats(stats))
+            }
+            Some(stats) => tr!("Burning... {}% ({})", percent, format_write_stats(stats)),
+            None => tr!("Burning... {}%", percent),
+        },
+        AppStatus::CreatingWinIso => match stats {
+            Some(stats) => tr!(
+                "Creating Windows ISO... {}% ({})",
+                percent,
+                format_write_stats(stats)
+            ),
+            None => tr!("Creating Windows ISO... {}%", percent),
+        },
+        AppStatus::CreatingIso => match stats {
+            Some(stats) => tr!("Creating ISO... {}% ({})", percent, format_write_stats(stats)),
+            None => tr!("Creating ISO... {}%", percent),
+        },
+        AppStatus::Verifying => tr!("Verifying written data... {}%", percent),
+        AppStatus::SettingUpBootable => tr!("Making device bootable... {}%", percent),
+        AppStatus::Ejecting => tr!("Ejecting device..."),
+        AppStatus::Erasing if progress > 0.0 => tr!("Erasing device... {}%", percent),
+        AppStatus::Erasing => tr!("Erasing device..."),
+        AppStatus::Formatting if progress > 0.0 => tr!("Formatting device... {}%", percent),
+        AppStatus::Formatting => tr!("Formatting device..."),
+        AppStatus::Benchmarking => tr!("Testing device speed..."),
+        AppStatus::Hashing => tr!("Computing checksum... {}%", percent),
+        AppStatus::Stopping => tr!("Stopping writers..."),
+        AppStatus::Cloning => match stats {
+            Some(stats) => tr!("Cloning device... {}% ({})", percent, format_write_stats(stats)),
+            None => tr!("Cloning device... {}%", percent),
+        },
+        AppStatus::Downloading => match stats {
+            Some(stats) if stats.total == 0 => {
+                tr!("Downloading ISO... ({})", format_write_stats(stats))
+            }
+            Some(stats) => tr!(
+                "Downloading ISO... {}% ({})",
+                percent,
+                format_write_stats(stats)
+            ),
+            None => tr!("Downloading ISO... {}%", percent),
+        },
+        AppStatus::Done => tr!("Operation completed successfully."),
+        AppStatus::Error(e) => tr!("Error: {}", e),
+    }
+}
+
+/// Formats write stats as "1.2 GB / 2.6 GB, 38 MB/s, ETA 00:38".
+fn format_write_stats(stats: &WriteStats) -> String {
+    if stats.total == 0 {
+        return format!(
+            "{:.1} GB written, {:.0} MB/s",
+            stats.bytes_done as f64 / 1e9,
+            stats.bytes_per_sec / 1e6
+        );
+    }
+    let mut text = format!(
+        "{:.1} GB / {:.1} GB, {:.0} MB/s",
+        stats.bytes_done as f64 / 1e9,
+        stats.total as f64 / 1e9,
+        stats.bytes_per_sec / 1e6
+    );
+    if let Some(secs) = eta_secs(stats) {
+        text.push_str(&format!(", ETA {}", format_duration(secs)));
+    }
+    text
+}
+
+/// Seconds left at the current rate, if the total is known and data is moving.
+fn eta_secs(stats: &WriteStats) -> Option<u64> {
+    if stats.bytes_per_sec > 0.0 && stats.total > stats.bytes_done {
+        let remaining = (stats.total - stats.bytes_done) as f64 / stats.bytes_per_sec;
+        Some(remaining as u64)
+    } else {
+        None
+    }
+}
+
+/// Checks that `dir` is a writable directory and returns its free space.
+fn check_work_dir(dir: &Path) -> Result<u64, String> {
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()));
+    }
+    let probe = dir.join(".rustburn-write-test");
+    std::fs::write(&probe, b"")
+        .map_err(|e| format!("{} is not writable: {}", dir.display(), e))?;
+    let _ = std::fs::remove_file(&probe);
+    RustBurn::free_space(&dir.display().to_string())
+        .map_err(|e| format!("Could not read free space: {}", e))
+}
+
+/// Formats a wall-clock time as "2024-05-01 13:37:00 UTC".
+fn format_utc(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    // Days-to-civil conversion (proleptic Gregorian), after Howard Hinnant.
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    let tod = secs % 86_400;
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        tod / 3600,
+        (tod / 60) % 60,
+        tod % 60
+    )
+}
+
+/// Formats a completion summary as "Wrote 2.6 GB in 01:12 (36 MB/s), verified OK."
+fn format_summary(bytes: u64, elapsed: Duration, avg_mbps: f64, verified: bool) -> String {
+    let gb = format!("{:.1}", bytes as f64 / 1e9);
+    let duration = format_duration(elapsed.as_secs());
+    let mbps = format!("{:.0}", avg_mbps);
+    if verified {
+        tr!(
+            "Wrote {} GB in {} ({} MB/s), verified OK.",
+            gb,
+            duration,
+            mbps
+        )
+    } else {
+        tr!("Wrote {} GB in {} ({} MB/s).", gb, duration, mbps)
+    }
+}
+
+/// Formats a number of seconds as MM:SS, or HH:MM:SS past an hour.
+fn format_duration(secs: u64) -> String {
+    let (h, m, s) = (secs / 3600, (secs / 60) % 60, secs % 60);
+    if h > 0 {
+        format!("{:02}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{:02}:{:02}", m, s)
+    }
+}
+
+/// Starts another copy of this program with administrator rights, and the
+/// same command-line arguments: through pkexec on Linux, an authorization
+/// prompt on macOS and UAC on Windows. The returned launcher exits once the
+/// new copy is running, successfully only if the prompt was accepted.
+fn spawn_elevated() -> std::io::Result<std::process::Child> {
+    let exe = std::env::current_exe()?;
+    let args: Vec<std::ffi::OsString> = std::env::args_os().skip(1).collect();
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let mut command = {
+        // pkexec starts from a clean environment; pass on what the window needs.
+        let mut command = std::process::Command::new("pkexec");
+        command.arg("env");
+        for var in ["DISPLAY", "WAYLAND_DISPLAY", "XAUTHORITY", "XDG_RUNTIME_DIR"] {
+            if let Ok(value) = std::env::var(var) {
+                command.arg(format!("{}={}", var, value));
+            }
+        }
+        // pkexec waits for what it runs, so a shell starts the copy in the
+        // background and returns; the path and arguments stay separate argv
+        // entries and are never parsed by the shell.
+        command
+            .args(["sh", "-c", "\"$0\" \"$@\" > /dev/null 2>&1 &"])
+            .arg(&exe)
+            .args(&args);
+        command
+    };
+    #[cfg(target_os = "macos")]
+    let mut command = {
+        // The path and arguments come in as the script's argv and are quoted
+        // by AppleScript itself, so nothing is spliced into the source.
+        let mut command = std::process::Command::new("osascript");
+        for line in [
+            "on run argv",
+            "set cmd to \"\"",
+            "repeat with arg in argv",
+            "set cmd to cmd & quoted form of (arg as text) & \" \"",
+            "end repeat",
+            "do shell script cmd & \"> /dev/null 2>&1 &\" with administrator privileges",
+            "end run",
+        ] {
+            command.arg("-e").arg(line);
+        }
+        command.arg(&exe).args(&args);
+        command
+    };
+    #[cfg(windows)]
+    let mut command = {
+        // Each value becomes a single-quoted PowerShell literal ('' escapes a
+        // quote); arguments are double-quoted for the new process's own parser.
+        let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+        let mut script = format!(
+            "Start-Process -FilePath {} -Verb RunAs -ErrorAction Stop",
+            quote(&exe.to_string_lossy())
+        );
+        if !args.is_empty() {
+            let list: Vec<String> = args
+                .iter()
+                .map(|a| quote(&format!("\"{}\"", a.to_string_lossy())))
+                .collect();
+            script.push_str(&format!(" -ArgumentList {}", list.join(",")));
+        }
+        let mut command = std::process::Command::new("powershell");
+        command.args(["-NoProfile", "-Command"]).arg(script);
+        command
+    };
+    command.spawn()
+}
+
 /// Sets up custom fonts for the egui context.
 fn setup_custom_fonts(ctx: &egui::Context) {
     let mut fonts = FontDefinitions::default();
@@ -601,21 +4977,39 @@ fn setup_custom_fonts(ctx: &egui::Context) {
         .entry(FontFamily::Proportional)
         .or_default()
         .insert(0, "my_font".to_owned());
+    // The bundled font has no Hebrew glyphs; borrow them from a system font.
+    let fallback = HEBREW_FALLBACK_FONTS
+        .iter()
+        .find_map(|path| std::fs::read(path).ok());
+    if let Some(bytes) = fallback {
+        fonts
+            .font_data
+            .insert("hebrew_fallback".to_owned(), FontData::from_owned(bytes));
+        for family in [FontFamily::Proportional, FontFamily::Monospace] {
+            fonts
+                .families
+                .entry(family)
+                .or_default()
+                .push("hebrew_fallback".to_owned());
+        }
+    }
     ctx.set_fonts(fonts);
 }
 
 /// The main entry point of the application.
 fn main() {
+    let config = AppConfig::load();
+    let launch = LaunchArgs::parse(std::env::args().skip(1));
     let options = NativeOptions {
         viewport: egui::ViewportBuilder::default()
-            .with_inner_size([800.0, 600.0])
+            .with_inner_size(config.window_size.unwrap_or([800.0, 600.0]))
             .with_min_inner_size([600.0, 400.0]),
         ..Default::default()
     };
     eframe::run_native(
-        "RustBurn Professional",
+        APP_TITLE,
         options,
-        Box::new(|cc| Box::new(RustBurnApp::new(cc))),
+        Box::new(move |cc| Box::new(RustBurnApp::new(cc, config, launch))),
     )
     .expect("Failed to run eframe");
 }
@@ -0,0 +1,630 @@
+// rustburn-cli/src/main.rs
+
+// A headless front end for rustburn-core, for machines where eframe can't start.
+#[path = "../../labels.rs"]
+mod labels;
+
+use rustburn_core::{
+    BootType, BurnError, BurnOptions, FsType, LogLevel, OsFamily, PartitionScheme, RustBurn,
+    UiProgress, VerifyMode, WriteMode, AUTO_BLOCK_SIZE,
+};
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::atomic::AtomicBool;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage:
+  rustburn-cli list
+  rustburn-cli --iso <FILE> --device <PATH> [--device <PATH>...] [OPTIONS]
+
+Options:
+  --write-mode <auto|dd|iso>      Copy the image block for block (dd) or format the device
+                                  and copy its files (iso); auto picks from the image
+  --threads <N>                   Reader threads feeding the writer (default: 1)
+  --verify                        Verify the device after burning
+  --verify-sample <PERCENT>       Verify only a random sample of blocks (e.g. 5)
+  --verify-skip-padding           Stop verifying at the ISO's declared volume size
+  --buffered-verify               Verify through the page cache instead of direct IO
+  --bad-blocks                    Pattern-test the whole device before burning
+  --wipe-table                    Zero the old partition tables (MBR and backup GPT) first
+  --bootable <uefi|legacy|hybrid> Make the device bootable
+  --partition-scheme <mbr|gpt>    Partition table for --bootable (default: mbr)
+  --label <NAME>                  Volume label for --bootable (FAT32: up to 11 chars)
+  --persistence <MB>              Add a persistence partition (bootable live images)
+  --extra-file <SOURCE>=<DEST>    Copy a file (autounattend.xml, preseed, kickstart...) to
+                                  DEST on the new volume after --bootable setup; repeatable
+  --block-size <KB|auto>          Block size in KB (a power of two up to 65536), or auto to
+                                  probe the device (default: 1024)
+  --retries <N>                   Retries per failed block write (default: 3)
+  --sync-every <MB>               Flush the device after this much data (default: 256)
+  --max-speed <MB/s>              Cap the write rate (default: unlimited)
+  --unmount                       Unmount the device's filesystems before writing
+  --eject                         Eject the device after a successful burn
+  --reverify                      Eject, wait for the device to reappear and verify it
+                                  again, bypassing any cache in the USB bridge
+  --sidecar                       Record the image hash, device serial and time in
+                                  <image>.burned.sha256 after a successful burn
+  --on-complete <CMD>             Run CMD through the shell after a successful burn, with
+                                  RUSTBURN_DEVICE, RUSTBURN_ISO and RUSTBURN_RESULT set.
+                                  It runs with this process's privileges (often root)
+  --dry-run                       Run every check but write nothing
+  --preserve-partition <PATH>     Back up this partition of the device first and re-create
+                                  it in the space left after the image (single device only)
+  --resume                        Continue from a previous burn's checkpoint, if any
+                                  (single device only)
+  -v, --verbose                   Show debug log lines (per-block detail)
+  -h, --help                      Show this help";
+
+/// How long `list` waits on one device before reporting it as unresponsive.
+const SCAN_DEVICE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Width of the text progress bar, in characters.
+const BAR_WIDTH: usize = 40;
+
+/// Largest --block-size accepted, matching the GUI's "Custom…" limit.
+const MAX_BLOCK_SIZE_KB: usize = 64 * 1024;
+
+/// The main entry point of the CLI.
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("list") {
+        return list_devices();
+    }
+    if args.iter().any(|a| a == "-h" || a == "--help") {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    let verbose = args.iter().any(|a| a == "-v" || a == "--verbose");
+    let args: Vec<String> = args
+        .into_iter()
+        .filter(|a| a != "-v" && a != "--verbose")
+        .collect();
+    match parse_burn_args(&args) {
+        Ok((options, devices)) if devices.len() > 1 => burn_multi(options, devices, verbose),
+        Ok((options, _)) => burn(options, verbose),
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Prints the table of devices found by `scan_devices`.
+fn list_devices() -> ExitCode {
+    let devices = match RustBurn::scan_devices_with_timeout(SCAN_DEVICE_TIMEOUT) {
+        Ok(devices) => devices,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    if devices.is_empty() {
+        println!("No devices found.");
+        return ExitCode::SUCCESS;
+    }
+    println!(
+        "{:<16} {:<16} {:<24} {:>10}  {:<20} {:<12} NOTES",
+        "DEVICE", "VENDOR", "MODEL", "SIZE", "SERIAL", "PORT"
+    );
+    for device in &devices {
+        let mut notes = if device.unresponsive {
+            "unresponsive; re-plug and scan again"
+        } else if device.is_system {
+            "SYSTEM DISK"
+        } else if device.write_protected {
+            "write-protected"
+        } else if device.suspected_fake {
+            "capacity looks fake; test with --bad-blocks"
+        } else if !device.is_removable {
+            "internal"
+        } else {
+            ""
+        }
+        .to_string();
+        if let Some(os) = &device.detected_os {
+            let media = match &device.detected_label {
+                Some(label) => format!("has {} boot media \"{}\"", os_family_name(os), label),
+                None => format!("has {} boot media", os_family_name(os)),
+            };
+            notes = if notes.is_empty() {
+                media
+            } else {
+                format!("{}; {}", notes, media)
+            };
+        }
+        println!(
+            "{:<16} {:<16} {:<24} {:>7.1} GB  {:<20} {:<12} {}",
+            device.device,
+            device.vendor,
+            device.model,
+            device.size as f64 / 1e9,
+            device.serial,
+            device.bus_path,
+            notes
+        );
+        for partition in &device.partitions {
+            println!(
+                "  {:<14} {:<16} {:<24} {:>7.1} GB",
+                partition.device,
+                "",
+                "(partition)",
+                partition.size as f64 / 1e9
+            );
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Builds `BurnOptions` from the command-line flags, plus every `--device` given.
+fn parse_burn_args(args: &[String]) -> Result<(BurnOptions, Vec<String>), String> {
+    let mut options = BurnOptions::default();
+    let mut devices: Vec<String> = Vec::new();
+    let mut resume = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--iso" => options.iso_path = value()?.clone(),
+            "--device" => {
+                let device = value()?;
+                if devices.contains(device) {
+                    return Err(format!("{} was given more than once", device));
+                }
+                devices.push(device.clone());
+            }
+            "--threads" => {
+                options.threads = value()?
+                    .parse()
+                    .map_err(|_| "--threads expects a number".to_string())?;
+            }
+            "--verify" => options.verify = true,
+            "--verify-sample" => {
+                let percent: f32 = value()?
+                    .parse()
+                    .map_err(|_| "--verify-sample expects a percentage".to_string())?;
+                // Written this way round so NaN is refused too.
+                if !(percent > 0.0 && percent <= 100.0) {
+                    return Err("--verify-sample must be between 0 and 100".to_string());
+                }
+                options.verify = true;
+                options.verify_mode = VerifyMode::Sample {
+                    fraction: percent / 100.0,
+                };
+            }
+            "--verify-skip-padding" => options.verify_skip_trailing_zeros = true,
+            "--buffered-verify" => options.direct_verify = false,
+            "--bad-blocks" => options.bad_block_check = true,
+            "--wipe-table" => options.wipe_table_first = true,
+            "--unmount" => options.auto_unmount = true,
+            "--eject" => options.auto_eject = true,
+            "--reverify" => {
+                options.auto_eject = true;
+                options.reverify_after_eject = true;
+            }
+            "--sidecar" => options.write_sidecar = true,
+            "--on-complete" => options.on_complete_command = Some(value()?.clone()),
+            "--dry-run" => options.dry_run = true,
+            "--resume" => resume = true,
+            "--preserve-partition" => options.preserve_partition = Some(value()?.clone()),
+            "--bootable" => {
+                options.make_bootable = true;
+                options.boot_type = match value()?.to_lowercase().as_str() {
+                    "uefi" => BootType::UEFI,
+                    "legacy" => BootType::Legacy,
+                    "hybrid" => BootType::Hybrid,
+                    other => return Err(format!("unknown boot type '{}'", other)),
+                };
+            }
+            "--extra-file" => {
+                let spec = value()?;
+                let (source, dest) = spec
+                    .rsplit_once('=')
+                    .ok_or_else(|| "--extra-file expects <SOURCE>=<DEST>".to_string())?;
+                options
+                    .extra_files
+                    .push((PathBuf::from(source), dest.to_string()));
+            }
+            "--write-mode" => {
+                options.write_mode = match value()?.to_lowercase().as_str() {
+                    "auto" => None,
+                    "dd" => Some(WriteMode::RawDd),
+                    "iso" => Some(WriteMode::FileCopy),
+                    other => return Err(format!("unknown write mode '{}'", other)),
+                };
+            }
+            "--partition-scheme" => {
+                options.partition_scheme = match value()?.to_lowercase().as_str() {
+                    "mbr" => PartitionScheme::Mbr,
+                    "gpt" => PartitionScheme::Gpt,
+                    other => return Err(format!("unknown partition scheme '{}'", other)),
+                };
+            }
+            "--label" => options.volume_label = Some(value()?.trim().to_uppercase()),
+            "--persistence" => {
+                let mb: u64 = value()?
+                    .parse()
+                    .map_err(|_| "--persistence expects a size in MB".to_string())?;
+                options.persistence_mb = Some(mb);
+            }
+            "--block-size" => {
+                let size = value()?;
+                if size == "auto" {
+                    options.block_size = AUTO_BLOCK_SIZE;
+                } else {
+                    let kb: usize = size
+                        .parse()
+                        .map_err(|_| "--block-size expects a size in KB or auto".to_string())?;
+                    if kb == 0 {
+                        return Err("--block-size must be at least 1 KB, or auto".to_string());
+                    }
+                    if kb > MAX_BLOCK_SIZE_KB {
+                        return Err(format!(
+                            "--block-size must be at most {} KB",
+                            MAX_BLOCK_SIZE_KB
+                        ));
+                    }
+                    if !kb.is_power_of_two() {
+                        return Err("--block-size must be a power of two".to_string());
+                    }
+                    options.block_size = kb
+                        .checked_mul(1024)
+                        .ok_or_else(|| "--block-size is too large".to_string())?;
+                }
+            }
+            "--retries" => {
+                options.max_retries = value()?
+                    .parse()
+                    .map_err(|_| "--retries expects a number".to_string())?;
+            }
+            "--sync-every" => {
+                options.sync_every_mb = value()?
+                    .parse()
+                    .ok()
+                    .filter(|&mb: &u64| mb > 0)
+                    .ok_or_else(|| "--sync-every expects a size in MB above 0".to_string())?;
+            }
+            "--max-speed" => {
+                let mb: u64 = value()?
+                    .parse()
+                    .map_err(|_| "--max-speed expects a rate in MB/s".to_string())?;
+                let bytes = mb
+                    .checked_mul(1_000_000)
+                    .ok_or_else(|| "--max-speed is too large".to_string())?;
+                options.max_write_bytes_per_sec = Some(bytes);
+            }
+            other => return Err(format!("unexpected argument '{}'", other)),
+        }
+    }
+    if options.iso_path.is_empty() {
+        return Err("--iso is required".to_string());
+    }
+    match devices.first() {
+        Some(device) => options.device_path = device.clone(),
+        None => return Err("--device is required".to_string()),
+    }
+    // Bootable setup labels a FAT volume, so the label has to fit FAT32's rules.
+    if let Some(label) = &options.volume_label {
+        if !options.make_bootable {
+            return Err("--label needs --bootable".to_string());
+        }
+        if let Some(error) = labels::format_label_error(FsType::Fat32, label) {
+            return Err(format!("--label: {}", error));
+        }
+    }
+    if !options.extra_files.is_empty() && !options.make_bootable {
+        return Err("--extra-file needs --bootable".to_string());
+    }
+    if options.make_bootable && !options.partition_scheme.supports(&options.boot_type) {
+        return Err(format!(
+            "{:?} boot needs an MBR partition table",
+            options.boot_type
+        ));
+    }
+    // A file copy formats the device, so nothing that relies on a block-for-block
+    // copy of the image can go with it. --verify compares the copied files instead.
+    let write_mode = options
+        .write_mode
+        .unwrap_or_else(|| RustBurn::inspect_iso(&options.iso_path).write_mode);
+    if write_mode == WriteMode::FileCopy {
+        if options.reverify_after_eject {
+            return Err("--reverify needs a dd write mode".to_string());
+        }
+        if options.persistence_mb.is_some() {
+            return Err("--persistence needs a dd write mode".to_string());
+        }
+        if options.preserve_partition.is_some() {
+            return Err("--preserve-partition needs a dd write mode".to_string());
+        }
+    }
+    if options.preserve_partition.is_some() && devices.len() > 1 {
+        return Err("--preserve-partition works with a single --device".to_string());
+    }
+    if resume {
+        if devices.len() > 1 {
+            return Err("--resume works with a single --device".to_string());
+        }
+        options.resume_from = RustBurn::find_checkpoint(&options.iso_path, &options.device_path);
+        match options.resume_from {
+            Some(offset) => println!("Resuming previous burn at {:.1} GB", offset as f64 / 1e9),
+            None => println!("No checkpoint found; starting from the beginning"),
+        }
+    }
+    Ok((options, devices))
+}
+
+/// Runs a burn and renders its `UiProgress` stream as text.
+/// Debug log lines are only shown when `verbose` is set.
+fn burn(options: BurnOptions, verbose: bool) -> ExitCode {
+    let min_level = if verbose {
+        LogLevel::Debug
+    } else {
+        LogLevel::Info
+    };
+    let dry_run = options.dry_run;
+    let sidecar = options
+        .write_sidecar
+        .then(|| RustBurn::sidecar_path(&options.iso_path));
+    let (tx, rx) = mpsc::channel();
+    let cancel = Arc::new(AtomicBool::new(false));
+    // The CLI has no pause control; the flag just stays clear.
+    let pause = Arc::new(AtomicBool::new(false));
+    let worker = thread::spawn(move || RustBurn::burn_iso(options, tx, cancel, pause));
+
+    let started = Instant::now();
+    let mut failed = false;
+    let mut done = false;
+    // Set while the device is being written, so a failure can flag it as incomplete.
+    let mut writing = false;
+    let mut checked = false;
+    for update in rx {
+        match update {
+            UiProgress::Log(level, msg) if level >= min_level => {
+                println!("[{}] {}", format_elapsed(started), msg)
+            }
+            UiProgress::Log(..) => {}
+            UiProgress::SourceHash(hash) => println!("\nSource SHA-256: {}", hash),
+            UiProgress::StartingHashCheck => {
+                checked = true;
+                println!("Checking ISO integrity...");
+            }
+            UiProgress::Hashing(p) => print_bar("Checking", p, None),
+            UiProgress::StartingBurn => {
+                // A dry run never touches the device, so it can't be left half-written.
+                writing = !dry_run;
+                // End the checksum bar's line first.
+                if checked {
+                    println!();
+                }
+                println!("Burning...");
+            }
+            UiProgress::Writing(p) => print_bar("Burning", p, None),
+            UiProgress::WritingDetailed {
+                bytes_done,
+                total: 0,
+                bytes_per_sec,
+                ..
+            } => {
+                // Compressed input: the final size is unknown, so no bar.
+                print!(
+                    "\rBurning... {:.1} GB written, {:.0} MB/s   ",
+                    bytes_done as f64 / 1e9,
+                    bytes_per_sec / 1e6
+                );
+                let _ = io::stdout().flush();
+            }
+            UiProgress::WritingDetailed {
+                fraction,
+                bytes_done,
+                bytes_per_sec,
+                ..
+            } => print_bar(
+                "Burning",
+                fraction,
+                Some(format!(
+                    "{:.1} GB, {:.0} MB/s",
+                    bytes_done as f64 / 1e9,
+                    bytes_per_sec / 1e6
+                )),
+            ),
+            UiProgress::StartingVerification => {
+                writing = false;
+                println!("\nVerifying...");
+            }
+            UiProgress::Verifying(p) => print_bar("Verifying", p, None),
+            UiProgress::StartingBootableSetup => println!("\nMaking device bootable..."),
+            UiProgress::Summary {
+                bytes,
+                elapsed,
+                avg_mbps,
+                verified,
+            } => {
+                let secs = elapsed.as_secs();
+                println!(
+                    "\nWrote {:.1} GB in {:02}:{:02} ({:.0} MB/s){}.",
+                    bytes as f64 / 1e9,
+                    secs / 60,
+                    secs % 60,
+                    avg_mbps,
+                    if verified { ", verified OK" } else { "" }
+                );
+            }
+            UiProgress::Done => {
+                done = true;
+                println!("\nOperation completed successfully.");
+                if let Some(path) = &sidecar {
+                    println!("Wrote {}", path);
+                }
+            }
+            UiProgress::Error(e) => {
+                eprintln!("\nerror: {}", e);
+                if matches!(e, BurnError::PermissionDenied(_)) {
+                    eprintln!("hint: run as root (sudo), or from an elevated prompt on Windows");
+                }
+                if writing {
+                    eprintln!("warning: the device was only partially written and is NOT bootable");
+                }
+                failed = true;
+            }
+            _ => {}
+        }
+    }
+    // The returned Result is the final word: a worker can fail, or stop,
+    // without ever sending Error or Done.
+    match worker.join() {
+        Ok(Ok(_)) => {}
+        Ok(Err(e)) => {
+            if !failed {
+                eprintln!("\nerror: {}", e);
+            }
+            failed = true;
+        }
+        Err(_) => {
+            eprintln!("error: burn thread terminated unexpectedly");
+            failed = true;
+        }
+    }
+    if !done && !failed {
+        eprintln!("error: the burn ended without reporting completion");
+        failed = true;
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Burns one image to several devices through `burn_iso_multi`, printing one
+/// aggregate bar that follows the slowest device.
+fn burn_multi(options: BurnOptions, devices: Vec<String>, verbose: bool) -> ExitCode {
+    let min_level = if verbose {
+        LogLevel::Debug
+    } else {
+        LogLevel::Info
+    };
+    let (tx, rx) = mpsc::channel();
+    let count = devices.len();
+    let mut progress: BTreeMap<String, f32> = devices.iter().map(|d| (d.clone(), 0.0)).collect();
+    let names = devices.clone();
+    let worker = thread::spawn(move || RustBurn::burn_iso_multi(options, devices, tx));
+
+    let started = Instant::now();
+    let mut failed = 0;
+    let mut finished = 0;
+    // Devices that sent Done or Error, checked against the joined results below.
+    let mut done: Vec<String> = Vec::new();
+    let mut errored: Vec<String> = Vec::new();
+    for (device, update) in rx {
+        match update {
+            UiProgress::Log(level, msg) if level >= min_level => {
+                println!("\n[{}] [{}] {}", format_elapsed(started), device, msg)
+            }
+            UiProgress::Writing(p) | UiProgress::WritingDetailed { fraction: p, .. } => {
+                progress.insert(device, p);
+            }
+            UiProgress::Done => {
+                finished += 1;
+                progress.insert(device.clone(), 1.0);
+                println!("\n[{}] done", device);
+                done.push(device);
+            }
+            UiProgress::Error(e) => {
+                finished += 1;
+                failed += 1;
+                progress.insert(device.clone(), 1.0);
+                eprintln!("\n[{}] error: {}", device, e);
+                errored.push(device);
+            }
+            _ => {}
+        }
+        let slowest = progress.values().copied().fold(1.0, f32::min);
+        print_bar(
+            "Burning",
+            slowest,
+            Some(format!("{} of {} finished", finished, count)),
+        );
+    }
+    println!();
+    // burn_iso_multi returns one result per device, in the order given.
+    let results = match worker.join() {
+        Ok(results) => results,
+        Err(_) => {
+            eprintln!("error: burn thread terminated unexpectedly");
+            failed += 1;
+            Vec::new()
+        }
+    };
+    let mut results = results.into_iter();
+    for device in &names {
+        let result = results.next();
+        if errored.contains(device) {
+            // Already counted when its Error arrived.
+            continue;
+        }
+        match result {
+            Some(Err(e)) => eprintln!("[{}] error: {}", device, e),
+            _ if !done.contains(device) => {
+                eprintln!(
+                    "[{}] error: the burn ended without reporting completion",
+                    device
+                )
+            }
+            _ => continue,
+        }
+        failed += 1;
+    }
+
+    if failed > 0 {
+        eprintln!("{} of {} burns failed", failed, count);
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Display name of an `OsFamily`, matching the GUI's device list.
+fn os_family_name(os: &OsFamily) -> &'static str {
+    match os {
+        OsFamily::Windows => "Windows",
+        OsFamily::Linux => "Linux",
+        OsFamily::Unknown => "Unknown OS",
+    }
+}
+
+/// Formats the time since `started` as HH:MM:SS.mmm, matching the GUI log panel.
+fn format_elapsed(started: Instant) -> String {
+    let millis = started.elapsed().as_millis() as u64;
+    let secs = millis / 1000;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        secs / 3600,
+        (secs / 60) % 60,
+        secs % 60,
+        millis % 1000
+    )
+}
+
+/// Redraws a single-line progress bar in place.
+fn print_bar(label: &str, fraction: f32, detail: Option<String>) {
+    let filled = ((fraction.clamp(0.0, 1.0) * BAR_WIDTH as f32) as usize).min(BAR_WIDTH);
+    print!(
+        "\r{:<10} [{}{}] {:>3.0}%",
+        label,
+        "#".repeat(filled),
+        " ".repeat(BAR_WIDTH - filled),
+        fraction * 100.0
+    );
+    if let Some(detail) = detail {
+        print!("  {}", detail);
+    }
+    let _ = io::stdout().flush();
+}
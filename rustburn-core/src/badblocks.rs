@@ -0,0 +1,147 @@
+// rustburn-core/src/badblocks.rs
+
+// A destructive pattern test of the whole device before a burn. Every sector
+// gets data derived from its own offset, so a fake-capacity stick that wraps
+// writes around to its start reads back the wrong sector and is caught.
+use crate::burn::{fraction, read_full};
+use crate::error::BurnError;
+use crate::options::BurnOptions;
+use crate::progress::{log, send, ProgressSink, UiProgress};
+use std::fs::OpenOptions;
+use std::io::{Seek, SeekFrom, Write};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const CHUNK: usize = 1024 * 1024;
+
+/// Fills the device with the test pattern, reads it back and fails naming the
+/// first offset that came back wrong. Everything on the device is lost.
+pub(crate) fn check(
+    options: &BurnOptions,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+) -> Result<(), BurnError> {
+    let path = &options.device_path;
+    let mut device = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| BurnError::io("open", path, e))?;
+    let len = device
+        .seek(SeekFrom::End(0))
+        .map_err(|e| BurnError::io("read the size of", path, e))?;
+    // A new seed per run, so data left by an earlier check can't pass.
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    let mut buf = vec![0u8; CHUNK];
+
+    log(
+        tx,
+        format!(
+            "Checking {} for bad blocks: writing a test pattern over {:.1} GB",
+            path,
+            len as f64 / 1e9
+        ),
+    );
+    device
+        .seek(SeekFrom::Start(0))
+        .map_err(|e| BurnError::io("seek", path, e))?;
+    let mut offset = 0u64;
+    while offset < len {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(BurnError::Cancelled);
+        }
+        let n = (len - offset).min(CHUNK as u64) as usize;
+        fill(&mut buf[..n], offset, seed);
+        device.write_all(&buf[..n]).map_err(|e| {
+            BurnError::Other(format!(
+                "Bad block check: writing {} failed at offset {}: {}",
+                path, offset, e
+            ))
+        })?;
+        offset += n as u64;
+        send(tx, UiProgress::Writing(fraction(offset, len)));
+    }
+    device
+        .sync_all()
+        .map_err(|e| BurnError::io("flush", path, e))?;
+    drop_cache(path);
+
+    send(tx, UiProgress::StartingVerification);
+    log(tx, "Reading the test pattern back");
+    device
+        .seek(SeekFrom::Start(0))
+        .map_err(|e| BurnError::io("seek", path, e))?;
+    let mut expected = vec![0u8; CHUNK];
+    let mut first_bad = None;
+    let mut bad_bytes = 0u64;
+    offset = 0;
+    while offset < len {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(BurnError::Cancelled);
+        }
+        let n = (len - offset).min(CHUNK as u64) as usize;
+        let got = read_full(&mut device, &mut buf[..n]).map_err(|e| {
+            BurnError::Other(format!(
+                "Bad block check: reading {} failed at offset {}: {}",
+                path, offset, e
+            ))
+        })?;
+        fill(&mut expected[..n], offset, seed);
+        for (i, (a, b)) in buf[..n]
+            .chunks(512)
+            .zip(expected[..n].chunks(512))
+            .enumerate()
+        {
+            if i * 512 >= got || a != b {
+                first_bad.get_or_insert(offset + (i * 512) as u64);
+                bad_bytes += a.len() as u64;
+            }
+        }
+        offset += n as u64;
+        send(tx, UiProgress::Verifying(fraction(offset, len)));
+    }
+    match first_bad {
+        Some(first) => Err(BurnError::Other(format!(
+            "Bad block check failed: {:.1} MB of {} did not read back as written, starting \
+             at offset {} ({:.2} GB). The device is failing or smaller than it claims.",
+            bad_bytes as f64 / 1e6,
+            path,
+            first,
+            first as f64 / 1e9
+        ))),
+        None => {
+            log(tx, "Bad block check passed");
+            Ok(())
+        }
+    }
+}
+
+/// The pattern for the bytes at `offset..offset + buf.len()`: each 8-byte
+/// word is a hash of its own offset and the run's seed.
+pub(crate) fn fill(buf: &mut [u8], offset: u64, seed: u64) {
+    for (i, word) in buf.chunks_mut(8).enumerate() {
+        let bytes = splitmix64(seed ^ (offset + i as u64 * 8)).to_le_bytes();
+        word.copy_from_slice(&bytes[..word.len()]);
+    }
+}
+
+pub(crate) fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// Makes the read-back come from the device, not from pages the kernel kept
+/// while writing. Windows reads raw disks uncached already.
+pub(crate) fn drop_cache(path: &str) {
+    if cfg!(target_os = "linux") && path.starts_with("/dev/") {
+        let _ = Command::new("blockdev")
+            .args(["--flushbufs", path])
+            .output();
+    }
+}
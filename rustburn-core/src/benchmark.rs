@@ -0,0 +1,111 @@
+// rustburn-core/src/benchmark.rs
+
+// A quick speed test: write a test region at the start of the device, flush
+// it, then read it back, timing both. The region is big enough to get past
+// the stick's own write cache.
+use crate::badblocks;
+use crate::burn::read_full;
+use crate::error::BurnError;
+use crate::progress::{log, send, ProgressSink, UiProgress};
+use std::fs::OpenOptions;
+use std::io::{Seek, SeekFrom, Write};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// How much is written and read back.
+const TEST_LEN: u64 = 256 * 1024 * 1024;
+const BLOCK: usize = 4 * 1024 * 1024;
+
+/// Overwrites the first `TEST_LEN` bytes of `device_path` and reports the
+/// sustained write and read speeds as log lines, ending with `Done` or
+/// `Error`.
+pub(crate) fn benchmark(device_path: String, tx: impl ProgressSink) {
+    match run(&device_path, &tx) {
+        Ok(()) => send(&tx, UiProgress::Done),
+        Err(e) => send(&tx, UiProgress::Error(e)),
+    }
+}
+
+fn run(path: &str, tx: &dyn ProgressSink) -> Result<(), BurnError> {
+    let mut device = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| BurnError::io("open", path, e))?;
+    let size = device
+        .seek(SeekFrom::End(0))
+        .map_err(|e| BurnError::io("read the size of", path, e))?;
+    let len = TEST_LEN.min(size - size % BLOCK as u64);
+    if len == 0 {
+        return Err(BurnError::Other(format!("{} is too small to test", path)));
+    }
+    log(
+        tx,
+        format!(
+            "Testing {}: writing and reading {} MB",
+            path,
+            len / 1_000_000
+        ),
+    );
+
+    // Incompressible data, so a controller that compresses can't look faster.
+    let mut buf = vec![0u8; BLOCK];
+    let seed = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    badblocks::fill(&mut buf, 0, seed);
+    device
+        .seek(SeekFrom::Start(0))
+        .map_err(|e| BurnError::io("seek", path, e))?;
+    let started = Instant::now();
+    let mut offset = 0;
+    while offset < len {
+        device.write_all(&buf).map_err(|e| {
+            BurnError::Other(format!(
+                "Write to {} failed at offset {}: {}",
+                path, offset, e
+            ))
+        })?;
+        offset += BLOCK as u64;
+    }
+    // The data only counts as written once it has left the OS cache.
+    device
+        .sync_all()
+        .map_err(|e| BurnError::io("flush", path, e))?;
+    let write_time = started.elapsed();
+    badblocks::drop_cache(path);
+
+    device
+        .seek(SeekFrom::Start(0))
+        .map_err(|e| BurnError::io("seek", path, e))?;
+    let started = Instant::now();
+    offset = 0;
+    while offset < len {
+        let n = read_full(&mut device, &mut buf).map_err(|e| {
+            BurnError::Other(format!(
+                "Read from {} failed at offset {}: {}",
+                path, offset, e
+            ))
+        })?;
+        if n == 0 {
+            break;
+        }
+        offset += n as u64;
+    }
+    let read_time = started.elapsed();
+
+    log(
+        tx,
+        format!(
+            "{}: write {:.1} MB/s, read {:.1} MB/s",
+            path,
+            mb_per_sec(len, write_time),
+            mb_per_sec(offset, read_time)
+        ),
+    );
+    Ok(())
+}
+
+fn mb_per_sec(bytes: u64, time: Duration) -> f64 {
+    bytes as f64 / 1e6 / time.as_secs_f64().max(1e-6)
+}
@@ -0,0 +1,150 @@
+// rustburn-core/src/bootable.rs
+
+// Post-write boot setup: hybrid images already carry their partition table, so
+// this only makes sure BIOS firmware will pick the right partition and warns
+// when the image can't start the way the user asked.
+use crate::error::BurnError;
+use crate::fat;
+use crate::options::{BurnOptions, PartitionScheme};
+use crate::partition::Table;
+use crate::progress::{log, warn, ProgressSink};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// MBR partition type of a GPT protective entry.
+const MBR_TYPE_GPT_PROTECTIVE: u8 = 0xEE;
+/// MBR partition type of an EFI System Partition.
+const MBR_TYPE_EFI: u8 = 0xEF;
+/// Offset of the first of the four MBR partition entries.
+const MBR_TABLE_OFFSET: usize = 446;
+
+/// Converts the freshly written partition table to `options.partition_scheme`,
+/// checks the boot sector against `options.boot_type`, marks a partition
+/// active for BIOS boot when none is, and applies `options.volume_label`.
+pub(crate) fn prepare(options: &BurnOptions, tx: &dyn ProgressSink) -> Result<(), BurnError> {
+    let device_path = options.device_path.as_str();
+    let boot_type = options.boot_type;
+    let mut device = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(device_path)
+        .map_err(|e| BurnError::io("open", device_path, e))?;
+    let table = Table::read(&mut device)
+        .map_err(|e| BurnError::io("read the boot sector of", device_path, e))?;
+    match table {
+        Some(table) => convert(&mut device, table, options, tx)?,
+        None => {
+            warn(
+                tx,
+                "WARNING: The image has no partition table; it is not a hybrid ISO and may \
+                 only boot from optical media.",
+            );
+            return Ok(());
+        }
+    }
+    let mut mbr = [0u8; 512];
+    device
+        .seek(SeekFrom::Start(0))
+        .and_then(|_| device.read_exact(&mut mbr))
+        .map_err(|e| BurnError::io("read the boot sector of", device_path, e))?;
+
+    let types: Vec<u8> = (0..4).map(|i| mbr[MBR_TABLE_OFFSET + i * 16 + 4]).collect();
+    let gpt = types.contains(&MBR_TYPE_GPT_PROTECTIVE);
+    if boot_type.wants_uefi() && !gpt && !types.contains(&MBR_TYPE_EFI) {
+        warn(
+            tx,
+            "WARNING: The image has no EFI System Partition; UEFI firmware may not boot it.",
+        );
+    }
+    if boot_type.wants_legacy() && !gpt {
+        let active = (0..4).any(|i| mbr[MBR_TABLE_OFFSET + i * 16] == 0x80);
+        let first_used = types.iter().position(|&t| t != 0);
+        if let Some(index) = first_used.filter(|_| !active) {
+            mbr[MBR_TABLE_OFFSET + index * 16] = 0x80;
+            device
+                .seek(SeekFrom::Start(0))
+                .and_then(|_| device.write_all(&mbr))
+                .and_then(|_| device.sync_all())
+                .map_err(|e| BurnError::io("update the boot sector of", device_path, e))?;
+            log(
+                tx,
+                format!("Marked partition {} active for BIOS boot", index + 1),
+            );
+        }
+    }
+    if let Some(label) = &options.volume_label {
+        apply_label(&mut device, label, options, tx)?;
+    }
+    Ok(())
+}
+
+/// Labels the first FAT filesystem on the device: the whole device for a
+/// superfloppy image, else the EFI or data partition the image brought.
+fn apply_label(
+    device: &mut File,
+    label: &str,
+    options: &BurnOptions,
+    tx: &dyn ProgressSink,
+) -> Result<(), BurnError> {
+    let path = &options.device_path;
+    let padded = fat::check_label(label)?;
+    let table =
+        Table::read(device).map_err(|e| BurnError::io("read the boot sector of", path, e))?;
+    let starts = table
+        .map(|table| table.partition_starts())
+        .unwrap_or_default();
+    for start in std::iter::once(0).chain(starts) {
+        let labelled = fat::relabel(device, start, &padded)
+            .map_err(|e| BurnError::io("write the volume label on", path, e))?;
+        if labelled {
+            let label = String::from_utf8_lossy(&padded).trim_end().to_string();
+            log(tx, format!("Set the volume label to {}", label));
+            return Ok(());
+        }
+    }
+    warn(
+        tx,
+        "WARNING: The image has no FAT filesystem, so the volume label was not applied.",
+    );
+    Ok(())
+}
+
+/// Rewrites `table` as the scheme the user picked, when the image brought the
+/// other one.
+fn convert(
+    device: &mut File,
+    table: Table,
+    options: &BurnOptions,
+    tx: &dyn ProgressSink,
+) -> Result<(), BurnError> {
+    let path = &options.device_path;
+    match (options.partition_scheme, table) {
+        (PartitionScheme::Mbr, table @ Table::Gpt { .. }) => {
+            table
+                .into_mbr(device)
+                .map_err(|e| BurnError::io("convert the GPT to MBR on", path, e))?;
+            log(tx, "Converted the image's GPT to an MBR partition table");
+        }
+        (PartitionScheme::Gpt, table @ Table::Mbr(_)) => {
+            let disk_len = device
+                .seek(SeekFrom::End(0))
+                .map_err(|e| BurnError::io("read the size of", path, e))?;
+            let converted = table
+                .into_gpt(device, disk_len, options.boot_type.wants_legacy())
+                .map_err(|e| BurnError::io("convert the MBR to GPT on", path, e))?;
+            if converted {
+                log(tx, "Converted the image's MBR to a GPT partition table");
+            } else {
+                // Isohybrid images keep boot code and the filesystem where the
+                // GPT would go; rewriting them would break the image.
+                warn(
+                    tx,
+                    "WARNING: The image's layout leaves no room for a GPT; keeping its MBR \
+                     partition table.",
+                );
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
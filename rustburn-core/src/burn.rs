@@ -0,0 +1,1123 @@
+// rustburn-core/src/burn.rs
+
+// The raw image write: a reader thread feeds blocks to the writer, then the
+// device is optionally read back and prepared for booting.
+use crate::badblocks;
+use crate::bootable;
+use crate::checkpoint;
+use crate::device;
+use crate::direct::DeviceReader;
+use crate::eject;
+use crate::erase;
+use crate::error::{is_disconnect, BurnError};
+use crate::extras;
+use crate::fat;
+use crate::filecopy;
+use crate::hash::{self, Hasher};
+use crate::hook;
+use crate::inspect;
+use crate::options::{
+    BurnOptions, ChecksumAlgo, VerifyMode, WriteMode, AUTO_BLOCK_SIZE, DEFAULT_BLOCK_SIZE,
+};
+use crate::persistence;
+use crate::preserve;
+use crate::progress::{debug, log, send, warn, ProgressSink, SpeedMeter, UiProgress};
+use crate::recheck;
+use crate::sidecar;
+use crate::source::{self, Compression};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a paused writer looks at its flags again.
+const PAUSE_POLL: Duration = Duration::from_millis(100);
+/// Pause before the first retry of a failed write; each later retry waits longer.
+const RETRY_BACKOFF: Duration = Duration::from_millis(250);
+/// Block sizes the automatic probe tries.
+const PROBE_SIZES: [usize; 6] = [
+    256 * 1024,
+    512 * 1024,
+    1024 * 1024,
+    2 * 1024 * 1024,
+    4 * 1024 * 1024,
+    8 * 1024 * 1024,
+];
+/// How much the probe writes at each candidate size.
+const PROBE_BYTES: usize = 16 * 1024 * 1024;
+
+/// What a finished burn did.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BurnReport {
+    pub bytes_written: u64,
+    pub duration: Duration,
+    /// Whether the device was read back and matched the image.
+    pub verified: bool,
+}
+
+/// Runs a whole burn, reporting every step on `tx` and the outcome both on
+/// `tx` and as the return value.
+pub(crate) fn burn_iso(
+    options: BurnOptions,
+    tx: impl ProgressSink,
+    cancel: Arc<AtomicBool>,
+    pause: Arc<AtomicBool>,
+) -> Result<BurnReport, BurnError> {
+    let mut result = run(&options, &tx, &cancel, &pause);
+    if options.auto_eject && !options.dry_run {
+        result =
+            result.and_then(|report| recheck::eject_and_recheck(&options, report, &tx, &cancel));
+    }
+    match &result {
+        Ok(report) => {
+            if !options.dry_run {
+                send(
+                    &tx,
+                    UiProgress::Summary {
+                        bytes: report.bytes_written,
+                        elapsed: report.duration,
+                        avg_mbps: report.bytes_written as f64
+                            / 1e6
+                            / report.duration.as_secs_f64().max(1e-3),
+                        verified: report.verified,
+                    },
+                );
+            }
+            let outcome = if options.dry_run {
+                "dry-run"
+            } else if report.verified {
+                "verified"
+            } else {
+                "written"
+            };
+            hook::on_complete(&options, outcome, &tx);
+            send(&tx, UiProgress::Done)
+        }
+        Err(BurnError::Cancelled) => warn(
+            &tx,
+            format!(
+                "Stopped writing {}; the device was flushed.",
+                options.device_path
+            ),
+        ),
+        Err(e) => send(&tx, UiProgress::Error(e.clone())),
+    }
+    result
+}
+
+fn run(
+    options: &BurnOptions,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+    pause: &AtomicBool,
+) -> Result<BurnReport, BurnError> {
+    if options.iso_path.is_empty() {
+        return Err(BurnError::Other("No image selected".to_string()));
+    }
+    if options.device_path.is_empty() {
+        return Err(BurnError::Other("No device selected".to_string()));
+    }
+    // Until the probe has run, everything before the write uses the default.
+    let auto_block_size = options.block_size == AUTO_BLOCK_SIZE;
+    let mut options = options.clone();
+    if auto_block_size {
+        options.block_size = DEFAULT_BLOCK_SIZE;
+    }
+    let options = &mut options;
+    if let Some(id) = &options.device_id {
+        match device::resolve_stable_id(id)? {
+            Some(path) if path == options.device_path => {}
+            Some(path) => {
+                log(
+                    tx,
+                    format!(
+                        "{} is now {}; writing to it there",
+                        options.device_path, path
+                    ),
+                );
+                options.device_path = path;
+            }
+            None => {
+                return Err(BurnError::Other(format!(
+                    "The device selected as {} is no longer plugged in; nothing was written",
+                    options.device_path
+                )))
+            }
+        }
+    }
+    // A partition has no table of its own to convert, mark or extend.
+    let partition_target = device::is_partition(&options.device_path);
+    let make_bootable = options.make_bootable && !partition_target;
+    if make_bootable && !options.partition_scheme.supports(&options.boot_type) {
+        return Err(BurnError::Other(format!(
+            "{:?} boot needs an MBR partition table",
+            options.boot_type
+        )));
+    }
+    // A bad label would otherwise only fail after the whole image is written.
+    if let Some(label) = options.volume_label.as_deref().filter(|_| make_bootable) {
+        fat::check_label(label)?;
+    }
+    if make_bootable {
+        extras::check(options)?;
+    }
+    if device::is_write_protected(&options.device_path) {
+        return Err(BurnError::WriteProtected);
+    }
+    if let Some(mount) = device::mount_holding(&options.device_path, &options.iso_path) {
+        return Err(BurnError::Other(format!(
+            "{} holds the image itself (mounted at {}); writing it would destroy {}",
+            options.device_path, mount, options.iso_path
+        )));
+    }
+    let mounts = device::linux_mounts(&options.device_path);
+    if !mounts.is_empty() && !options.auto_unmount {
+        let targets: Vec<&str> = mounts.iter().map(|(_, target)| target.as_str()).collect();
+        return Err(BurnError::Other(format!(
+            "{} is mounted at {}; unmount it first or turn on automatic unmounting",
+            options.device_path,
+            targets.join(", ")
+        )));
+    }
+    if partition_target && options.preserve_partition.is_some() {
+        return Err(BurnError::Other(
+            "Keeping a partition needs the whole device as the target".to_string(),
+        ));
+    }
+    let write_mode = match options.write_mode {
+        Some(mode) => mode,
+        None => {
+            let mode = inspect::inspect_iso(&options.iso_path).write_mode;
+            if mode == WriteMode::FileCopy {
+                log(
+                    tx,
+                    "The image is a Windows installer; writing it in ISO file-copy mode",
+                );
+            }
+            mode
+        }
+    };
+    if write_mode == WriteMode::FileCopy {
+        filecopy::check(options, partition_target)?;
+    } else if partition_target {
+        warn(
+            tx,
+            format!(
+                "WARNING: {} is a partition. Only the raw image is written to it; the \
+                 partition table and bootable setup are skipped, so the device may not boot \
+                 from it.",
+                options.device_path
+            ),
+        );
+    }
+    let started = Instant::now();
+    let compression = Compression::from_path(&options.iso_path);
+    // A SHA-256 check of a plain image already yields the source hash.
+    let mut known_hash = None;
+    if let Some(expected) = &options.checksum {
+        send(tx, UiProgress::StartingHashCheck);
+        log(
+            tx,
+            format!("Checking the image's {:?} checksum", options.checksum_algo),
+        );
+        let digest = check_checksum(options, expected, tx, cancel)?;
+        log(tx, "Image checksum matches");
+        if options.checksum_algo == ChecksumAlgo::Sha256 && compression.is_none() {
+            known_hash = Some(digest);
+        }
+    }
+    if write_mode == WriteMode::FileCopy {
+        return filecopy::write(options, started, tx, cancel, pause);
+    }
+    let image = source::open_image(&options.iso_path)?;
+    if let Some(compression) = compression {
+        log(
+            tx,
+            format!("Decompressing the {:?} image while writing", compression),
+        );
+    }
+    // macOS refuses to open a disk with mounted volumes for writing at all.
+    if (cfg!(target_os = "macos") || !mounts.is_empty()) && !options.dry_run {
+        eject::unmount(&options.device_path, tx).map_err(BurnError::Other)?;
+    }
+    let mut device = OpenOptions::new()
+        .write(true)
+        .open(&options.device_path)
+        .map_err(|e| BurnError::io("open", &options.device_path, e))?;
+    check_fits(&image, &mut device, options)?;
+    let preserve = match options.preserve_partition.clone() {
+        Some(partition) => {
+            let device_len = device
+                .seek(SeekFrom::End(0))
+                .map_err(|e| BurnError::io("read the size of", &options.device_path, e))?;
+            let len = preserve::check(options, &partition, image.len, device_len)?;
+            Some((partition, len))
+        }
+        None => None,
+    };
+    if options.dry_run {
+        let size = match image.len {
+            Some(len) => format!("{:.1} GB", len as f64 / 1e9),
+            None => "the decompressed image".to_string(),
+        };
+        log(
+            tx,
+            format!(
+                "Dry run: all checks passed; would write {} from {} to {}{}{}{}",
+                size,
+                options.iso_path,
+                options.device_path,
+                if options.verify { ", verify it" } else { "" },
+                if make_bootable {
+                    ", and set it up to boot"
+                } else {
+                    ""
+                },
+                match &preserve {
+                    Some((partition, _)) => format!(", keeping {}", partition),
+                    None => String::new(),
+                },
+            ),
+        );
+        return Ok(BurnReport {
+            bytes_written: 0,
+            duration: started.elapsed(),
+            verified: false,
+        });
+    }
+
+    let mut source_hash = known_hash
+        .is_none()
+        .then(|| Hasher::new(ChecksumAlgo::Sha256));
+    // The partition is copied aside from a full device; a resumed burn may
+    // already have overwritten it.
+    if preserve.is_some() && options.resume_from.is_some_and(|offset| offset > 0) {
+        log(tx, "Starting the burn over to keep the partition");
+        options.resume_from = None;
+    }
+    let backup = match &preserve {
+        Some((partition, len)) => Some(preserve::back_up(partition, *len, tx, cancel)?),
+        None => None,
+    };
+    let start = match options.resume_from.filter(|&offset| offset > 0) {
+        Some(offset) => resume_point(options, offset, image.len, &mut source_hash, tx, cancel)?,
+        None => 0,
+    };
+    // The pattern test would wipe what a resume keeps.
+    if options.bad_block_check && start == 0 {
+        badblocks::check(options, tx, cancel)?;
+    } else if options.bad_block_check {
+        log(tx, "Skipping the bad block check when resuming a burn");
+    }
+    if options.wipe_table_first && start == 0 {
+        log(tx, "Clearing the old partition tables");
+        erase::wipe_tables(&mut device, &options.device_path)?;
+    }
+    // The probe scribbles over the start of the device, which a resume keeps.
+    if auto_block_size && start == 0 {
+        options.block_size = probe_block_size(&mut device, options, tx, cancel)?;
+    }
+
+    send(tx, UiProgress::StartingBurn);
+    if start > 0 {
+        log(
+            tx,
+            format!(
+                "Resuming the write of {} to {} at {:.1} GB",
+                options.iso_path,
+                options.device_path,
+                start as f64 / 1e9
+            ),
+        );
+    } else {
+        log(
+            tx,
+            format!("Writing {} to {}", options.iso_path, options.device_path),
+        );
+    }
+    let image_len = write_image(
+        image,
+        &mut device,
+        start,
+        options,
+        source_hash.as_mut(),
+        tx,
+        cancel,
+        pause,
+    )?;
+    // Everything may be "written" while the OS still caches much of it.
+    log(tx, "Flushing buffers…");
+    device
+        .sync_all()
+        .map_err(|e| BurnError::io("flush", &options.device_path, e))?;
+    drop(device);
+    badblocks::drop_cache(&options.device_path);
+    checkpoint::clear(&options.iso_path, &options.device_path);
+    let source_sha256 = match source_hash {
+        Some(hasher) => hasher.finish_hex(),
+        None => known_hash.unwrap_or_default(),
+    };
+    send(tx, UiProgress::SourceHash(source_sha256.clone()));
+
+    if options.verify {
+        send(tx, UiProgress::StartingVerification);
+        let content_len = options
+            .verify_skip_trailing_zeros
+            .then(|| inspect::content_len(&options.iso_path))
+            .flatten()
+            .filter(|&len| len < image_len);
+        if let Some(len) = content_len {
+            if options.verify_mode == VerifyMode::HashCompare {
+                // The digest covers the padding too, so it can't be reused.
+                log(
+                    tx,
+                    "Comparing bytes, since only part of the image is verified",
+                );
+            }
+            log(
+                tx,
+                format!(
+                    "Verifying the first {:.1} MB; the {:.1} MB after it is padding",
+                    len as f64 / 1e6,
+                    (image_len - len) as f64 / 1e6
+                ),
+            );
+        }
+        match (content_len, options.verify_mode) {
+            (len, VerifyMode::Sample { fraction }) => {
+                verify_sample(options, len.unwrap_or(image_len), fraction, tx)?
+            }
+            (Some(len), _) => verify(options, len, tx)?,
+            (None, VerifyMode::HashCompare) => verify_hash(options, image_len, &source_sha256, tx)?,
+            (None, VerifyMode::ByteCompare) => verify(options, image_len, tx)?,
+        }
+        log(tx, "Verification passed");
+    }
+    if make_bootable && source::is_disk_image(&options.iso_path) {
+        log(
+            tx,
+            "Skipping bootable setup: the disk image carries its own partition layout.",
+        );
+        if !options.extra_files.is_empty() {
+            warn(
+                tx,
+                "WARNING: The extra files were not copied, since bootable setup was skipped.",
+            );
+        }
+    } else if make_bootable {
+        send(tx, UiProgress::StartingBootableSetup);
+        bootable::prepare(options, tx)?;
+        extras::copy_to_volume(options, tx)?;
+        persistence::add_partition(options, image_len, tx)?;
+    }
+    if let Some(backup) = backup {
+        backup.restore(&options.device_path, image_len, tx)?;
+    }
+    if options.write_sidecar {
+        // The burn itself succeeded; a missing record is not worth failing it.
+        match sidecar::write(&options.iso_path, &options.device_path, &source_sha256) {
+            Ok(path) => log(tx, format!("Recorded the burn in {}", path)),
+            Err(e) => warn(
+                tx,
+                format!(
+                    "WARNING: Could not write {}: {}",
+                    sidecar::path(&options.iso_path),
+                    e
+                ),
+            ),
+        }
+    }
+    Ok(BurnReport {
+        bytes_written: image_len,
+        duration: started.elapsed(),
+        verified: options.verify,
+    })
+}
+
+/// Times `PROBE_BYTES` of writes at each of `PROBE_SIZES` over the start of
+/// the device, which the image overwrites next, and returns the fastest size.
+fn probe_block_size(
+    device: &mut File,
+    options: &BurnOptions,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+) -> Result<usize, BurnError> {
+    log(tx, "Measuring the fastest block size for this device");
+    let path = &options.device_path;
+    let buf = vec![0u8; PROBE_BYTES];
+    let mut best = (DEFAULT_BLOCK_SIZE, 0.0);
+    for block_size in PROBE_SIZES {
+        if cancel.load(Ordering::SeqCst) {
+            return Err(BurnError::Cancelled);
+        }
+        let started = Instant::now();
+        device
+            .seek(SeekFrom::Start(0))
+            .map_err(|e| BurnError::io("seek", path, e))?;
+        for block in buf.chunks(block_size) {
+            device
+                .write_all(block)
+                .map_err(|e| BurnError::io("write", path, e))?;
+        }
+        // Without the flush this would time the page cache.
+        device
+            .sync_data()
+            .map_err(|e| BurnError::io("flush", path, e))?;
+        let mbps = PROBE_BYTES as f64 / 1e6 / started.elapsed().as_secs_f64().max(1e-6);
+        debug(
+            tx,
+            format!("{} KB blocks: {:.1} MB/s", block_size / 1024, mbps),
+        );
+        if mbps > best.1 {
+            best = (block_size, mbps);
+        }
+    }
+    log(
+        tx,
+        format!("Using {} KB blocks ({:.1} MB/s)", best.0 / 1024, best.1),
+    );
+    Ok(best.0)
+}
+
+/// Refuses an image that is known to be bigger than the device. A regular file
+/// as the target just grows, and a compressed image's size is only known once
+/// it has been written, so neither is checked.
+fn check_fits(
+    image: &source::Source,
+    device: &mut File,
+    options: &BurnOptions,
+) -> Result<(), BurnError> {
+    let image_len = match image.len {
+        Some(len) => len,
+        None => return Ok(()),
+    };
+    let is_file = device.metadata().map(|m| m.is_file()).unwrap_or(false);
+    if is_file {
+        return Ok(());
+    }
+    let capacity = device
+        .seek(SeekFrom::End(0))
+        .and_then(|end| device.seek(SeekFrom::Start(0)).map(|_| end))
+        .map_err(|e| BurnError::io("read the size of", &options.device_path, e))?;
+    if image_len > capacity {
+        return Err(BurnError::Other(format!(
+            "The image is {:.1} GB but {} is only {:.1} GB",
+            image_len as f64 / 1e9,
+            options.device_path,
+            capacity as f64 / 1e9
+        )));
+    }
+    Ok(())
+}
+
+/// Checks that the device still holds the first `offset` bytes of the image
+/// and returns where to carry on writing: `offset` when it does, else 0, so
+/// the burn starts over. On a match the prefix has also gone into
+/// `source_hash`, so the digest still covers the whole image.
+fn resume_point(
+    options: &BurnOptions,
+    offset: u64,
+    image_len: Option<u64>,
+    source_hash: &mut Option<Hasher>,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+) -> Result<u64, BurnError> {
+    if image_len.is_some_and(|len| offset >= len) {
+        warn(
+            tx,
+            "WARNING: The checkpoint is past the end of the image; starting over.",
+        );
+        return Ok(0);
+    }
+    send(tx, UiProgress::StartingVerification);
+    log(
+        tx,
+        format!(
+            "Re-verifying the {:.1} GB already written before resuming",
+            offset as f64 / 1e9
+        ),
+    );
+    let mut image = source::open_image(&options.iso_path)?.reader;
+    let mut device = File::open(&options.device_path)
+        .map_err(|e| BurnError::io("open", &options.device_path, e))?;
+    let mut prefix_hash = source_hash
+        .as_ref()
+        .map(|_| Hasher::new(ChecksumAlgo::Sha256));
+    let block_size = options.block_size.max(512);
+    let mut expected = vec![0; block_size];
+    let mut actual = vec![0; block_size];
+    let mut checked = 0u64;
+    while checked < offset {
+        if cancel.load(Ordering::SeqCst) {
+            return Err(BurnError::Cancelled);
+        }
+        let want = (offset - checked).min(block_size as u64) as usize;
+        let n = read_full(&mut image, &mut expected[..want])
+            .map_err(|e| BurnError::io("read", &options.iso_path, e))?;
+        let got = read_full(&mut device, &mut actual[..n])
+            .map_err(|e| BurnError::io("read", &options.device_path, e))?;
+        if n < want || got < n || expected[..n] != actual[..n] {
+            warn(
+                tx,
+                format!(
+                    "WARNING: {} no longer matches the image at {:.1} GB; starting over.",
+                    options.device_path,
+                    checked as f64 / 1e9
+                ),
+            );
+            return Ok(0);
+        }
+        if let Some(hasher) = prefix_hash.as_mut() {
+            hasher.update(&expected[..n]);
+        }
+        checked += n as u64;
+        send(tx, UiProgress::Verifying(fraction(checked, offset)));
+    }
+    if prefix_hash.is_some() {
+        *source_hash = prefix_hash;
+    }
+    Ok(offset)
+}
+
+/// Copies the image onto the device block by block. `options.threads` reader
+/// threads each read every n-th block ahead of the single writer, which takes
+/// them in turn, so reads overlap writes and the order is kept. A compressed
+/// image can only be read in order, so it gets one reader with a queue that
+/// deep. Writing starts `start` bytes into both the image and the device.
+/// `source_hash`, when given, is fed every block on the way through. Every
+/// `options.sync_every_mb` the device is flushed and the offset recorded
+/// for a later resume. Returns the image length: how far the device now holds
+/// it.
+#[allow(clippy::too_many_arguments)]
+fn write_image(
+    image: source::Source,
+    device: &mut File,
+    start: u64,
+    options: &BurnOptions,
+    mut source_hash: Option<&mut Hasher>,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+    pause: &AtomicBool,
+) -> Result<u64, BurnError> {
+    let block_size = options.block_size.max(512);
+    // Zero tells the front end the total is unknown.
+    let image_len = image.len.unwrap_or(0);
+    let threads = options.threads.max(1);
+    let mut queues = Vec::new();
+    let mut readers = Vec::new();
+    if image.len.is_some() && threads > 1 {
+        for first in 0..threads {
+            let (block_tx, block_rx) = mpsc::sync_channel(1);
+            let path = options.iso_path.clone();
+            readers.push(thread::spawn(move || {
+                let stride = ((threads - 1) * block_size) as i64;
+                let mut file = match File::open(&path).and_then(|mut file| {
+                    file.seek(SeekFrom::Start(start + (first * block_size) as u64))?;
+                    Ok(file)
+                }) {
+                    Ok(file) => file,
+                    Err(e) => {
+                        let _ = block_tx.send(Err(e));
+                        return;
+                    }
+                };
+                read_blocks(block_size, block_tx, |buf| {
+                    let n = read_full(&mut file, buf)?;
+                    // Skip the blocks the other readers handle.
+                    file.seek(SeekFrom::Current(stride))?;
+                    Ok(n)
+                });
+            }));
+            queues.push(block_rx);
+        }
+    } else {
+        let (block_tx, block_rx) = mpsc::sync_channel(threads);
+        let mut reader = image.reader;
+        readers.push(thread::spawn(move || {
+            // A compressed stream can't seek; read past the part already written.
+            if let Err(e) = io::copy(&mut (&mut reader).take(start), &mut io::sink()) {
+                let _ = block_tx.send(Err(e));
+                return;
+            }
+            read_blocks(block_size, block_tx, |buf| read_full(&mut reader, buf))
+        }));
+        queues.push(block_rx);
+    }
+
+    debug(
+        tx,
+        format!(
+            "{} reader thread(s), {} byte blocks",
+            queues.len(),
+            block_size
+        ),
+    );
+    device
+        .seek(SeekFrom::Start(start))
+        .map_err(|e| BurnError::io("seek", &options.device_path, e))?;
+    let mut written = start;
+    let sync_interval = options.sync_every_mb.max(1) * 1024 * 1024;
+    let mut synced = start;
+    let mut checkpoints = true;
+    let mut meter = SpeedMeter::starting_at(start);
+    let mut throttle = options.max_write_bytes_per_sec.map(Throttle::new);
+    let mut result = Ok(());
+    // A reader that hangs up has reached the end of the image.
+    for block in (0..).map_while(|index| queues[index % queues.len()].recv().ok()) {
+        if pause.load(Ordering::SeqCst) {
+            let paused = wait_while_paused(pause, cancel, tx);
+            meter.skip(paused);
+        }
+        if cancel.load(Ordering::SeqCst) {
+            result = Err(BurnError::Cancelled);
+            break;
+        }
+        let block = match block {
+            Ok(block) => block,
+            Err(e) => {
+                // Not `BurnError::io`: a vanished share or card holding the
+                // image must not read as the target being unplugged.
+                result = Err(BurnError::Other(format!(
+                    "Failed reading source ISO at offset {}: {}",
+                    written, e
+                )));
+                break;
+            }
+        };
+        if let Some(throttle) = throttle.as_mut() {
+            throttle.wait_for(block.len() as u64);
+        }
+        if let Err(e) = write_block(device, &block, written, options, tx, cancel) {
+            result = Err(e);
+            break;
+        }
+        if let Some(hasher) = source_hash.as_deref_mut() {
+            hasher.update(&block);
+        }
+        debug(
+            tx,
+            format!("Wrote {} bytes at offset {}", block.len(), written),
+        );
+        written += block.len() as u64;
+        if written - synced >= sync_interval {
+            // Only what has reached the device counts as done.
+            let flushed = device.sync_data();
+            synced = written;
+            if checkpoints {
+                let recorded = flushed.and_then(|()| {
+                    checkpoint::record(&options.iso_path, &options.device_path, written)
+                });
+                if let Err(e) = recorded {
+                    warn(
+                        tx,
+                        format!(
+                            "WARNING: Could not save a resume point ({}); an interrupted burn \
+                             will have to start over.",
+                            e
+                        ),
+                    );
+                    checkpoints = false;
+                }
+            }
+        }
+        send(
+            tx,
+            UiProgress::WritingDetailed {
+                fraction: if image_len == 0 {
+                    0.0
+                } else {
+                    fraction(written, image_len)
+                },
+                bytes_done: written,
+                total: image_len,
+                bytes_per_sec: meter.update(written),
+            },
+        );
+    }
+    drop(queues);
+    for reader in readers {
+        let _ = reader.join();
+    }
+    if result == Err(BurnError::Cancelled) {
+        // Leave what was written in a consistent state before returning.
+        let _ = device.flush();
+        let _ = device.sync_all();
+    }
+    result.map(|()| written)
+}
+
+/// Blocks while `pause` is set and `cancel` is not, and returns how long that
+/// took.
+pub(crate) fn wait_while_paused(
+    pause: &AtomicBool,
+    cancel: &AtomicBool,
+    tx: &dyn ProgressSink,
+) -> Duration {
+    let started = Instant::now();
+    debug(tx, "Writer paused");
+    while pause.load(Ordering::SeqCst) && !cancel.load(Ordering::SeqCst) {
+        thread::sleep(PAUSE_POLL);
+    }
+    if !cancel.load(Ordering::SeqCst) {
+        debug(tx, "Writer resumed");
+    }
+    started.elapsed()
+}
+
+/// Writes `block` at `offset`, retrying up to `options.max_retries` times
+/// with a growing pause, since a flaky hub can fail a single write.
+fn write_block(
+    device: &mut File,
+    block: &[u8],
+    offset: u64,
+    options: &BurnOptions,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+) -> Result<(), BurnError> {
+    let mut attempt = 0;
+    loop {
+        // A failed write_all may have written part of the block; start it over.
+        let result = if attempt == 0 {
+            device.write_all(block)
+        } else {
+            device
+                .seek(SeekFrom::Start(offset))
+                .and_then(|_| device.write_all(block))
+        };
+        let e = match result {
+            Ok(()) => return Ok(()),
+            Err(e) => e,
+        };
+        // A pulled stick mostly fails writes with a plain EIO, but its node
+        // is gone by then; retrying would only wait for the timeouts.
+        let unplugged =
+            options.device_path.starts_with("/dev/") && !Path::new(&options.device_path).exists();
+        if is_disconnect(&e) || unplugged {
+            return Err(BurnError::Disconnected);
+        }
+        let permanent = matches!(
+            e.kind(),
+            io::ErrorKind::PermissionDenied | io::ErrorKind::ReadOnlyFilesystem
+        );
+        if permanent || attempt >= options.max_retries {
+            let retried = if attempt > 0 {
+                format!(" after {} retries", attempt)
+            } else {
+                String::new()
+            };
+            return Err(BurnError::Other(format!(
+                "Write to {} failed at offset {}{}: {}",
+                options.device_path, offset, retried, e
+            )));
+        }
+        attempt += 1;
+        warn(
+            tx,
+            format!(
+                "WARNING: Write failed at offset {} ({}); retry {} of {}",
+                offset, e, attempt, options.max_retries
+            ),
+        );
+        thread::sleep(RETRY_BACKOFF * attempt);
+        if cancel.load(Ordering::SeqCst) {
+            return Err(BurnError::Cancelled);
+        }
+    }
+}
+
+/// Token bucket for the write rate: tokens refill at `rate` bytes a second up
+/// to one second's worth, and each write waits until it has enough.
+struct Throttle {
+    rate: f64,
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl Throttle {
+    fn new(bytes_per_sec: u64) -> Self {
+        let rate = bytes_per_sec.max(1) as f64;
+        Self {
+            rate,
+            tokens: rate,
+            refilled: Instant::now(),
+        }
+    }
+
+    /// Sleeps until `bytes` may be written, then spends them.
+    fn wait_for(&mut self, bytes: u64) {
+        let now = Instant::now();
+        let elapsed = (now - self.refilled).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.rate);
+        self.refilled = now;
+        self.tokens -= bytes as f64;
+        if self.tokens < 0.0 {
+            thread::sleep(Duration::from_secs_f64(-self.tokens / self.rate));
+        }
+    }
+}
+
+/// Reads blocks with `read` and queues them on `block_tx` until the image ends,
+/// a read fails or the writer hangs up.
+fn read_blocks(
+    block_size: usize,
+    block_tx: mpsc::SyncSender<io::Result<Vec<u8>>>,
+    mut read: impl FnMut(&mut [u8]) -> io::Result<usize>,
+) {
+    loop {
+        let mut block = vec![0; block_size];
+        match read(&mut block) {
+            Ok(0) => break,
+            Ok(n) => {
+                block.truncate(n);
+                // The writer hung up: cancelled or failed.
+                if block_tx.send(Ok(block)).is_err() {
+                    break;
+                }
+            }
+            Err(e) => {
+                let _ = block_tx.send(Err(e));
+                break;
+            }
+        }
+    }
+}
+
+/// Hashes the image file as it is on disk, compressed or not, and compares it
+/// with `expected`. Returns the digest.
+pub(crate) fn check_checksum(
+    options: &BurnOptions,
+    expected: &str,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+) -> Result<String, BurnError> {
+    let block_size = match options.block_size {
+        AUTO_BLOCK_SIZE => DEFAULT_BLOCK_SIZE,
+        block_size => block_size,
+    };
+    let actual = hash::digest_file(
+        &options.iso_path,
+        options.checksum_algo,
+        block_size,
+        tx,
+        cancel,
+        UiProgress::Hashing,
+    )?;
+    if actual.eq_ignore_ascii_case(expected.trim()) {
+        Ok(actual)
+    } else {
+        Err(BurnError::ChecksumMismatch {
+            expected: expected.trim().to_string(),
+            actual,
+        })
+    }
+}
+
+/// Reads the first `image_len` bytes of the device back and compares them
+/// with the image, decompressing the image again if needed. The rest of the
+/// device was not written, so it is not read; progress is relative to
+/// `image_len`.
+pub(crate) fn verify(
+    options: &BurnOptions,
+    image_len: u64,
+    tx: &dyn ProgressSink,
+) -> Result<(), BurnError> {
+    let mut image = source::open_image(&options.iso_path)?.reader;
+    let mut device = open_for_verify(options, tx)?;
+    let block_size = options.block_size.max(512);
+    let mut expected = vec![0; block_size];
+    let mut actual = vec![0; block_size];
+    let mut checked = 0u64;
+    while checked < image_len {
+        let want = (image_len - checked).min(block_size as u64) as usize;
+        let n = read_full(&mut image, &mut expected[..want])
+            .map_err(|e| BurnError::io("read", &options.iso_path, e))?;
+        if n == 0 {
+            break;
+        }
+        let got = read_full(&mut device, &mut actual[..n])
+            .map_err(|e| BurnError::io("read", &options.device_path, e))?;
+        if got < n {
+            return Err(BurnError::VerificationFailed(format!(
+                "{} ended at offset {}",
+                options.device_path,
+                checked + got as u64
+            )));
+        }
+        if let Some(pos) = expected[..n]
+            .iter()
+            .zip(&actual[..n])
+            .position(|(a, b)| a != b)
+        {
+            return Err(BurnError::VerificationFailed(format!(
+                "mismatch at offset {}",
+                checked + pos as u64
+            )));
+        }
+        checked += n as u64;
+        send(tx, UiProgress::Verifying(fraction(checked, image_len)));
+    }
+    Ok(())
+}
+
+/// Opens the device for a verify: past the OS cache with `direct_verify`
+/// where that works, else through the cache once it has been dropped.
+fn open_for_verify(
+    options: &BurnOptions,
+    tx: &dyn ProgressSink,
+) -> Result<DeviceReader, BurnError> {
+    let path = &options.device_path;
+    if options.direct_verify {
+        match DeviceReader::open_direct(path, options.block_size.max(512)) {
+            Ok(reader) => return Ok(reader),
+            Err(e) => debug(
+                tx,
+                format!(
+                    "Direct IO is not available for {} ({}); reading through the cache",
+                    path, e
+                ),
+            ),
+        }
+    }
+    badblocks::drop_cache(path);
+    DeviceReader::open_buffered(path).map_err(|e| BurnError::io("open", path, e))
+}
+
+/// Byte-compares `share` of the blocks of the first `image_len` bytes: one
+/// pseudo-random block from each of as many equal stretches, seeded by the
+/// length so a rerun checks the same ones. The first and last blocks, where
+/// partition tables live, are always among them.
+fn verify_sample(
+    options: &BurnOptions,
+    image_len: u64,
+    share: f32,
+    tx: &dyn ProgressSink,
+) -> Result<(), BurnError> {
+    let block_size = options.block_size.max(512) as u64;
+    let count = image_len.div_ceil(block_size);
+    let wanted = (count as f64 * share.clamp(0.0, 1.0) as f64).ceil() as u64;
+    let picks = wanted.clamp(2.min(count), count);
+    if picks == count {
+        return verify(options, image_len, tx);
+    }
+    let mut blocks: Vec<u64> = (0..picks)
+        .map(|i| {
+            let (lo, hi) = (i * count / picks, (i + 1) * count / picks);
+            lo + badblocks::splitmix64(image_len ^ i) % (hi - lo)
+        })
+        .collect();
+    blocks[0] = 0;
+    blocks[picks as usize - 1] = count - 1;
+    log(
+        tx,
+        format!(
+            "Sample verification: checking {} of {} blocks ({:.1}%, {:.1} MB)",
+            picks,
+            count,
+            picks as f64 * 100.0 / count as f64,
+            (picks * block_size).min(image_len) as f64 / 1e6
+        ),
+    );
+
+    // Compressed images can't seek, so the image is read through in order.
+    let mut image = source::open_image(&options.iso_path)?.reader;
+    let mut device = open_for_verify(options, tx)?;
+    let mut expected = vec![0; block_size as usize];
+    let mut actual = vec![0; block_size as usize];
+    let mut image_pos = 0u64;
+    for (done, &block) in blocks.iter().enumerate() {
+        let offset = block * block_size;
+        let want = (image_len - offset).min(block_size) as usize;
+        io::copy(&mut (&mut image).take(offset - image_pos), &mut io::sink())
+            .map_err(|e| BurnError::io("read", &options.iso_path, e))?;
+        let n = read_full(&mut image, &mut expected[..want])
+            .map_err(|e| BurnError::io("read", &options.iso_path, e))?;
+        image_pos = offset + n as u64;
+        let got = device
+            .seek(SeekFrom::Start(offset))
+            .and_then(|_| read_full(&mut device, &mut actual[..n]))
+            .map_err(|e| BurnError::io("read", &options.device_path, e))?;
+        if got < n {
+            return Err(BurnError::VerificationFailed(format!(
+                "{} ended at offset {}",
+                options.device_path,
+                offset + got as u64
+            )));
+        }
+        if let Some(pos) = expected[..n]
+            .iter()
+            .zip(&actual[..n])
+            .position(|(a, b)| a != b)
+        {
+            return Err(BurnError::VerificationFailed(format!(
+                "mismatch at offset {}",
+                offset + pos as u64
+            )));
+        }
+        send(tx, UiProgress::Verifying(fraction(done as u64 + 1, picks)));
+    }
+    Ok(())
+}
+
+/// Hashes exactly `image_len` bytes of the device and compares the digest with
+/// the one taken from the image while it was written.
+fn verify_hash(
+    options: &BurnOptions,
+    image_len: u64,
+    expected: &str,
+    tx: &dyn ProgressSink,
+) -> Result<(), BurnError> {
+    let mut device = open_for_verify(options, tx)?;
+    let mut hasher = Hasher::new(ChecksumAlgo::Sha256);
+    let mut buf = vec![0; options.block_size.max(512)];
+    let mut checked = 0u64;
+    while checked < image_len {
+        let want = (image_len - checked).min(buf.len() as u64) as usize;
+        let got = read_full(&mut device, &mut buf[..want])
+            .map_err(|e| BurnError::io("read", &options.device_path, e))?;
+        if got < want {
+            return Err(BurnError::VerificationFailed(format!(
+                "{} ended at offset {}",
+                options.device_path,
+                checked + got as u64
+            )));
+        }
+        hasher.update(&buf[..got]);
+        checked += got as u64;
+        send(tx, UiProgress::Verifying(fraction(checked, image_len)));
+    }
+    let actual = hasher.finish_hex();
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(BurnError::VerificationFailed(format!(
+            "the device hashes to {}, the image to {}",
+            actual, expected
+        )))
+    }
+}
+
+/// Fills `buf` as far as the source allows; returns how much was read.
+pub(crate) fn read_full(source: &mut impl Read, buf: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match source.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+/// `done / total` as a progress fraction; an empty total counts as finished.
+pub(crate) fn fraction(done: u64, total: u64) -> f32 {
+    if total == 0 {
+        1.0
+    } else {
+        (done as f64 / total as f64).min(1.0) as f32
+    }
+}
@@ -0,0 +1,99 @@
+// rustburn-core/src/checkpoint.rs
+
+// Resume points for interrupted burns. At each sync point a burn records how
+// far the device is known to be flushed in a `.rbresume` file next to the
+// image, one line per device; a later burn of the same image to the same
+// device can pick up from there.
+use std::fs;
+use std::io;
+use std::sync::Mutex;
+use std::time::UNIX_EPOCH;
+
+/// Burns to several devices share the file; updates go one at a time.
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// One device's line: `device \t image size \t image mtime \t offset`.
+struct Entry {
+    device: String,
+    stamp: (u64, u64),
+    offset: u64,
+}
+
+/// The checkpoint file for the image at `iso_path`.
+fn file_path(iso_path: &str) -> String {
+    format!("{}.rbresume", iso_path)
+}
+
+/// The image's size and modification time, so a checkpoint taken of an
+/// older copy under the same name is not used.
+fn stamp(iso_path: &str) -> Option<(u64, u64)> {
+    let meta = fs::metadata(iso_path).ok()?;
+    let mtime = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+    Some((meta.len(), mtime.as_secs()))
+}
+
+fn read_entries(iso_path: &str) -> Vec<Entry> {
+    fs::read_to_string(file_path(iso_path))
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            Some(Entry {
+                device: fields.next()?.to_string(),
+                stamp: (fields.next()?.parse().ok()?, fields.next()?.parse().ok()?),
+                offset: fields.next()?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+fn write_entries(iso_path: &str, entries: &[Entry]) -> io::Result<()> {
+    let path = file_path(iso_path);
+    if entries.is_empty() {
+        return match fs::remove_file(&path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        };
+    }
+    let text: String = entries
+        .iter()
+        .map(|e| format!("{}\t{}\t{}\t{}\n", e.device, e.stamp.0, e.stamp.1, e.offset))
+        .collect();
+    fs::write(path, text)
+}
+
+/// Where an earlier burn of this image to `device` got to, if it left a
+/// checkpoint and the image has not changed since.
+pub(crate) fn find(iso_path: &str, device: &str) -> Option<u64> {
+    let stamp = stamp(iso_path)?;
+    read_entries(iso_path)
+        .into_iter()
+        .find(|e| e.device == device && e.stamp == stamp)
+        .map(|e| e.offset)
+        .filter(|&offset| offset > 0)
+}
+
+/// Records that the first `offset` bytes of the image are flushed to `device`.
+pub(crate) fn record(iso_path: &str, device: &str, offset: u64) -> io::Result<()> {
+    let stamp = stamp(iso_path).ok_or_else(|| io::Error::other("the image is gone"))?;
+    let _lock = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = read_entries(iso_path);
+    entries.retain(|e| e.device != device);
+    entries.push(Entry {
+        device: device.to_string(),
+        stamp,
+        offset,
+    });
+    write_entries(iso_path, &entries)
+}
+
+/// Drops `device`'s checkpoint once its write has finished.
+pub(crate) fn clear(iso_path: &str, device: &str) {
+    let _lock = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut entries = read_entries(iso_path);
+    let before = entries.len();
+    entries.retain(|e| e.device != device);
+    if entries.len() != before {
+        let _ = write_entries(iso_path, &entries);
+    }
+}
@@ -0,0 +1,140 @@
+// rustburn-core/src/clone.rs
+
+// Reading a device back into an image file, e.g. to keep a configured stick.
+use crate::burn::{fraction, read_full};
+use crate::error::BurnError;
+use crate::partition::{Table, GPT_BACKUP_LEN};
+use crate::progress::{log, send, ProgressSink, SpeedMeter, UiProgress};
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::process::{Child, Command, Stdio};
+
+const BLOCK: usize = 1024 * 1024;
+
+/// Copies `device_path` into `output`, gzipped when `output` ends in `.gz`.
+/// Only the part up to the end of the last partition is read when the device
+/// has a partition table. Ends with `Done` or `Error`.
+pub(crate) fn clone_to_image(device_path: String, output: String, tx: impl ProgressSink) {
+    match run(&device_path, &output, &tx) {
+        Ok(()) => {
+            log(&tx, format!("Saved {} to {}", device_path, output));
+            send(&tx, UiProgress::Done);
+        }
+        Err(e) => {
+            let _ = std::fs::remove_file(&output);
+            send(&tx, UiProgress::Error(e));
+        }
+    }
+}
+
+fn run(device_path: &str, output: &str, tx: &dyn ProgressSink) -> Result<(), BurnError> {
+    let mut device = File::open(device_path).map_err(|e| BurnError::io("open", device_path, e))?;
+    let capacity = device
+        .seek(SeekFrom::End(0))
+        .map_err(|e| BurnError::io("read the size of", device_path, e))?;
+    let table = Table::read(&mut device)
+        .map_err(|e| BurnError::io("read the partition table of", device_path, e))?;
+    // Keep the room after a GPT's last partition where its backup would go on
+    // a device of exactly that size.
+    let used = table.and_then(|table| match table {
+        Table::Gpt { .. } => table.used_length().map(|end| end + GPT_BACKUP_LEN),
+        Table::Mbr(_) => table.used_length(),
+    });
+    let total = match used {
+        Some(end) if end < capacity => {
+            log(
+                tx,
+                format!(
+                    "Reading up to the end of the last partition ({:.1} of {:.1} GB)",
+                    end as f64 / 1e9,
+                    capacity as f64 / 1e9
+                ),
+            );
+            end
+        }
+        _ => capacity,
+    };
+    device
+        .seek(SeekFrom::Start(0))
+        .map_err(|e| BurnError::io("seek", device_path, e))?;
+
+    let mut sink = Sink::create(output)?;
+    let mut input = device.take(total);
+    let mut buf = vec![0u8; BLOCK];
+    let mut done = 0u64;
+    let mut meter = SpeedMeter::new();
+    loop {
+        let n =
+            read_full(&mut input, &mut buf).map_err(|e| BurnError::io("read", device_path, e))?;
+        if n == 0 {
+            break;
+        }
+        sink.write_all(&buf[..n])
+            .map_err(|e| BurnError::io("write", output, e))?;
+        done += n as u64;
+        send(
+            tx,
+            UiProgress::WritingDetailed {
+                fraction: fraction(done, total),
+                bytes_done: done,
+                total,
+                bytes_per_sec: meter.update(done),
+            },
+        );
+    }
+    sink.finish().map_err(|e| BurnError::io("write", output, e))
+}
+
+/// The output file, written directly or through `gzip`.
+enum Sink {
+    Plain(File),
+    Gzip(Child),
+}
+
+impl Sink {
+    fn create(output: &str) -> Result<Self, BurnError> {
+        let file = File::create(output).map_err(|e| BurnError::io("create", output, e))?;
+        if !output.to_ascii_lowercase().ends_with(".gz") {
+            return Ok(Sink::Plain(file));
+        }
+        Command::new("gzip")
+            .arg("-c")
+            .stdin(Stdio::piped())
+            .stdout(file)
+            .stderr(Stdio::null())
+            .spawn()
+            .map(Sink::Gzip)
+            .map_err(|e| {
+                BurnError::Other(format!(
+                    "Could not start gzip to compress {}: {}",
+                    output, e
+                ))
+            })
+    }
+
+    fn write_all(&mut self, data: &[u8]) -> io::Result<()> {
+        match self {
+            Sink::Plain(file) => file.write_all(data),
+            Sink::Gzip(child) => child
+                .stdin
+                .as_mut()
+                .expect("stdin is piped")
+                .write_all(data),
+        }
+    }
+
+    fn finish(self) -> io::Result<()> {
+        match self {
+            Sink::Plain(file) => file.sync_all(),
+            Sink::Gzip(mut child) => {
+                drop(child.stdin.take());
+                let status = child.wait()?;
+                if status.success() {
+                    Ok(())
+                } else {
+                    Err(io::Error::other(format!("gzip failed ({})", status)))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,94 @@
+// rustburn-core/src/compare.rs
+
+// Checking a device burned earlier against an image without writing it: both
+// are hashed over the image's length, side by side.
+use crate::burn::{fraction, read_full};
+use crate::error::BurnError;
+use crate::hash::Hasher;
+use crate::options::ChecksumAlgo;
+use crate::progress::{log, send, warn, ProgressSink, UiProgress};
+use crate::source;
+use std::fs::File;
+
+const BLOCK: usize = 1024 * 1024;
+
+/// Hashes the image at `iso_path`, decompressed, and as many bytes from the
+/// start of `device_path`, and logs whether they match. Ends with `Done` on
+/// a match and `Error` otherwise.
+pub(crate) fn compare(device_path: String, iso_path: String, tx: impl ProgressSink) {
+    send(&tx, UiProgress::StartingVerification);
+    log(&tx, format!("Comparing {} with {}", device_path, iso_path));
+    match run(&device_path, &iso_path, &tx) {
+        Ok(()) => send(&tx, UiProgress::Done),
+        Err(e) => send(&tx, UiProgress::Error(e)),
+    }
+}
+
+fn run(device_path: &str, iso_path: &str, tx: &dyn ProgressSink) -> Result<(), BurnError> {
+    let image = source::open_image(iso_path)?;
+    let mut reader = image.reader;
+    let mut device = File::open(device_path).map_err(|e| BurnError::io("open", device_path, e))?;
+    let mut image_hash = Hasher::new(ChecksumAlgo::Sha256);
+    let mut device_hash = Hasher::new(ChecksumAlgo::Sha256);
+    let mut expected = vec![0; BLOCK];
+    let mut actual = vec![0; BLOCK];
+    let mut checked = 0u64;
+    loop {
+        let n = read_full(&mut reader, &mut expected)
+            .map_err(|e| BurnError::io("read", iso_path, e))?;
+        if n == 0 {
+            break;
+        }
+        let got = read_full(&mut device, &mut actual[..n])
+            .map_err(|e| BurnError::io("read", device_path, e))?;
+        if got < n {
+            warn(
+                tx,
+                format!(
+                    "{} does not match: it is smaller than the image",
+                    device_path
+                ),
+            );
+            return Err(BurnError::VerificationFailed(format!(
+                "{} ended at offset {}",
+                device_path,
+                checked + got as u64
+            )));
+        }
+        image_hash.update(&expected[..n]);
+        device_hash.update(&actual[..n]);
+        checked += n as u64;
+        // A compressed image's length is only known at its end.
+        if let Some(len) = image.len {
+            send(tx, UiProgress::Verifying(fraction(checked, len)));
+        }
+    }
+    let (image_sha256, device_sha256) = (image_hash.finish_hex(), device_hash.finish_hex());
+    if image_sha256 == device_sha256 {
+        log(
+            tx,
+            format!(
+                "{} matches the image over its {:.1} MB (SHA-256 {})",
+                device_path,
+                checked as f64 / 1e6,
+                image_sha256
+            ),
+        );
+        Ok(())
+    } else {
+        warn(
+            tx,
+            format!(
+                "{} does not match: its first {:.1} MB hash to {}, the image to {}",
+                device_path,
+                checked as f64 / 1e6,
+                device_sha256,
+                image_sha256
+            ),
+        );
+        Err(BurnError::VerificationFailed(format!(
+            "{} differs from {}",
+            device_path, iso_path
+        )))
+    }
+}
@@ -0,0 +1,835 @@
+// rustburn-core/src/device.rs
+
+// Finding the disks a burn may target, per platform. Internal disks are listed
+// too, flagged, so front ends decide what to hide.
+use crate::error::BurnError;
+use crate::inspect::{self, OsFamily};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::{mpsc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+/// A whole disk as the OS exposes it, or one of its partitions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UsbDevice {
+    /// Path to open for raw access, e.g. `/dev/sdb` or `\\.\PhysicalDrive2`.
+    pub device: String,
+    pub vendor: String,
+    pub model: String,
+    /// Capacity in bytes.
+    pub size: u64,
+    /// Logical sector size in bytes; writes must be a whole number of these.
+    pub sector_size: u64,
+    /// The drive's serial number; empty when the OS doesn't report one.
+    pub serial: String,
+    /// Where the drive is plugged in, e.g. USB port `1-2.3`; may be empty.
+    pub bus_path: String,
+    /// On a USB bus or flagged removable by the OS.
+    pub is_removable: bool,
+    /// Holds the running OS (root, boot, EFI or swap); writing it would
+    /// take the machine down.
+    pub is_system: bool,
+    /// The lock switch is on, or the OS otherwise refuses writes.
+    pub write_protected: bool,
+    /// The reported capacity looks like a fake-capacity stick's: suspiciously
+    /// round on a no-name brand, or not what the USB descriptors advertise.
+    /// Only a full bad block check tells for sure.
+    pub suspected_fake: bool,
+    /// The OS of the bootable media already on the disk, such as a Windows
+    /// installer or a live Linux stick; None for a plain data disk or one
+    /// that can't be read.
+    pub detected_os: Option<OsFamily>,
+    /// The volume label of that media, when it has one.
+    pub detected_label: Option<String>,
+    /// Reading the disk timed out during a scan with a limit, so
+    /// `detected_os` is unknown; the disk is listed but should not be used.
+    pub unresponsive: bool,
+    /// Where the OS has this node's filesystem mounted (mount points, or
+    /// drive letters on Windows). A disk's list leaves out its partitions'.
+    pub mount_points: Vec<String>,
+    /// The disk's partitions, in order, each described like a disk; always
+    /// empty for a partition.
+    pub partitions: Vec<UsbDevice>,
+}
+
+impl UsbDevice {
+    /// An ID that stays with the drive when the OS renumbers its node: its
+    /// serial number and the port it is plugged into. None when the OS
+    /// reports neither.
+    pub fn stable_id(&self) -> Option<String> {
+        if self.serial.is_empty() && self.bus_path.is_empty() {
+            None
+        } else {
+            Some(format!("{}@{}", self.serial, self.bus_path))
+        }
+    }
+}
+
+/// The node the drive with `stable_id` has now, if it is plugged in.
+pub(crate) fn resolve_stable_id(stable_id: &str) -> Result<Option<String>, BurnError> {
+    Ok(scan_devices()?
+        .into_iter()
+        .find(|d| d.stable_id().as_deref() == Some(stable_id))
+        .map(|d| d.device))
+}
+
+/// Whether the kernel marks the disk or partition behind `device_path`
+/// read-only. Always false where that can't be asked up front; opening it
+/// will tell then.
+pub(crate) fn is_write_protected(device_path: &str) -> bool {
+    linux_class_dir(device_path).is_some_and(|dir| read_sysfs(&dir.join("ro")) == "1")
+}
+
+/// Whether `device_path` is a partition rather than a whole disk.
+pub(crate) fn is_partition(device_path: &str) -> bool {
+    if cfg!(windows) {
+        let lower = device_path.to_ascii_lowercase();
+        // Partition0 is Windows' name for the whole disk.
+        lower.contains(r"\partition") && !lower.ends_with(r"\partition0")
+    } else {
+        linux_class_dir(device_path).is_some_and(|dir| dir.join("partition").is_file())
+    }
+}
+
+/// Where the partition at `device_path` starts on its disk, in bytes, where
+/// the OS says so cheaply.
+pub(crate) fn partition_offset(device_path: &str) -> Option<u64> {
+    let dir = linux_class_dir(device_path)?;
+    let sectors: u64 = read_sysfs(&dir.join("start")).parse().ok()?;
+    Some(sectors * 512)
+}
+
+/// Whether `partition` is one of the partitions sysfs lists under the disk at
+/// `disk`.
+pub(crate) fn is_partition_on(partition: &str, disk: &str) -> bool {
+    match (linux_class_dir(disk), Path::new(partition).file_name()) {
+        (Some(dir), Some(name)) => dir.join(name).join("partition").is_file(),
+        _ => false,
+    }
+}
+
+/// The sysfs directory of a `/dev` block node; disks and partitions alike
+/// are in /sys/class/block.
+fn linux_class_dir(device_path: &str) -> Option<PathBuf> {
+    if !cfg!(target_os = "linux") || !device_path.starts_with("/dev/") {
+        return None;
+    }
+    let name = Path::new(device_path).file_name()?;
+    Some(Path::new("/sys/class/block").join(name))
+}
+
+/// Disks seen by earlier scans, as (identity, disk without partitions), so a
+/// rescan can skip asking about the details that don't change while a disk
+/// stays plugged in. The identity is whatever is cheap to read and differs
+/// when another disk takes the node: serial, bus path and size on Linux, the
+/// disk's row in `diskutil list` on macOS.
+static KNOWN_DISKS: Mutex<Vec<(String, UsbDevice)>> = Mutex::new(Vec::new());
+
+/// The disk remembered at `device` under `identity`.
+fn known_disk(device: &str, identity: &str) -> Option<UsbDevice> {
+    KNOWN_DISKS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|(id, disk)| disk.device == device && id == identity)
+        .map(|(_, disk)| disk.clone())
+}
+
+fn remember_disk(identity: String, disk: &UsbDevice) {
+    let mut known = KNOWN_DISKS.lock().unwrap_or_else(|e| e.into_inner());
+    known.retain(|(_, d)| d.device != disk.device);
+    known.push((
+        identity,
+        UsbDevice {
+            partitions: Vec::new(),
+            ..disk.clone()
+        },
+    ));
+}
+
+/// Drops what scans remembered about the disk at `device_path`, or the disk
+/// holding that partition, so the next scan queries it in full.
+pub(crate) fn forget_device(device_path: &str) {
+    let name = Path::new(device_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    KNOWN_DISKS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|(_, disk)| {
+            // Only /dev nodes are remembered; Windows lists every disk in one query.
+            let disk_name = disk.device.trim_start_matches("/dev/");
+            !is_partition_of(&name, disk_name) && whole_disk_name(&name) != disk_name
+        });
+}
+
+/// Vendor names, upper-cased, that fake-capacity sticks hide behind.
+const NO_NAME_VENDORS: [&str; 10] = [
+    "", "GENERIC", "GENERAL", "USB", "USB2.0", "USB3.0", "MASS", "FLASH", "UDISK", "CHIPSBNK",
+];
+/// Real flash keeps part of its capacity back, so a stick reporting at least
+/// this much in exact GB or GiB is suspicious.
+const ROUND_SIZE_FROM: u64 = 32 * 1_000_000_000;
+
+/// Whether a removable disk's `size` looks like a fake-capacity stick's:
+/// exactly round on a no-name brand, or far off the capacity its
+/// `descriptors` (model, USB product) advertise.
+fn looks_fake(vendor: &str, descriptors: &[&str], size: u64) -> bool {
+    let no_name = NO_NAME_VENDORS.contains(&vendor.trim().to_uppercase().as_str());
+    let round = size >= ROUND_SIZE_FROM
+        && (size.is_multiple_of(1 << 30) || size.is_multiple_of(1_000_000_000));
+    // A genuine stick reports a little under its label, never more.
+    let mismatched = descriptors
+        .iter()
+        .filter_map(|text| advertised_size(text))
+        .any(|claimed| size > claimed + claimed / 10 || size < claimed - claimed / 6);
+    (no_name && round) || mismatched
+}
+
+/// The capacity named in a descriptor like "Flash Disk 128GB" or "USB 2T".
+fn advertised_size(text: &str) -> Option<u64> {
+    let text = text.to_uppercase();
+    let bytes = text.as_bytes();
+    let mut at = 0;
+    while at < bytes.len() {
+        let digits = bytes[at..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit())
+            .count();
+        // Only whole numbers: the "0" in "USB 3.0" is no capacity.
+        if digits == 0 || (at > 0 && bytes[at - 1] == b'.') {
+            at += digits.max(1);
+            continue;
+        }
+        let number: u64 = text[at..at + digits].parse().ok()?;
+        let unit = text[at + digits..].trim_start();
+        let scale = match unit.as_bytes().first() {
+            Some(b'G') => 1_000_000_000,
+            Some(b'T') => 1_000_000_000_000,
+            _ => 0,
+        };
+        // "G" or "GB", not the start of a word.
+        let rest = unit.get(1..).unwrap_or("").trim_start_matches('B');
+        if scale > 0 && number > 0 && !rest.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+            return number.checked_mul(scale);
+        }
+        at += digits;
+    }
+    None
+}
+
+/// Mount points whose backing disk counts as a system disk.
+const SYSTEM_MOUNTS: [&str; 5] = ["/", "/boot", "/boot/efi", "/usr", "/var"];
+
+/// Lists the disks on this machine, removable or not.
+pub(crate) fn scan_devices() -> Result<Vec<UsbDevice>, BurnError> {
+    scan(None)
+}
+
+/// Like `scan_devices`, but gives up on reading a disk after `timeout` and
+/// lists it as unresponsive.
+pub(crate) fn scan_devices_with_timeout(timeout: Duration) -> Result<Vec<UsbDevice>, BurnError> {
+    scan(Some(timeout))
+}
+
+fn scan(timeout: Option<Duration>) -> Result<Vec<UsbDevice>, BurnError> {
+    let mut devices = if cfg!(target_os = "linux") {
+        scan_linux(Path::new("/sys/block"))
+    } else if cfg!(windows) {
+        scan_windows()
+    } else if cfg!(target_os = "macos") {
+        scan_macos()
+    } else {
+        Err(BurnError::Other(
+            "Device scanning is not supported on this platform".to_string(),
+        ))
+    }?;
+    // Read on every scan, not cached: a burn changes it.
+    for device in devices
+        .iter_mut()
+        .filter(|d| d.is_removable && !d.is_system)
+    {
+        detect_media(device, timeout);
+    }
+    Ok(devices)
+}
+
+/// Fills in `detected_os` and `detected_label` when the disk's headers show
+/// a bootable image: El Torito boot entries or an EFI System Partition.
+///
+/// Reading the disk is where a misbehaving stick hangs, so with a `timeout`
+/// the read runs on a thread of its own and is abandoned when it is late.
+fn detect_media(device: &mut UsbDevice, timeout: Option<Duration>) {
+    let path = device.device.clone();
+    let info = match timeout {
+        None => inspect::inspect_iso(&path),
+        Some(timeout) => {
+            let (tx, rx) = mpsc::channel();
+            thread::spawn(move || {
+                let _ = tx.send(inspect::inspect_iso(&path));
+            });
+            match rx.recv_timeout(timeout) {
+                Ok(info) => info,
+                Err(_) => {
+                    device.unresponsive = true;
+                    for partition in &mut device.partitions {
+                        partition.unresponsive = true;
+                    }
+                    return;
+                }
+            }
+        }
+    };
+    if info.bios_bootable || info.uefi_bootable {
+        device.detected_os = Some(info.os_family);
+        device.detected_label = Some(info.volume_label).filter(|label| !label.is_empty());
+    }
+}
+
+/// Walks sysfs for whole disks and notes which are removable or hold the OS.
+fn scan_linux(sys_block: &Path) -> Result<Vec<UsbDevice>, BurnError> {
+    let entries = fs::read_dir(sys_block)
+        .map_err(|e| BurnError::io("read", &sys_block.display().to_string(), e))?;
+    let system_nodes = linux_system_nodes();
+    let mount_table = linux_mount_table();
+    let mut devices = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        // Loop, RAM, device-mapper and optical nodes are never burn targets.
+        if ["loop", "ram", "zram", "dm-", "sr", "md"]
+            .iter()
+            .any(|prefix| name.starts_with(prefix))
+        {
+            continue;
+        }
+        let dir = entry.path();
+        let usb_dir = fs::canonicalize(&dir)
+            .ok()
+            .and_then(|path| usb_device_dir(&path));
+        let is_removable = usb_dir.is_some() || read_sysfs(&dir.join("removable")) == "1";
+        let sectors: u64 = read_sysfs(&dir.join("size")).parse().unwrap_or(0);
+        // Empty card readers report a zero size.
+        if sectors == 0 {
+            continue;
+        }
+        let device = format!("/dev/{}", name);
+        let serial = usb_dir
+            .as_ref()
+            .map(|usb| read_sysfs(&usb.join("serial")))
+            .unwrap_or_default();
+        let bus_path = usb_dir
+            .as_ref()
+            .and_then(|usb| Some(usb.file_name()?.to_string_lossy().into_owned()))
+            .unwrap_or_default();
+        let identity = format!("{}|{}|{}", serial, bus_path, sectors);
+        let (vendor, model, sector_size, suspected_fake) = match known_disk(&device, &identity) {
+            Some(known) => (
+                known.vendor,
+                known.model,
+                known.sector_size,
+                known.suspected_fake,
+            ),
+            None => {
+                let vendor = read_sysfs(&dir.join("device/vendor"));
+                let model = read_sysfs(&dir.join("device/model"));
+                // The USB product string often names the capacity on the box.
+                let product = usb_dir
+                    .as_ref()
+                    .map(|usb| read_sysfs(&usb.join("product")))
+                    .unwrap_or_default();
+                let suspected_fake =
+                    is_removable && looks_fake(&vendor, &[&model, &product], sectors * 512);
+                let sector_size = read_sysfs(&dir.join("queue/logical_block_size"))
+                    .parse()
+                    .unwrap_or(512);
+                (vendor, model, sector_size, suspected_fake)
+            }
+        };
+        let mut disk = UsbDevice {
+            device,
+            vendor,
+            model,
+            size: sectors * 512,
+            sector_size,
+            serial,
+            bus_path,
+            is_removable,
+            is_system: disk_nodes(&dir, &name)
+                .iter()
+                .any(|node| system_nodes.contains(node)),
+            write_protected: read_sysfs(&dir.join("ro")) == "1",
+            suspected_fake,
+            detected_os: None,
+            detected_label: None,
+            unresponsive: false,
+            mount_points: mount_points_of(&mount_table, &name),
+            partitions: Vec::new(),
+        };
+        remember_disk(identity, &disk);
+        disk.partitions = linux_partitions(&dir, &disk, &system_nodes, &mount_table);
+        devices.push(disk);
+    }
+    devices.sort_by(|a, b| a.device.cmp(&b.device));
+    Ok(devices)
+}
+
+/// The partitions in a disk's sysfs directory, which share the disk's
+/// details but have their own node, size and flags.
+fn linux_partitions(
+    dir: &Path,
+    disk: &UsbDevice,
+    system_nodes: &[String],
+    mount_table: &[MountEntry],
+) -> Vec<UsbDevice> {
+    let mut partitions: Vec<(u64, UsbDevice)> = fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().join("partition").is_file())
+        .map(|entry| {
+            let part_dir = entry.path();
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let number = read_sysfs(&part_dir.join("partition")).parse().unwrap_or(0);
+            let sectors: u64 = read_sysfs(&part_dir.join("size")).parse().unwrap_or(0);
+            let partition = UsbDevice {
+                device: format!("/dev/{}", name),
+                size: sectors * 512,
+                is_system: disk_nodes(&part_dir, &name)
+                    .iter()
+                    .any(|node| system_nodes.contains(node)),
+                write_protected: disk.write_protected || read_sysfs(&part_dir.join("ro")) == "1",
+                mount_points: mount_points_of(mount_table, &name),
+                partitions: Vec::new(),
+                ..disk.clone()
+            };
+            (number, partition)
+        })
+        .collect();
+    partitions.sort_by_key(|(number, _)| *number);
+    partitions
+        .into_iter()
+        .map(|(_, partition)| partition)
+        .collect()
+}
+
+/// The USB device a block device hangs off: the nearest ancestor in sysfs
+/// with a `busnum` attribute. Its directory name is the port path.
+fn usb_device_dir(block_dir: &Path) -> Option<PathBuf> {
+    block_dir
+        .ancestors()
+        .find(|dir| dir.join("busnum").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// Kernel names (`sda2`, `dm-0`, ...) of the block devices behind the system
+/// mounts and active swap.
+fn linux_system_nodes() -> Vec<String> {
+    let mut sources: Vec<String> = fs::read_to_string("/proc/mounts")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let source = fields.next()?;
+            let target = fields.next()?;
+            SYSTEM_MOUNTS.contains(&target).then(|| source.to_string())
+        })
+        .collect();
+    sources.extend(
+        fs::read_to_string("/proc/swaps")
+            .unwrap_or_default()
+            .lines()
+            .skip(1)
+            .filter_map(|line| line.split_whitespace().next().map(str::to_string)),
+    );
+    sources
+        .iter()
+        .filter(|source| source.starts_with("/dev/"))
+        // Resolves /dev/mapper/* and /dev/disk/by-* links to the kernel name.
+        .filter_map(|source| fs::canonicalize(source).ok())
+        .filter_map(|path| Some(path.file_name()?.to_string_lossy().into_owned()))
+        .collect()
+}
+
+/// One line of /proc/mounts for a block device.
+struct MountEntry {
+    /// Kernel name of the mounted node, e.g. `sdb1`.
+    node: String,
+    source: String,
+    target: String,
+}
+
+/// The block devices mounted right now, from /proc/mounts.
+fn linux_mount_table() -> Vec<MountEntry> {
+    fs::read_to_string("/proc/mounts")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let source = fields.next()?;
+            let target = fields.next()?;
+            let node = fs::canonicalize(source).ok()?;
+            Some(MountEntry {
+                node: node.file_name()?.to_string_lossy().into_owned(),
+                source: source.to_string(),
+                // /proc/mounts escapes spaces in paths as \040.
+                target: target.replace("\\040", " "),
+            })
+        })
+        .collect()
+}
+
+fn mount_points_of(table: &[MountEntry], node: &str) -> Vec<String> {
+    table
+        .iter()
+        .filter(|entry| entry.node == node)
+        .map(|entry| entry.target.clone())
+        .collect()
+}
+
+/// The mounted filesystems on `device_path` or its partitions, as
+/// (source, mount point) pairs from /proc/mounts.
+pub(crate) fn linux_mounts(device_path: &str) -> Vec<(String, String)> {
+    let disk = match Path::new(device_path).file_name() {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => return Vec::new(),
+    };
+    linux_mount_table()
+        .into_iter()
+        .filter(|entry| is_partition_of(&entry.node, &disk))
+        .map(|entry| (entry.source, entry.target))
+        .collect()
+}
+
+/// The mount point on `device_path` or its partitions under which `file`
+/// lives, if any: writing the device would destroy the file.
+pub(crate) fn mount_holding(device_path: &str, file: &str) -> Option<String> {
+    let file = fs::canonicalize(file).ok()?;
+    // Windows canonicalizes to `\\?\E:\...`, which no drive letter prefixes.
+    let file = PathBuf::from(file.to_string_lossy().trim_start_matches(r"\\?\"));
+    let mut mounts = Vec::new();
+    if cfg!(target_os = "linux") {
+        mounts.extend(
+            linux_mounts(device_path)
+                .into_iter()
+                .map(|(_, target)| target),
+        );
+    } else {
+        for disk in scan_devices().unwrap_or_default() {
+            if disk.device == device_path {
+                mounts.extend(disk.mount_points);
+                mounts.extend(disk.partitions.into_iter().flat_map(|p| p.mount_points));
+            } else if let Some(partition) = disk
+                .partitions
+                .into_iter()
+                .find(|p| p.device == device_path)
+            {
+                mounts.extend(partition.mount_points);
+            }
+        }
+    }
+    mounts.into_iter().find(|mount| file.starts_with(mount))
+}
+
+/// Whether kernel name `node` is `disk` itself or one of its partitions
+/// (`sdb1`, `mmcblk0p1`).
+fn is_partition_of(node: &str, disk: &str) -> bool {
+    match node.strip_prefix(disk) {
+        Some(rest) => {
+            let number = rest.strip_prefix('p').unwrap_or(rest);
+            rest.is_empty() || (!number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()))
+        }
+        None => false,
+    }
+}
+
+/// The disk's own kernel name, its partitions, and the device-mapper or RAID
+/// nodes stacked on either.
+fn disk_nodes(dir: &Path, name: &str) -> Vec<String> {
+    let mut nodes = vec![name.to_string()];
+    let mut dirs = vec![dir.to_path_buf()];
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let part = entry.file_name().to_string_lossy().into_owned();
+            if part.starts_with(name) {
+                nodes.push(part);
+                dirs.push(entry.path());
+            }
+        }
+    }
+    for dir in dirs {
+        if let Ok(holders) = fs::read_dir(dir.join("holders")) {
+            nodes.extend(
+                holders
+                    .flatten()
+                    .map(|holder| holder.file_name().to_string_lossy().into_owned()),
+            );
+        }
+    }
+    nodes
+}
+
+/// Reads a sysfs attribute, trimmed; empty when it is missing.
+fn read_sysfs(path: &Path) -> String {
+    fs::read_to_string(path)
+        .map(|s| s.trim().to_string())
+        .unwrap_or_default()
+}
+
+/// Lists the physical disks with `diskutil`, which fronts IOKit and
+/// DiskArbitration, then asks it about each disk and partition.
+fn scan_macos() -> Result<Vec<UsbDevice>, BurnError> {
+    let list = diskutil(&["list", "physical"]).map_err(BurnError::Other)?;
+    // The disks holding the running system: the whole disk of "/", and the
+    // physical store under an APFS container.
+    let root = diskutil(&["info", "/"]).unwrap_or_default();
+    let system_disks: Vec<String> = ["Part of Whole", "APFS Physical Store"]
+        .iter()
+        .filter_map(|key| diskutil_field(&root, key))
+        .map(|node| whole_disk_name(&node))
+        .collect();
+    let mut devices = Vec::new();
+    for (device, row, partitions) in parse_diskutil_list(&list) {
+        // A disk without partitions carries its filesystem itself, and so
+        // its mount point, which has to be asked for every time.
+        let known = known_disk(&device, &row).filter(|_| !partitions.is_empty());
+        let mut disk = match known {
+            Some(disk) => disk,
+            None => match diskutil(&["info", &device]) {
+                Ok(info) => macos_device(&device, &info),
+                Err(_) => continue,
+            },
+        };
+        let name = device.trim_start_matches("/dev/");
+        disk.is_system = system_disks.iter().any(|system| system == name);
+        remember_disk(row, &disk);
+        disk.partitions = partitions
+            .iter()
+            .filter_map(|part| {
+                let info = diskutil(&["info", part]).ok()?;
+                let partition = macos_device(part, &info);
+                Some(UsbDevice {
+                    device: partition.device,
+                    size: partition.size,
+                    write_protected: disk.write_protected || partition.write_protected,
+                    mount_points: partition.mount_points,
+                    partitions: Vec::new(),
+                    ..disk.clone()
+                })
+            })
+            .collect();
+        devices.push(disk);
+    }
+    Ok(devices)
+}
+
+/// The disks (`/dev/diskN`) with their own row, which gives their scheme and
+/// size, and their partitions (`/dev/diskNsM`) from `diskutil list physical`.
+fn parse_diskutil_list(text: &str) -> Vec<(String, String, Vec<String>)> {
+    let mut disks: Vec<(String, String, Vec<String>)> = Vec::new();
+    for line in text.lines() {
+        if line.starts_with("/dev/disk") {
+            let device = line.split_whitespace().next().unwrap_or("").to_string();
+            disks.push((device, String::new(), Vec::new()));
+        } else if let Some((disk, row, partitions)) = disks.last_mut() {
+            // Rows end in the identifier; the first (index 0) is the disk itself.
+            let id = line.split_whitespace().last().unwrap_or("");
+            let node = format!("/dev/{}", id);
+            if node == *disk {
+                *row = line.trim().to_string();
+            } else if id.starts_with("disk") {
+                partitions.push(node);
+            }
+        }
+    }
+    disks
+}
+
+/// A disk or partition as `diskutil info` describes it.
+fn macos_device(device: &str, info: &str) -> UsbDevice {
+    let field = |key| diskutil_field(info, key).unwrap_or_default();
+    // "32.0 GB (32017047552 Bytes) (exactly 62533296 512-Byte-Units)"
+    let size = field("Disk Size")
+        .split_once('(')
+        .and_then(|(_, rest)| rest.split_whitespace().next()?.parse().ok())
+        .unwrap_or(0);
+    let protocol = field("Protocol");
+    let model = field("Device / Media Name");
+    let is_removable = protocol == "USB"
+        || protocol == "Secure Digital"
+        || field("Device Location") == "External"
+        || field("Removable Media") == "Removable";
+    let suspected_fake = is_removable && looks_fake("", &[&model], size);
+    UsbDevice {
+        device: device.to_string(),
+        vendor: String::new(),
+        model,
+        size,
+        // "512 Bytes"
+        sector_size: field("Device Block Size")
+            .split_whitespace()
+            .next()
+            .and_then(|bytes| bytes.parse().ok())
+            .unwrap_or(512),
+        serial: String::new(),
+        bus_path: String::new(),
+        is_removable,
+        is_system: false,
+        write_protected: field("Media Read-Only") == "Yes" || field("Read-Only Media") == "Yes",
+        suspected_fake,
+        detected_os: None,
+        detected_label: None,
+        unresponsive: false,
+        mount_points: diskutil_field(info, "Mount Point")
+            .filter(|mount| !mount.is_empty() && !mount.starts_with("Not "))
+            .into_iter()
+            .collect(),
+        partitions: Vec::new(),
+    }
+}
+
+/// The value after `key:` in `diskutil info` output.
+fn diskutil_field(info: &str, key: &str) -> Option<String> {
+    info.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        (name.trim() == key).then(|| value.trim().to_string())
+    })
+}
+
+/// `disk0` for `disk0s2` or `/dev/disk0s2`.
+fn whole_disk_name(node: &str) -> String {
+    let name = node.trim_start_matches("/dev/");
+    match name[4.min(name.len())..].find('s') {
+        Some(at) => name[..4 + at].to_string(),
+        None => name.to_string(),
+    }
+}
+
+fn diskutil(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("diskutil")
+        .args(args)
+        .output()
+        .map_err(|e| format!("Could not run diskutil: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "diskutil {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Asks PowerShell for every disk, with its bus and system flags.
+fn scan_windows() -> Result<Vec<UsbDevice>, BurnError> {
+    let script = "Get-Disk | ForEach-Object { \
+                  \"$($_.Number)|$($_.Manufacturer)|$($_.FriendlyName)|$($_.Size)|\
+                  $($_.BusType)|$($_.IsSystem -or $_.IsBoot)|\
+                  $($_.SerialNumber)|$($_.Location)|$($_.IsReadOnly)|\
+                  $($_.LogicalSectorSize)\" }; \
+                  Get-Partition | ForEach-Object { \
+                  \"P|$($_.DiskNumber)|$($_.PartitionNumber)|$($_.Size)|$($_.DriveLetter)\" }";
+    let output = Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", script])
+        .output()
+        .map_err(|e| BurnError::Other(format!("Could not run PowerShell: {}", e)))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("Access denied") || stderr.contains("PermissionDenied") {
+            return Err(BurnError::PermissionDenied("the disk list".to_string()));
+        }
+        return Err(BurnError::Other(format!(
+            "Get-Disk failed: {}",
+            stderr.trim()
+        )));
+    }
+    Ok(parse_windows_disks(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// Parses the `Number|Manufacturer|FriendlyName|Size|BusType|IsSystem|Serial|Location|
+/// IsReadOnly|LogicalSectorSize` disk lines and `P|DiskNumber|PartitionNumber|Size|DriveLetter`
+/// partition lines from `scan_windows`.
+fn parse_windows_disks(text: &str) -> Vec<UsbDevice> {
+    let mut disks: Vec<UsbDevice> = text
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.trim().splitn(10, '|');
+            let number: u32 = fields.next()?.parse().ok()?;
+            let vendor = fields.next()?.trim().to_string();
+            let model = fields.next()?.trim().to_string();
+            let size = fields.next()?.trim().parse().ok()?;
+            let bus = fields.next()?.trim();
+            let is_system = fields.next()?.trim().eq_ignore_ascii_case("true");
+            let serial = fields.next().unwrap_or("").trim().to_string();
+            let bus_path = fields.next().unwrap_or("").trim().to_string();
+            let write_protected = fields
+                .next()
+                .is_some_and(|ro| ro.trim().eq_ignore_ascii_case("true"));
+            let sector_size = fields
+                .next()
+                .and_then(|bytes| bytes.trim().parse().ok())
+                .unwrap_or(512);
+            let is_removable = bus.eq_ignore_ascii_case("USB") || bus.eq_ignore_ascii_case("SD");
+            let suspected_fake = is_removable && looks_fake(&vendor, &[&model], size);
+            Some(UsbDevice {
+                device: format!(r"\\.\PhysicalDrive{}", number),
+                vendor,
+                model,
+                size,
+                sector_size,
+                serial,
+                bus_path,
+                is_removable,
+                is_system,
+                write_protected,
+                suspected_fake,
+                detected_os: None,
+                detected_label: None,
+                unresponsive: false,
+                mount_points: Vec::new(),
+                partitions: Vec::new(),
+            })
+        })
+        .collect();
+    for line in text.lines() {
+        let mut fields = line.trim().splitn(5, '|');
+        if fields.next() != Some("P") {
+            continue;
+        }
+        let parsed = (|| {
+            let disk: u32 = fields.next()?.trim().parse().ok()?;
+            let number: u32 = fields.next()?.trim().parse().ok()?;
+            let size: u64 = fields.next()?.trim().parse().ok()?;
+            // Empty, or a NUL character, when the partition has no letter.
+            let letter = fields
+                .next()
+                .and_then(|letter| letter.trim().chars().next())
+                .filter(char::is_ascii_alphabetic);
+            Some((disk, number, size, letter))
+        })();
+        let (disk_number, number, size, letter) = match parsed {
+            Some(parsed) => parsed,
+            None => continue,
+        };
+        let disk_path = format!(r"\\.\PhysicalDrive{}", disk_number);
+        if let Some(disk) = disks.iter_mut().find(|d| d.device == disk_path) {
+            let partition = UsbDevice {
+                // The partition's raw node, which needs no drive letter.
+                device: format!(
+                    r"\\?\GLOBALROOT\Device\Harddisk{}\Partition{}",
+                    disk_number, number
+                ),
+                size,
+                mount_points: letter.map(|l| format!("{}:", l)).into_iter().collect(),
+                partitions: Vec::new(),
+                ..disk.clone()
+            };
+            disk.partitions.push(partition);
+        }
+    }
+    disks
+}
@@ -0,0 +1,127 @@
+// rustburn-core/src/direct.rs
+
+// Reading a device past the OS page cache, so a verify sees what the device
+// holds rather than what the write just left in memory. Direct IO wants
+// buffers, offsets and lengths aligned to the sector size, so reads go
+// through an aligned bounce buffer.
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+
+/// Alignment of direct reads; a multiple of every logical sector size in use.
+const ALIGN: usize = 4096;
+/// O_DIRECT, whose value differs between architectures.
+#[cfg(target_os = "linux")]
+const O_DIRECT: i32 = if cfg!(any(target_arch = "aarch64", target_arch = "arm")) {
+    0o200000
+} else if cfg!(any(target_arch = "powerpc", target_arch = "powerpc64")) {
+    0o400000
+} else if cfg!(any(target_arch = "mips", target_arch = "mips64")) {
+    0o100000
+} else {
+    0o40000
+};
+#[cfg(windows)]
+const FILE_FLAG_NO_BUFFERING: u32 = 0x2000_0000;
+
+/// Whether this platform can open a device for uncached reads.
+pub(crate) const SUPPORTED: bool = cfg!(any(target_os = "linux", windows));
+
+/// A device opened for reading, with or without the OS cache.
+pub(crate) struct DeviceReader {
+    file: File,
+    /// Bounce buffer for direct reads; empty when reading through the cache.
+    bounce: Vec<u8>,
+    /// Where in `bounce` the aligned part starts.
+    start: usize,
+    pos: u64,
+}
+
+impl DeviceReader {
+    /// Opens `path` for uncached reads of up to `block_size` bytes at a time.
+    /// Fails where the platform or the file doesn't allow it.
+    pub(crate) fn open_direct(path: &str, block_size: usize) -> io::Result<Self> {
+        let file = open_uncached(path)?;
+        let len = block_size.div_ceil(ALIGN) * ALIGN + ALIGN;
+        let bounce = vec![0u8; len + ALIGN];
+        let start = bounce.as_ptr().align_offset(ALIGN);
+        Ok(DeviceReader {
+            file,
+            bounce,
+            start,
+            pos: 0,
+        })
+    }
+
+    /// Opens `path` for ordinary reads through the OS cache.
+    pub(crate) fn open_buffered(path: &str) -> io::Result<Self> {
+        Ok(DeviceReader {
+            file: File::open(path)?,
+            bounce: Vec::new(),
+            start: 0,
+            pos: 0,
+        })
+    }
+
+    pub(crate) fn is_direct(&self) -> bool {
+        !self.bounce.is_empty()
+    }
+}
+
+impl Read for DeviceReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if !self.is_direct() {
+            return self.file.read(buf);
+        }
+        let end = self.start + self.bounce.len() - ALIGN;
+        let aligned = &mut self.bounce[self.start..end];
+        let skip = (self.pos % ALIGN as u64) as usize;
+        let want = buf.len().min(aligned.len() - skip);
+        let len = (skip + want).div_ceil(ALIGN) * ALIGN;
+        self.file.seek(SeekFrom::Start(self.pos - skip as u64))?;
+        let n = self.file.read(&mut aligned[..len])?;
+        let got = n.saturating_sub(skip).min(want);
+        buf[..got].copy_from_slice(&aligned[skip..skip + got]);
+        self.pos += got as u64;
+        Ok(got)
+    }
+}
+
+impl Seek for DeviceReader {
+    fn seek(&mut self, from: SeekFrom) -> io::Result<u64> {
+        if !self.is_direct() {
+            return self.file.seek(from);
+        }
+        self.pos = match from {
+            SeekFrom::Start(offset) => offset,
+            SeekFrom::Current(delta) => self
+                .pos
+                .checked_add_signed(delta)
+                .ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput))?,
+            SeekFrom::End(_) => self.file.seek(from)?,
+        };
+        Ok(self.pos)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn open_uncached(path: &str) -> io::Result<File> {
+    use std::os::unix::fs::OpenOptionsExt;
+    std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(O_DIRECT)
+        .open(path)
+}
+
+#[cfg(windows)]
+fn open_uncached(path: &str) -> io::Result<File> {
+    use std::os::windows::fs::OpenOptionsExt;
+    std::fs::OpenOptions::new()
+        .read(true)
+        .custom_flags(FILE_FLAG_NO_BUFFERING)
+        .open(path)
+}
+
+#[cfg(not(any(target_os = "linux", windows)))]
+fn open_uncached(_path: &str) -> io::Result<File> {
+    Err(io::Error::from(io::ErrorKind::Unsupported))
+}
@@ -0,0 +1,94 @@
+// rustburn-core/src/eject.rs
+
+// Safe removal: flush what the OS still holds for the device, unmount it and
+// ask the platform to power it down.
+use crate::device;
+use crate::error::BurnError;
+use crate::progress::{log, send, ProgressSink, UiProgress};
+use std::fs::File;
+use std::process::Command;
+
+/// Flushes, unmounts and ejects `device_path`, ending with `Done` or `Error`.
+pub(crate) fn eject(device_path: String, tx: impl ProgressSink) {
+    send(&tx, UiProgress::StartingEject);
+    match run(&device_path, &tx) {
+        Ok(()) => {
+            log(&tx, format!("{} can be removed safely", device_path));
+            send(&tx, UiProgress::Done);
+        }
+        Err(e) => send(&tx, UiProgress::Error(BurnError::Other(e))),
+    }
+}
+
+/// Does the work of `eject` without the start and end messages.
+pub(crate) fn run(device_path: &str, tx: &dyn ProgressSink) -> Result<(), String> {
+    if cfg!(windows) {
+        return eject_windows(device_path);
+    }
+    if let Ok(device) = File::open(device_path) {
+        let _ = device.sync_all();
+    }
+    if cfg!(target_os = "macos") {
+        return run_tool("diskutil", &["eject", device_path])
+            .map_err(|e| format!("Could not eject {}: {}", device_path, e));
+    }
+    if cfg!(target_os = "linux") {
+        unmount(device_path, tx)?;
+        // udisks powers the port down too; plain eject is the fallback.
+        run_tool("udisksctl", &["power-off", "-b", device_path])
+            .or_else(|_| run_tool("eject", &[device_path]))
+            .map_err(|e| format!("Could not eject {}: {}", device_path, e))
+    } else {
+        Err("Ejecting is not supported on this platform".to_string())
+    }
+}
+
+/// Unmounts every filesystem on `device_path` and its partitions. macOS
+/// needs this before the raw device can be opened for writing.
+pub(crate) fn unmount(device_path: &str, tx: &dyn ProgressSink) -> Result<(), String> {
+    if cfg!(target_os = "macos") {
+        log(tx, format!("Unmounting the volumes on {}", device_path));
+        return run_tool("diskutil", &["unmountDisk", device_path])
+            .map_err(|e| format!("Could not unmount {}: {}", device_path, e));
+    }
+    if cfg!(target_os = "linux") {
+        for (source, target) in device::linux_mounts(device_path) {
+            log(tx, format!("Unmounting {} from {}", source, target));
+            run_tool("umount", &[&source])
+                .map_err(|e| format!("{} is busy and could not be unmounted: {}", target, e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Ejects every volume on the disk through the shell, like Explorer's Eject.
+fn eject_windows(device_path: &str) -> Result<(), String> {
+    let number = device_path
+        .trim_start_matches(r"\\.\PhysicalDrive")
+        .parse::<u32>()
+        .map_err(|_| format!("{} is not a physical drive path", device_path))?;
+    let script = format!(
+        "$shell = New-Object -ComObject Shell.Application; \
+         Get-Partition -DiskNumber {} | Where-Object DriveLetter | ForEach-Object {{ \
+         $shell.Namespace(17).ParseName(\"$($_.DriveLetter):\").InvokeVerb('Eject') }}",
+        number
+    );
+    run_tool(
+        "powershell",
+        &["-NoProfile", "-NonInteractive", "-Command", &script],
+    )
+    .map_err(|e| format!("Could not eject {}: {}", device_path, e))
+}
+
+/// Runs `program`, turning a failed start or exit into its stderr.
+pub(crate) fn run_tool(program: &str, args: &[&str]) -> Result<(), String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("could not run {}: {}", program, e))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
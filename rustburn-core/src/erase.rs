@@ -0,0 +1,98 @@
+// rustburn-core/src/erase.rs
+
+// Wiping a device so it can be reused: either just the partition tables or
+// every byte.
+use crate::burn::fraction;
+use crate::error::BurnError;
+use crate::options::EraseMode;
+use crate::progress::{log, send, ProgressSink, SpeedMeter, UiProgress};
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+
+/// How much of each end a quick erase clears: the MBR and primary GPT at the
+/// start, the backup GPT at the end, and any filesystem signatures nearby.
+const QUICK_SPAN: u64 = 4 * 1024 * 1024;
+const BLOCK: usize = 1024 * 1024;
+/// How much of each end `wipe_tables` clears: the MBR and primary GPT in the
+/// first MB, the backup GPT in the last.
+const TABLE_SPAN: u64 = 1024 * 1024;
+
+/// Erases `device_path` as `mode` says, ending with `Done` or `Error`.
+pub(crate) fn erase(device_path: String, mode: EraseMode, tx: impl ProgressSink) {
+    send(&tx, UiProgress::StartingErase);
+    match run(&device_path, mode, &tx) {
+        Ok(()) => {
+            log(&tx, format!("Erased {}", device_path));
+            send(&tx, UiProgress::Done);
+        }
+        Err(e) => send(&tx, UiProgress::Error(e)),
+    }
+}
+
+fn run(device_path: &str, mode: EraseMode, tx: &dyn ProgressSink) -> Result<(), BurnError> {
+    let mut device = OpenOptions::new()
+        .write(true)
+        .open(device_path)
+        .map_err(|e| BurnError::io("open", device_path, e))?;
+    let size = device
+        .seek(SeekFrom::End(0))
+        .map_err(|e| BurnError::io("read the size of", device_path, e))?;
+    let ranges = match mode {
+        EraseMode::FullZero => vec![(0, size)],
+        EraseMode::QuickZeroTable if size <= 2 * QUICK_SPAN => vec![(0, size)],
+        EraseMode::QuickZeroTable => vec![(0, QUICK_SPAN), (size - QUICK_SPAN, size)],
+    };
+    let total: u64 = ranges.iter().map(|(start, end)| end - start).sum();
+    let zeros = vec![0u8; BLOCK];
+    let mut done = 0u64;
+    let mut meter = SpeedMeter::new();
+    for (start, end) in ranges {
+        device
+            .seek(SeekFrom::Start(start))
+            .map_err(|e| BurnError::io("seek", device_path, e))?;
+        let mut at = start;
+        while at < end {
+            let n = (end - at).min(BLOCK as u64) as usize;
+            device.write_all(&zeros[..n]).map_err(|e| {
+                BurnError::Other(format!(
+                    "Write to {} failed at offset {}: {}",
+                    device_path, at, e
+                ))
+            })?;
+            at += n as u64;
+            done += n as u64;
+            if mode == EraseMode::FullZero {
+                send(
+                    tx,
+                    UiProgress::WritingDetailed {
+                        fraction: fraction(done, total),
+                        bytes_done: done,
+                        total,
+                        bytes_per_sec: meter.update(done),
+                    },
+                );
+            }
+        }
+    }
+    device
+        .sync_all()
+        .map_err(|e| BurnError::io("flush", device_path, e))
+}
+
+/// Zeroes the partition tables at both ends of the open `device`, so nothing
+/// of an earlier layout survives a smaller image written over it.
+pub(crate) fn wipe_tables(device: &mut File, device_path: &str) -> Result<(), BurnError> {
+    let size = device
+        .seek(SeekFrom::End(0))
+        .map_err(|e| BurnError::io("read the size of", device_path, e))?;
+    let zeros = vec![0u8; TABLE_SPAN.min(size) as usize];
+    for start in [0, size - zeros.len() as u64] {
+        device
+            .seek(SeekFrom::Start(start))
+            .and_then(|_| device.write_all(&zeros))
+            .map_err(|e| BurnError::io("clear the partition table of", device_path, e))?;
+    }
+    device
+        .sync_data()
+        .map_err(|e| BurnError::io("flush", device_path, e))
+}
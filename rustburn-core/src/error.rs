@@ -0,0 +1,98 @@
+// rustburn-core/src/error.rs
+
+// How a burn fails, in a form callers can match on.
+use std::fmt;
+use std::io;
+
+/// What to do about a permission error on this platform.
+pub(crate) const PERMISSION_FIX: &str = if cfg!(windows) {
+    "writing to disks needs elevation, so run RustBurn as administrator"
+} else if cfg!(target_os = "macos") {
+    "run RustBurn with sudo"
+} else {
+    "run with sudo or add your user to the disk group"
+};
+
+/// Whether `e` means the device itself went away: ENXIO or ENODEV, or on
+/// Windows ERROR_DEV_NOT_EXIST, ERROR_NO_SUCH_DEVICE or
+/// ERROR_DEVICE_NOT_CONNECTED.
+pub(crate) fn is_disconnect(e: &io::Error) -> bool {
+    let codes: &[i32] = if cfg!(windows) {
+        &[55, 433, 1167]
+    } else {
+        &[6, 19]
+    };
+    e.raw_os_error().is_some_and(|code| codes.contains(&code))
+}
+
+/// Why an operation did not finish.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BurnError {
+    /// The cancel flag was set.
+    Cancelled,
+    /// The OS refused access (EACCES/EPERM, or Windows wanting elevation);
+    /// holds the path that could not be opened.
+    PermissionDenied(String),
+    /// The device's lock switch is on.
+    WriteProtected,
+    /// The device was unplugged while in use.
+    Disconnected,
+    /// The image's digest did not match the one the user supplied.
+    ChecksumMismatch {
+        expected: String,
+        actual: String,
+    },
+    /// The device read back differently from the image.
+    VerificationFailed(String),
+    Other(String),
+}
+
+impl BurnError {
+    /// Wraps an IO error from `action` (e.g. "open /dev/sdb"), keeping
+    /// permission problems distinct so front ends can offer to elevate.
+    pub(crate) fn io(action: impl fmt::Display, path: &str, e: io::Error) -> Self {
+        // 19 is Windows' ERROR_WRITE_PROTECT, 740 its ERROR_ELEVATION_REQUIRED.
+        let write_protect = e.kind() == io::ErrorKind::ReadOnlyFilesystem
+            || (cfg!(windows) && e.raw_os_error() == Some(19));
+        let denied = e.kind() == io::ErrorKind::PermissionDenied
+            || (cfg!(windows) && e.raw_os_error() == Some(740));
+        if write_protect {
+            BurnError::WriteProtected
+        } else if is_disconnect(&e) {
+            BurnError::Disconnected
+        } else if denied {
+            BurnError::PermissionDenied(path.to_string())
+        } else {
+            BurnError::Other(format!("Could not {} {}: {}", action, path, e))
+        }
+    }
+}
+
+impl fmt::Display for BurnError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BurnError::Cancelled => write!(f, "Cancelled"),
+            BurnError::PermissionDenied(path) => {
+                write!(f, "Permission denied opening {}; {}", path, PERMISSION_FIX)
+            }
+            BurnError::WriteProtected => write!(
+                f,
+                "Device is write-protected; slide the lock switch off and try again"
+            ),
+            BurnError::Disconnected => write!(
+                f,
+                "Device disconnected during write — the burn is incomplete"
+            ),
+            BurnError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "Checksum mismatch: expected {}, got {}. The image may be corrupted; \
+                 download it again.",
+                expected, actual
+            ),
+            BurnError::VerificationFailed(detail) => write!(f, "Verification failed: {}", detail),
+            BurnError::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for BurnError {}
@@ -0,0 +1,178 @@
+// rustburn-core/src/extras.rs
+
+// Extra files, such as an autounattend.xml, preseed or kickstart file, copied
+// onto the written image's FAT volume so the install runs unattended.
+use crate::eject::run_tool;
+use crate::error::BurnError;
+use crate::fat;
+use crate::options::BurnOptions;
+use crate::partition::Table;
+use crate::progress::{log, ProgressSink};
+use std::fs::{self, File};
+use std::path::{Component, Path, PathBuf};
+
+/// Refuses missing sources and destinations that would land outside the
+/// volume, before anything is written.
+pub(crate) fn check(options: &BurnOptions) -> Result<(), BurnError> {
+    if !options.extra_files.is_empty() && !cfg!(any(target_os = "linux", target_os = "macos")) {
+        return Err(BurnError::Other(
+            "Copying extra files onto the volume is only supported on Linux and macOS".to_string(),
+        ));
+    }
+    for (source, dest) in &options.extra_files {
+        if !source.is_file() {
+            return Err(BurnError::Other(format!(
+                "The extra file {} does not exist",
+                source.display()
+            )));
+        }
+        if relative(dest).is_none() {
+            return Err(BurnError::Other(format!(
+                "'{}' is not a file path on the volume",
+                dest
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Mounts the first FAT volume of the written device (the whole device for a
+/// superfloppy image, else the partition holding it), copies
+/// `options.extra_files` onto it and unmounts it again.
+pub(crate) fn copy_to_volume(
+    options: &BurnOptions,
+    tx: &dyn ProgressSink,
+) -> Result<(), BurnError> {
+    if options.extra_files.is_empty() {
+        return Ok(());
+    }
+    let path = &options.device_path;
+    let (index, start) = find_fat_volume(path)?.ok_or_else(|| {
+        BurnError::Other(
+            "The image has no FAT volume, so the extra files could not be copied".to_string(),
+        )
+    })?;
+    let mount_point =
+        std::env::temp_dir().join(format!("rustburn-extra-files-{}", std::process::id()));
+    fs::create_dir_all(&mount_point)
+        .map_err(|e| BurnError::io("create", &mount_point.display().to_string(), e))?;
+    if let Err(e) = mount(path, index, start, &mount_point) {
+        let _ = fs::remove_dir(&mount_point);
+        return Err(e);
+    }
+    log(
+        tx,
+        format!("Mounted the volume at {:.1} MB", start as f64 / 1e6),
+    );
+    let copied = copy_files(options, &mount_point, tx);
+    let unmounted = unmount(&mount_point);
+    let _ = fs::remove_dir(&mount_point);
+    copied?;
+    unmounted.map_err(|e| {
+        BurnError::Other(format!(
+            "Could not unmount {}: {}",
+            mount_point.display(),
+            e
+        ))
+    })
+}
+
+/// The position in the table and byte offset of the first FAT volume; a
+/// superfloppy image is index 0 at offset 0.
+fn find_fat_volume(path: &str) -> Result<Option<(usize, u64)>, BurnError> {
+    let mut device = File::open(path).map_err(|e| BurnError::io("open", path, e))?;
+    let starts = Table::read(&mut device)
+        .map_err(|e| BurnError::io("read the boot sector of", path, e))?
+        .map(|table| table.partition_starts())
+        .unwrap_or_default();
+    for (index, start) in std::iter::once(0).chain(starts).enumerate() {
+        if fat::is_fat(&mut device, start).map_err(|e| BurnError::io("read", path, e))? {
+            return Ok(Some((index, start)));
+        }
+    }
+    Ok(None)
+}
+
+/// Mounts the volume at `start` of `device_path`, entry `index` of its
+/// table, on `mount_point`.
+pub(crate) fn mount(
+    device_path: &str,
+    index: usize,
+    start: u64,
+    mount_point: &Path,
+) -> Result<(), BurnError> {
+    let target = mount_point.display().to_string();
+    let mounted = if cfg!(target_os = "linux") {
+        // A loop device at the offset needs no partition node.
+        run_tool(
+            "mount",
+            &[
+                "-t",
+                "vfat",
+                "-o",
+                &format!("loop,offset={}", start),
+                device_path,
+                &target,
+            ],
+        )
+    } else if cfg!(target_os = "macos") {
+        // diskutil numbers the slices in table order.
+        let node = match index {
+            0 => device_path.to_string(),
+            n => format!("{}s{}", device_path, n),
+        };
+        run_tool("diskutil", &["mount", "-mountPoint", &target, &node])
+    } else {
+        return Err(BurnError::Other(
+            "Copying extra files onto the volume is only supported on Linux and macOS".to_string(),
+        ));
+    };
+    mounted.map_err(|e| BurnError::Other(format!("Could not mount the volume: {}", e)))
+}
+
+/// Unmounts what `mount` mounted.
+pub(crate) fn unmount(mount_point: &Path) -> Result<(), String> {
+    let target = mount_point.display().to_string();
+    if cfg!(target_os = "macos") {
+        run_tool("diskutil", &["unmount", &target])
+    } else {
+        run_tool("umount", &[&target])
+    }
+}
+
+/// Copies `options.extra_files` onto the volume mounted at `mount_point`.
+pub(crate) fn copy_files(
+    options: &BurnOptions,
+    mount_point: &Path,
+    tx: &dyn ProgressSink,
+) -> Result<(), BurnError> {
+    for (source, dest) in &options.extra_files {
+        let target = relative(dest)
+            .map(|rel| mount_point.join(rel))
+            .ok_or_else(|| {
+                BurnError::Other(format!("'{}' is not a file path on the volume", dest))
+            })?;
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| BurnError::io("create", &parent.display().to_string(), e))?;
+        }
+        fs::copy(source, &target)
+            .map_err(|e| BurnError::io("copy", &source.display().to_string(), e))?;
+        log(tx, format!("Copied {} to {}", source.display(), dest));
+    }
+    Ok(())
+}
+
+/// `dest` as a path below the volume's root, or None when it names no file
+/// or climbs out with `..`.
+fn relative(dest: &str) -> Option<PathBuf> {
+    let mut path = PathBuf::new();
+    for component in Path::new(&dest.replace('\\', "/")).components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::RootDir | Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!path.as_os_str().is_empty()).then_some(path)
+}
@@ -0,0 +1,287 @@
+// rustburn-core/src/fat.rs
+
+// Volume labels on FAT filesystems, and new FAT32 volumes of any size. A FAT
+// volume keeps its label twice: in the boot sector and as a root directory
+// entry, which is the one most OSes show.
+use crate::burn::{fraction, read_full};
+use crate::error::BurnError;
+use crate::progress::{send, ProgressSink, SpeedMeter, UiProgress};
+use std::fs::File;
+use std::io::{self, Seek, SeekFrom, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Longest label FAT stores.
+pub(crate) const LABEL_MAX: usize = 11;
+/// Characters FAT does not allow in a label.
+const LABEL_FORBIDDEN: &str = "\"*+,./:;<=>?[\\]|";
+const ATTR_VOLUME_ID: u8 = 0x08;
+const ATTR_LONG_NAME: u8 = 0x0F;
+const DIR_ENTRY_LEN: usize = 32;
+const SECTOR: u64 = 512;
+/// FAT32's cluster count range; fewer clusters make it FAT16 to every reader.
+const FAT32_MIN_CLUSTERS: u64 = 65_525;
+const FAT32_MAX_CLUSTERS: u64 = 0x0FFF_FFF5;
+/// Sectors before the first FAT: boot sector, FSInfo, and their backups at 6.
+const RESERVED_SECTORS: u64 = 32;
+const BACKUP_BOOT_SECTOR: u64 = 6;
+const ZERO_BLOCK: usize = 1024 * 1024;
+
+/// Uppercases `label` and pads it to the 11 bytes FAT stores, or says why
+/// FAT can't hold it.
+pub(crate) fn check_label(label: &str) -> Result<[u8; LABEL_MAX], BurnError> {
+    let label = label.trim().to_uppercase();
+    let problem = if label.is_empty() {
+        Some("must not be empty".to_string())
+    } else if !label.is_ascii() {
+        Some("must be ASCII".to_string())
+    } else if label.len() > LABEL_MAX {
+        Some(format!("must be at most {} characters", LABEL_MAX))
+    } else if label
+        .chars()
+        .any(|c| c.is_ascii_control() || LABEL_FORBIDDEN.contains(c))
+    {
+        Some("must not contain \" * + , . / : ; < = > ? [ \\ ] |".to_string())
+    } else {
+        None
+    };
+    if let Some(problem) = problem {
+        return Err(BurnError::Other(format!(
+            "The volume label '{}' {}",
+            label, problem
+        )));
+    }
+    let mut padded = [b' '; LABEL_MAX];
+    padded[..label.len()].copy_from_slice(label.as_bytes());
+    Ok(padded)
+}
+
+/// Sets the label of the FAT filesystem starting `start` bytes into
+/// `device`. Returns false, writing nothing, when there is no FAT
+/// filesystem there.
+pub(crate) fn relabel(device: &mut File, start: u64, label: &[u8; LABEL_MAX]) -> io::Result<bool> {
+    let mut boot = [0u8; 512];
+    device.seek(SeekFrom::Start(start))?;
+    if read_full(device, &mut boot)? < 512 || boot[510..512] != [0x55, 0xAA] {
+        return Ok(false);
+    }
+    let (label_at, root_at, root_len) = match layout(&boot) {
+        Some(layout) => layout,
+        None => return Ok(false),
+    };
+
+    // Only the first cluster of a FAT32 root is searched; formatters put the
+    // label entry first.
+    let mut root = vec![0u8; root_len as usize];
+    device.seek(SeekFrom::Start(start + root_at))?;
+    let filled = read_full(device, &mut root)?;
+    root.truncate(filled - filled % DIR_ENTRY_LEN);
+    let mut slot = None;
+    for (i, entry) in root.chunks_exact(DIR_ENTRY_LEN).enumerate() {
+        let attr = entry[11];
+        if entry[0] == 0x00 || entry[0] == 0xE5 {
+            slot = slot.or(Some((i, false)));
+            if entry[0] == 0x00 {
+                break;
+            }
+        } else if attr != ATTR_LONG_NAME && attr & ATTR_VOLUME_ID != 0 {
+            slot = Some((i, true));
+            break;
+        }
+    }
+    if let Some((i, existing)) = slot {
+        let entry = &mut root[i * DIR_ENTRY_LEN..(i + 1) * DIR_ENTRY_LEN];
+        if !existing {
+            entry.fill(0);
+            entry[11] = ATTR_VOLUME_ID;
+        }
+        entry[..LABEL_MAX].copy_from_slice(label);
+        device.seek(SeekFrom::Start(
+            start + root_at + (i * DIR_ENTRY_LEN) as u64,
+        ))?;
+        device.write_all(entry)?;
+    }
+    boot[label_at..label_at + LABEL_MAX].copy_from_slice(label);
+    device.seek(SeekFrom::Start(start))?;
+    device.write_all(&boot)?;
+    device.sync_all()?;
+    Ok(true)
+}
+
+/// Whether a FAT filesystem starts `start` bytes into `device`.
+pub(crate) fn is_fat(device: &mut File, start: u64) -> io::Result<bool> {
+    let mut boot = [0u8; 512];
+    device.seek(SeekFrom::Start(start))?;
+    Ok(read_full(device, &mut boot)? == 512
+        && boot[510..512] == [0x55, 0xAA]
+        && layout(&boot).is_some())
+}
+
+/// Where a FAT boot sector keeps its label, and where (in bytes from the
+/// volume's start) and how long its root directory is; None when `boot` is
+/// not a FAT boot sector.
+fn layout(boot: &[u8; 512]) -> Option<(usize, u64, u64)> {
+    let sector_len = u16_at(boot, 11) as u64;
+    let cluster_sectors = boot[13] as u64;
+    let reserved = u16_at(boot, 14) as u64;
+    let fats = boot[16] as u64;
+    if ![512, 1024, 2048, 4096].contains(&sector_len)
+        || !cluster_sectors.is_power_of_two()
+        || reserved == 0
+        || !(1..=2).contains(&fats)
+    {
+        return None;
+    }
+    // FAT32 leaves the 16-bit FAT size zero and moves the extended fields.
+    let fat16_len = u16_at(boot, 22) as u64;
+    if fat16_len == 0 {
+        let fat_len = u32_at(boot, 36) as u64;
+        let root_cluster = u32_at(boot, 44) as u64;
+        if boot[66] != 0x29 || root_cluster < 2 {
+            return None;
+        }
+        let data = reserved + fats * fat_len;
+        let root = data + (root_cluster - 2) * cluster_sectors;
+        Some((71, root * sector_len, cluster_sectors * sector_len))
+    } else {
+        if boot[38] != 0x29 {
+            return None;
+        }
+        let root = reserved + fats * fat16_len;
+        Some((
+            43,
+            root * sector_len,
+            u16_at(boot, 17) as u64 * DIR_ENTRY_LEN as u64,
+        ))
+    }
+}
+
+/// Writes an empty FAT32 filesystem over the `len` bytes at `start` of
+/// `device`, with `label` or none. `hidden_sectors` is where the volume
+/// starts on its disk. Windows and most OS tools refuse FAT32 past 32 GB;
+/// the format itself goes up to 2 TB.
+pub(crate) fn format_fat32(
+    device: &mut File,
+    start: u64,
+    len: u64,
+    hidden_sectors: u64,
+    label: Option<&[u8; LABEL_MAX]>,
+    tx: &dyn ProgressSink,
+) -> io::Result<()> {
+    let sectors = len / SECTOR;
+    // The cluster sizes Windows picks for FAT32 volumes of each size.
+    let cluster_sectors: u64 = match len {
+        len if len <= 260 * 1024 * 1024 => 1,
+        len if len <= 8 << 30 => 8,
+        len if len <= 16 << 30 => 16,
+        len if len <= 32 << 30 => 32,
+        _ => 64,
+    };
+    // The FAT size from Microsoft's FAT specification; slightly generous.
+    let per_fat_sector = (256 * cluster_sectors + 2) / 2;
+    let fat_sectors = sectors
+        .saturating_sub(RESERVED_SECTORS)
+        .div_ceil(per_fat_sector);
+    let data_start = RESERVED_SECTORS + 2 * fat_sectors;
+    let clusters = sectors.saturating_sub(data_start) / cluster_sectors;
+    if clusters < FAT32_MIN_CLUSTERS {
+        return Err(io::Error::other("the device is too small for FAT32"));
+    }
+    // The sector count is 32-bit.
+    if clusters > FAT32_MAX_CLUSTERS || sectors > u32::MAX as u64 {
+        return Err(io::Error::other("the device is too large for FAT32"));
+    }
+
+    // Clear the reserved sectors, both FATs and the root directory.
+    let zero_len = (data_start + cluster_sectors) * SECTOR;
+    let zeros = vec![0u8; ZERO_BLOCK];
+    let mut meter = SpeedMeter::new();
+    device.seek(SeekFrom::Start(start))?;
+    let mut done = 0u64;
+    while done < zero_len {
+        let n = (zero_len - done).min(ZERO_BLOCK as u64) as usize;
+        device.write_all(&zeros[..n])?;
+        done += n as u64;
+        send(
+            tx,
+            UiProgress::WritingDetailed {
+                fraction: fraction(done, zero_len),
+                bytes_done: done,
+                total: zero_len,
+                bytes_per_sec: meter.update(done),
+            },
+        );
+    }
+
+    let volume_label = label.copied().unwrap_or(*b"NO NAME    ");
+    let volume_id = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as u32 ^ d.subsec_nanos())
+        .unwrap_or(0);
+    let mut boot = [0u8; 512];
+    boot[..3].copy_from_slice(&[0xEB, 0x58, 0x90]);
+    boot[3..11].copy_from_slice(b"MSWIN4.1");
+    boot[11..13].copy_from_slice(&(SECTOR as u16).to_le_bytes());
+    boot[13] = cluster_sectors as u8;
+    boot[14..16].copy_from_slice(&(RESERVED_SECTORS as u16).to_le_bytes());
+    boot[16] = 2;
+    // Fixed disk media, and the usual geometry, which LBA firmware ignores.
+    boot[21] = 0xF8;
+    boot[24..26].copy_from_slice(&63u16.to_le_bytes());
+    boot[26..28].copy_from_slice(&255u16.to_le_bytes());
+    boot[28..32].copy_from_slice(&(hidden_sectors as u32).to_le_bytes());
+    boot[32..36].copy_from_slice(&(sectors as u32).to_le_bytes());
+    boot[36..40].copy_from_slice(&(fat_sectors as u32).to_le_bytes());
+    // Root directory in cluster 2, FSInfo in sector 1, backups from sector 6.
+    boot[44..48].copy_from_slice(&2u32.to_le_bytes());
+    boot[48..50].copy_from_slice(&1u16.to_le_bytes());
+    boot[50..52].copy_from_slice(&(BACKUP_BOOT_SECTOR as u16).to_le_bytes());
+    boot[64] = 0x80;
+    boot[66] = 0x29;
+    boot[67..71].copy_from_slice(&volume_id.to_le_bytes());
+    boot[71..82].copy_from_slice(&volume_label);
+    boot[82..90].copy_from_slice(b"FAT32   ");
+    // Not bootable: hand over to the next boot device (INT 18h).
+    boot[90..92].copy_from_slice(&[0xCD, 0x18]);
+    boot[510..512].copy_from_slice(&[0x55, 0xAA]);
+
+    let mut fs_info = [0u8; 512];
+    fs_info[..4].copy_from_slice(&0x4161_5252u32.to_le_bytes());
+    fs_info[484..488].copy_from_slice(&0x6141_7272u32.to_le_bytes());
+    // Every cluster but the root directory's is free; the next free is 3.
+    fs_info[488..492].copy_from_slice(&((clusters - 1) as u32).to_le_bytes());
+    fs_info[492..496].copy_from_slice(&3u32.to_le_bytes());
+    fs_info[508..512].copy_from_slice(&0xAA55_0000u32.to_le_bytes());
+
+    for first in [0, BACKUP_BOOT_SECTOR] {
+        device.seek(SeekFrom::Start(start + first * SECTOR))?;
+        device.write_all(&boot)?;
+        device.write_all(&fs_info)?;
+    }
+    // Media byte, end-of-chain marker, and the root directory's one cluster.
+    let mut fat_head = [0u8; 12];
+    fat_head[..4].copy_from_slice(&0x0FFF_FFF8u32.to_le_bytes());
+    fat_head[4..8].copy_from_slice(&0x0FFF_FFFFu32.to_le_bytes());
+    fat_head[8..12].copy_from_slice(&0x0FFF_FFFFu32.to_le_bytes());
+    for fat in 0..2 {
+        device.seek(SeekFrom::Start(
+            start + (RESERVED_SECTORS + fat * fat_sectors) * SECTOR,
+        ))?;
+        device.write_all(&fat_head)?;
+    }
+    if let Some(label) = label {
+        let mut entry = [0u8; DIR_ENTRY_LEN];
+        entry[..LABEL_MAX].copy_from_slice(label);
+        entry[11] = ATTR_VOLUME_ID;
+        device.seek(SeekFrom::Start(start + data_start * SECTOR))?;
+        device.write_all(&entry)?;
+    }
+    device.sync_all()
+}
+
+fn u16_at(bytes: &[u8], at: usize) -> u16 {
+    u16::from_le_bytes(bytes[at..at + 2].try_into().unwrap())
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
@@ -0,0 +1,246 @@
+// rustburn-core/src/fetch.rs
+
+// Downloading an image straight from a URL. The transfer itself is left to
+// the system's curl, which handles TLS, proxies and redirects; this side
+// streams its output to disk, reports progress and resumes dropped
+// connections with HTTP Range requests.
+use crate::burn::{fraction, read_full};
+use crate::error::BurnError;
+use crate::progress::{log, send, warn, ProgressSink, SpeedMeter, UiProgress};
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+const BLOCK: usize = 1024 * 1024;
+/// How many times a dropped connection is resumed before giving up.
+const RETRIES: u32 = 5;
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+/// curl's exit code when the server ignored the Range header.
+const CURL_NO_RANGES: i32 = 33;
+/// curl exit codes for a connection that failed or broke off mid-transfer:
+/// couldn't connect, HTTP/2 error, partial file, timeout, empty reply, send
+/// and receive errors, HTTP/2 stream error.
+const CURL_TRANSIENT: [i32; 8] = [7, 16, 18, 28, 52, 55, 56, 92];
+
+/// How one curl run ended.
+enum Attempt {
+    Finished,
+    /// The connection dropped; what was received is kept.
+    Dropped(String),
+    /// The server can't resume; the file has to start over.
+    NoRanges,
+}
+
+/// Downloads `url` to `dest`, continuing a partial `dest` left by an earlier
+/// run. Ends with `Done` or `Error`; on error the partial file is kept so the
+/// next call resumes it.
+pub(crate) fn fetch_iso(url: String, dest: String, tx: impl ProgressSink) {
+    match run(&url, &dest, &tx) {
+        Ok(len) => {
+            log(
+                &tx,
+                format!("Downloaded {:.1} GB to {}", len as f64 / 1e9, dest),
+            );
+            send(&tx, UiProgress::Done);
+        }
+        Err(e) => {
+            if std::fs::metadata(&dest).is_ok_and(|meta| meta.len() == 0) {
+                let _ = std::fs::remove_file(&dest);
+            }
+            send(&tx, UiProgress::Error(e));
+        }
+    }
+}
+
+fn run(url: &str, dest: &str, tx: &dyn ProgressSink) -> Result<u64, BurnError> {
+    let lower = url.to_ascii_lowercase();
+    if !lower.starts_with("http://") && !lower.starts_with("https://") {
+        return Err(BurnError::Other(format!(
+            "Only http:// and https:// URLs can be downloaded, not {}",
+            url
+        )));
+    }
+    let total = content_length(url);
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dest)
+        .map_err(|e| BurnError::io("create", dest, e))?;
+    let mut done = file
+        .metadata()
+        .map_err(|e| BurnError::io("read", dest, e))?
+        .len();
+    match total {
+        Some(total) if done == total => {
+            log(tx, format!("{} is already fully downloaded", dest));
+            return Ok(done);
+        }
+        // Not a partial copy of this file; start over.
+        Some(total) if done > total => {
+            file.set_len(0)
+                .map_err(|e| BurnError::io("truncate", dest, e))?;
+            done = 0;
+        }
+        _ => {}
+    }
+    if done > 0 {
+        log(
+            tx,
+            format!("Resuming the download at {:.1} MB", done as f64 / 1e6),
+        );
+    } else {
+        log(tx, format!("Downloading {}", url));
+    }
+
+    let mut retries = 0;
+    loop {
+        match transfer(url, &mut file, dest, &mut done, total, tx)? {
+            Attempt::Finished => break,
+            Attempt::NoRanges if done > 0 => {
+                warn(
+                    tx,
+                    "The server can't resume downloads; starting again from the beginning",
+                );
+                file.set_len(0)
+                    .map_err(|e| BurnError::io("truncate", dest, e))?;
+                done = 0;
+            }
+            Attempt::NoRanges => {
+                return Err(BurnError::Other(
+                    "The server refused the download request".to_string(),
+                ))
+            }
+            Attempt::Dropped(reason) if retries < RETRIES => {
+                retries += 1;
+                warn(
+                    tx,
+                    format!(
+                        "The connection dropped ({}); resuming at {:.1} MB (attempt {} of {})",
+                        reason,
+                        done as f64 / 1e6,
+                        retries,
+                        RETRIES
+                    ),
+                );
+                thread::sleep(RETRY_DELAY);
+            }
+            Attempt::Dropped(reason) => {
+                return Err(BurnError::Other(format!(
+                    "Download failed after {} retries: {}",
+                    RETRIES, reason
+                )))
+            }
+        }
+    }
+    if let Some(total) = total.filter(|&total| total != done) {
+        return Err(BurnError::Other(format!(
+            "Download ended at {} of {} bytes",
+            done, total
+        )));
+    }
+    Ok(done)
+}
+
+/// Runs curl once from byte `done` on, appending what arrives to `file`.
+fn transfer(
+    url: &str,
+    file: &mut File,
+    dest: &str,
+    done: &mut u64,
+    total: Option<u64>,
+    tx: &dyn ProgressSink,
+) -> Result<Attempt, BurnError> {
+    let mut command = Command::new("curl");
+    command.args(["--fail", "--silent", "--show-error", "--location"]);
+    if *done > 0 {
+        command.args(["--continue-at", &done.to_string()]);
+    }
+    let mut child = command
+        .arg("--")
+        .arg(url)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| {
+            BurnError::Other(format!(
+                "Could not start curl to download {}: {}. Install curl or download the image \
+                 in a browser.",
+                url, e
+            ))
+        })?;
+    let mut stdout = child.stdout.take().expect("stdout is piped");
+    let resumed_at = *done;
+    let mut meter = SpeedMeter::new();
+    let mut buf = vec![0u8; BLOCK];
+    // A read error here is curl going away; its exit code says why.
+    while let Ok(n) = read_full(&mut stdout, &mut buf) {
+        if n == 0 {
+            break;
+        }
+        file.write_all(&buf[..n])
+            .map_err(|e| BurnError::io("write", dest, e))?;
+        *done += n as u64;
+        send(
+            tx,
+            UiProgress::WritingDetailed {
+                fraction: total.map_or(0.0, |total| fraction(*done, total)),
+                bytes_done: *done,
+                total: total.unwrap_or(0),
+                bytes_per_sec: meter.update(*done - resumed_at),
+            },
+        );
+    }
+    file.flush().map_err(|e| BurnError::io("write", dest, e))?;
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr);
+    }
+    let status = child
+        .wait()
+        .map_err(|e| BurnError::Other(format!("curl did not finish: {}", e)))?;
+    // "curl: (56) Recv failure: ..." -> "Recv failure: ..."
+    let stderr = stderr.trim();
+    let reason = stderr
+        .split_once(") ")
+        .map_or(stderr, |(_, reason)| reason)
+        .to_string();
+    match status.code() {
+        Some(0) => Ok(Attempt::Finished),
+        Some(CURL_NO_RANGES) => Ok(Attempt::NoRanges),
+        Some(code) if CURL_TRANSIENT.contains(&code) => Ok(Attempt::Dropped(reason)),
+        _ => Err(BurnError::Other(format!(
+            "Could not download {}: {}",
+            url,
+            if reason.is_empty() {
+                status.to_string()
+            } else {
+                reason
+            }
+        ))),
+    }
+}
+
+/// The file's size from a HEAD request, when the server says. The last
+/// Content-Length wins, since redirects print one header block each.
+fn content_length(url: &str) -> Option<u64> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--location", "--head", "--", url])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim()
+                .eq_ignore_ascii_case("content-length")
+                .then(|| value.trim().parse().ok())?
+        })
+        .next_back()
+        .filter(|&len| len > 0)
+}
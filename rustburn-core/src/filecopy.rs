@@ -0,0 +1,466 @@
+// rustburn-core/src/filecopy.rs
+
+// The ISO file-copy write mode. A Windows installer ISO has no partition
+// table, so a raw copy of it doesn't boot from USB; instead the device gets
+// a fresh FAT32 volume holding the ISO's files, which UEFI firmware boots
+// through the EFI loader among them.
+use crate::badblocks;
+use crate::burn::{fraction, read_full, wait_while_paused, BurnReport};
+use crate::eject::run_tool;
+use crate::error::BurnError;
+use crate::extras;
+use crate::fat;
+use crate::format;
+use crate::inspect;
+use crate::options::{BootType, BurnOptions, FsType};
+use crate::partition::ALIGN;
+use crate::progress::{log, send, warn, ProgressSink, SpeedMeter, UiProgress};
+use crate::source::{self, Compression};
+use crate::wim;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// The setup image, split into .swm parts when FAT32 can't hold it.
+const INSTALL_WIM: &str = "sources/install.wim";
+/// Where UEFI firmware looks for the loader of removable media.
+const EFI_BOOT_DIR: &str = "efi/boot";
+/// Bytes per read and write while copying and comparing files.
+const CHUNK: usize = 1024 * 1024;
+
+/// Refuses what file copy can't do, before anything is written.
+pub(crate) fn check(options: &BurnOptions, partition_target: bool) -> Result<(), BurnError> {
+    let refusal = if !cfg!(any(target_os = "linux", target_os = "macos")) {
+        "ISO file-copy mode is only supported on Linux and macOS"
+    } else if partition_target {
+        "ISO file-copy mode needs the whole device as the target"
+    } else if Compression::from_path(&options.iso_path).is_some() {
+        "ISO file-copy mode needs an uncompressed ISO"
+    } else if source::is_disk_image(&options.iso_path) {
+        "A disk image can't be written in ISO file-copy mode; use DD mode"
+    } else if options.boot_type == BootType::Legacy {
+        "A device written in ISO file-copy mode boots on UEFI only; use DD mode for legacy BIOS"
+    } else if options.preserve_partition.is_some() || options.persistence_mb.is_some() {
+        "ISO file-copy mode can't keep or add partitions; use DD mode"
+    } else if options.auto_eject && options.reverify_after_eject {
+        "The re-check after eject compares the device block for block; use DD mode"
+    } else {
+        return Ok(());
+    };
+    Err(BurnError::Other(refusal.to_string()))
+}
+
+/// Formats the device as one FAT32 volume and copies the ISO's files onto
+/// it, then `options.extra_files`. With `options.verify`, the volume is
+/// mounted afresh and every copied file compared with the ISO's.
+pub(crate) fn write(
+    options: &BurnOptions,
+    started: Instant,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+    pause: &AtomicBool,
+) -> Result<BurnReport, BurnError> {
+    let image_len = fs::metadata(&options.iso_path)
+        .map_err(|e| BurnError::io("read", &options.iso_path, e))?
+        .len();
+    let device_len = File::open(&options.device_path)
+        .and_then(|mut device| device.seek(SeekFrom::End(0)))
+        .map_err(|e| BurnError::io("read the size of", &options.device_path, e))?;
+    // The files take no more room than the image, and FAT32 little of its own.
+    if image_len + ALIGN > device_len * 99 / 100 {
+        return Err(BurnError::Other(format!(
+            "{} ({:.1} GB) is too small for the files of {} ({:.1} GB)",
+            options.device_path,
+            device_len as f64 / 1e9,
+            options.iso_path,
+            image_len as f64 / 1e9
+        )));
+    }
+    let label = volume_label(options);
+    if options.dry_run {
+        log(
+            tx,
+            format!(
+                "Dry run: all checks passed; would format {} as FAT32 and copy the files of \
+                 {} onto it{}",
+                options.device_path,
+                options.iso_path,
+                if options.verify {
+                    ", then verify them"
+                } else {
+                    ""
+                },
+            ),
+        );
+        return Ok(BurnReport {
+            bytes_written: 0,
+            duration: started.elapsed(),
+            verified: false,
+        });
+    }
+    if options.resume_from.is_some_and(|offset| offset > 0) {
+        log(tx, "Starting over: ISO file-copy mode can't resume a burn");
+    }
+    if options.bad_block_check {
+        badblocks::check(options, tx, cancel)?;
+    }
+
+    let image_dir = temp_mount_point("iso");
+    mount_image(&options.iso_path, &image_dir)?;
+    let result = format_and_copy(options, &label, &image_dir, tx, cancel, pause);
+    if let Err(e) = unmount_image(&image_dir) {
+        warn(
+            tx,
+            format!(
+                "WARNING: Could not unmount {} from {}: {}",
+                options.iso_path,
+                image_dir.display(),
+                e
+            ),
+        );
+    } else {
+        let _ = fs::remove_dir(&image_dir);
+    }
+    let bytes_written = result?;
+    Ok(BurnReport {
+        bytes_written,
+        duration: started.elapsed(),
+        verified: options.verify,
+    })
+}
+
+/// The part of `write` that needs the ISO mounted at `image_dir`; returns
+/// how many bytes of files were copied.
+fn format_and_copy(
+    options: &BurnOptions,
+    label: &str,
+    image_dir: &Path,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+    pause: &AtomicBool,
+) -> Result<u64, BurnError> {
+    let files = list_files(image_dir)
+        .map_err(|e| BurnError::io("read the files of", &options.iso_path, e))?;
+    for (path, len) in &files {
+        if *len > wim::FAT32_MAX_FILE && !is_install_wim(path) {
+            return Err(BurnError::Other(format!(
+                "{} in the image is {:.1} GB, more than FAT32 can hold; use DD mode",
+                path.display(),
+                *len as f64 / 1e9
+            )));
+        }
+    }
+    if !files.iter().any(|(path, _)| is_efi_loader(path)) {
+        return Err(BurnError::Other(format!(
+            "{} has no UEFI loader in {}, so a copy of its files would not boot; use DD mode",
+            options.iso_path, EFI_BOOT_DIR
+        )));
+    }
+
+    send(tx, UiProgress::StartingBurn);
+    log(tx, format!("Formatting {} as FAT32", options.device_path));
+    format::run(&options.device_path, FsType::Fat32, label, tx)?;
+    let volume_dir = temp_mount_point("volume");
+    mount_volume(options, &volume_dir)?;
+    log(
+        tx,
+        format!(
+            "Copying the files of {} to {}",
+            options.iso_path, options.device_path
+        ),
+    );
+    let copied = copy_files(&files, image_dir, &volume_dir, tx, cancel, pause)
+        .and_then(|copied| extras::copy_files(options, &volume_dir, tx).map(|()| copied));
+    // Unmounting flushes what the OS still caches.
+    log(tx, "Flushing buffers…");
+    let unmounted = unmount_volume(&volume_dir);
+    let copied = copied?;
+    unmounted?;
+    if options.boot_type.wants_legacy() {
+        warn(
+            tx,
+            "WARNING: ISO file-copy mode sets up no legacy BIOS boot; the device boots on UEFI \
+             only.",
+        );
+    }
+
+    if options.verify {
+        send(tx, UiProgress::StartingVerification);
+        badblocks::drop_cache(&options.device_path);
+        mount_volume(options, &volume_dir)?;
+        let compared = compare_files(&files, image_dir, &volume_dir, tx, cancel);
+        let unmounted = unmount_volume(&volume_dir);
+        compared?;
+        unmounted?;
+        log(tx, "Verification passed");
+    }
+    Ok(copied)
+}
+
+/// `options.volume_label`, else the ISO's own label where FAT allows it.
+fn volume_label(options: &BurnOptions) -> String {
+    if let Some(label) = &options.volume_label {
+        return label.clone();
+    }
+    let label: String = inspect::inspect_iso(&options.iso_path)
+        .volume_label
+        .trim()
+        .chars()
+        .take(fat::LABEL_MAX)
+        .collect();
+    match fat::check_label(&label) {
+        Ok(_) => label,
+        Err(_) => String::new(),
+    }
+}
+
+/// Every regular file below `root`, as paths relative to it with their
+/// sizes, in a stable order.
+fn list_files(root: &Path) -> io::Result<Vec<(PathBuf, u64)>> {
+    let mut files = Vec::new();
+    let mut dirs = vec![PathBuf::new()];
+    while let Some(dir) = dirs.pop() {
+        let mut entries = fs::read_dir(root.join(&dir))?.collect::<io::Result<Vec<_>>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+        for entry in entries {
+            let path = dir.join(entry.file_name());
+            let metadata = entry.metadata()?;
+            if metadata.is_dir() {
+                dirs.push(path);
+            } else if metadata.is_file() {
+                files.push((path, metadata.len()));
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// `path` with `/` separators and in lower case, since ISO and FAT names
+/// are matched without regard to case.
+fn normalized(path: &Path) -> String {
+    path.to_string_lossy()
+        .replace('\\', "/")
+        .to_ascii_lowercase()
+}
+
+fn is_install_wim(path: &Path) -> bool {
+    normalized(path) == INSTALL_WIM
+}
+
+/// Whether `path` is one of the `efi/boot/boot*.efi` loaders.
+fn is_efi_loader(path: &Path) -> bool {
+    let path = normalized(path);
+    path.strip_prefix(EFI_BOOT_DIR)
+        .and_then(|name| name.strip_prefix("/boot"))
+        .is_some_and(|name| name.ends_with(".efi") && !name.contains('/'))
+}
+
+/// Copies `files` from `image_dir` to `volume_dir`, splitting an
+/// install.wim too big for FAT32 into .swm parts. Returns the bytes copied.
+fn copy_files(
+    files: &[(PathBuf, u64)],
+    image_dir: &Path,
+    volume_dir: &Path,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+    pause: &AtomicBool,
+) -> Result<u64, BurnError> {
+    let total: u64 = files.iter().map(|(_, len)| len).sum();
+    let mut progress = Progress::new(total);
+    let mut buf = vec![0u8; CHUNK];
+    for (path, len) in files {
+        let source = image_dir.join(path);
+        let target = volume_dir.join(path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| BurnError::io("create", &parent.display().to_string(), e))?;
+        }
+        if *len > wim::FAT32_MAX_FILE {
+            log(
+                tx,
+                format!(
+                    "install.wim is {:.1} GB, over the FAT32 file size limit; splitting it",
+                    *len as f64 / 1e9
+                ),
+            );
+            let dir = target.parent().unwrap_or(volume_dir);
+            let parts = wim::split_wim(&source, dir, wim::SWM_PART_SIZE)
+                .map_err(|e| BurnError::io("split", &source.display().to_string(), e))?;
+            log(
+                tx,
+                format!(
+                    "Split install.wim into {} parts; Windows Setup reads install.swm the \
+                     same way",
+                    parts.len()
+                ),
+            );
+            progress.advance(*len, tx);
+            continue;
+        }
+        let mut input = File::open(&source)
+            .map_err(|e| BurnError::io("open", &path.display().to_string(), e))?;
+        let mut output = File::create(&target)
+            .map_err(|e| BurnError::io("create", &target.display().to_string(), e))?;
+        loop {
+            if pause.load(Ordering::SeqCst) {
+                wait_while_paused(pause, cancel, tx);
+            }
+            if cancel.load(Ordering::SeqCst) {
+                return Err(BurnError::Cancelled);
+            }
+            let n = input
+                .read(&mut buf)
+                .map_err(|e| BurnError::io("read", &path.display().to_string(), e))?;
+            if n == 0 {
+                break;
+            }
+            output
+                .write_all(&buf[..n])
+                .map_err(|e| BurnError::io("write", &target.display().to_string(), e))?;
+            progress.advance(n as u64, tx);
+        }
+    }
+    Ok(total)
+}
+
+/// Compares every file but a split install.wim with its copy on the volume.
+fn compare_files(
+    files: &[(PathBuf, u64)],
+    image_dir: &Path,
+    volume_dir: &Path,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+) -> Result<(), BurnError> {
+    let total: u64 = files
+        .iter()
+        .filter(|(_, len)| *len <= wim::FAT32_MAX_FILE)
+        .map(|(_, len)| len)
+        .sum();
+    let mut checked = 0u64;
+    let mut expected = vec![0u8; CHUNK];
+    let mut actual = vec![0u8; CHUNK];
+    for (path, len) in files.iter().filter(|(_, len)| *len <= wim::FAT32_MAX_FILE) {
+        let name = path.display().to_string();
+        let mut source =
+            File::open(image_dir.join(path)).map_err(|e| BurnError::io("open", &name, e))?;
+        let mut copy = File::open(volume_dir.join(path)).map_err(|e| {
+            BurnError::VerificationFailed(format!("{} is missing from the device: {}", name, e))
+        })?;
+        let mut at = 0u64;
+        while at < *len {
+            if cancel.load(Ordering::SeqCst) {
+                return Err(BurnError::Cancelled);
+            }
+            let n = read_full(&mut source, &mut expected)
+                .map_err(|e| BurnError::io("read", &name, e))?;
+            let m = read_full(&mut copy, &mut actual[..n])
+                .map_err(|e| BurnError::io("read the copy of", &name, e))?;
+            if n == 0 {
+                break;
+            }
+            if expected[..n] != actual[..m] {
+                return Err(BurnError::VerificationFailed(format!(
+                    "{} differs on the device near byte {}",
+                    name, at
+                )));
+            }
+            at += n as u64;
+            checked += n as u64;
+            send(tx, UiProgress::Verifying(fraction(checked, total)));
+        }
+    }
+    Ok(())
+}
+
+/// Byte count and speed of the copy, sent as `WritingDetailed`.
+struct Progress {
+    done: u64,
+    total: u64,
+    meter: SpeedMeter,
+}
+
+impl Progress {
+    fn new(total: u64) -> Self {
+        Progress {
+            done: 0,
+            total,
+            meter: SpeedMeter::new(),
+        }
+    }
+
+    fn advance(&mut self, bytes: u64, tx: &dyn ProgressSink) {
+        self.done += bytes;
+        send(
+            tx,
+            UiProgress::WritingDetailed {
+                fraction: fraction(self.done, self.total),
+                bytes_done: self.done,
+                total: self.total,
+                bytes_per_sec: self.meter.update(self.done),
+            },
+        );
+    }
+}
+
+fn temp_mount_point(what: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("rustburn-{}-{}", what, std::process::id()))
+}
+
+/// Mounts the volume `format::run` put on the device at `mount_point`.
+fn mount_volume(options: &BurnOptions, mount_point: &Path) -> Result<(), BurnError> {
+    fs::create_dir_all(mount_point)
+        .map_err(|e| BurnError::io("create", &mount_point.display().to_string(), e))?;
+    extras::mount(&options.device_path, 1, ALIGN, mount_point).inspect_err(|_| {
+        let _ = fs::remove_dir(mount_point);
+    })
+}
+
+fn unmount_volume(mount_point: &Path) -> Result<(), BurnError> {
+    extras::unmount(mount_point).map_err(|e| {
+        BurnError::Other(format!(
+            "Could not unmount {}: {}",
+            mount_point.display(),
+            e
+        ))
+    })?;
+    let _ = fs::remove_dir(mount_point);
+    Ok(())
+}
+
+/// Mounts the ISO at `iso_path` read-only on `mount_point`. Windows ISOs
+/// keep their files in UDF, which the OS picks over the ISO 9660 stub.
+fn mount_image(iso_path: &str, mount_point: &Path) -> Result<(), BurnError> {
+    fs::create_dir_all(mount_point)
+        .map_err(|e| BurnError::io("create", &mount_point.display().to_string(), e))?;
+    let target = mount_point.display().to_string();
+    let mounted = if cfg!(target_os = "macos") {
+        run_tool(
+            "hdiutil",
+            &[
+                "attach",
+                "-readonly",
+                "-nobrowse",
+                "-mountpoint",
+                &target,
+                iso_path,
+            ],
+        )
+    } else {
+        run_tool("mount", &["-o", "loop,ro", iso_path, &target])
+    };
+    mounted.map_err(|e| {
+        let _ = fs::remove_dir(mount_point);
+        BurnError::Other(format!("Could not mount {}: {}", iso_path, e))
+    })
+}
+
+fn unmount_image(mount_point: &Path) -> Result<(), String> {
+    let target = mount_point.display().to_string();
+    if cfg!(target_os = "macos") {
+        run_tool("hdiutil", &["detach", &target])
+    } else {
+        run_tool("umount", &[&target])
+    }
+}
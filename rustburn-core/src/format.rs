@@ -0,0 +1,285 @@
+// rustburn-core/src/format.rs
+
+// Reformatting a device to a single clean filesystem. FAT32 is written here,
+// so it works past the 32 GB that OS tools refuse; exFAT and NTFS come from
+// the platform's own formatters.
+use crate::device;
+use crate::eject::{self, run_tool};
+use crate::erase;
+use crate::error::BurnError;
+use crate::fat::{self, LABEL_MAX};
+use crate::options::FsType;
+use crate::partition::{self, ALIGN, SECTOR};
+use crate::progress::{log, send, ProgressSink, UiProgress};
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom};
+use std::path::Path;
+use std::process::Command;
+use std::thread;
+use std::time::Duration;
+
+/// Longest NTFS label, in characters.
+const NTFS_LABEL_MAX: usize = 32;
+/// How long the kernel gets to create the new partition's node.
+const PARTITION_NODE_WAIT: Duration = Duration::from_secs(5);
+
+/// Formats `device_path` as `fs` named `label` (none when empty), ending
+/// with `Done` or `Error`. A whole disk gets an MBR with one partition
+/// spanning it; a partition is formatted in place.
+pub(crate) fn format(device_path: String, fs: FsType, label: String, tx: impl ProgressSink) {
+    match run(&device_path, fs, label.trim(), &tx) {
+        Ok(()) => {
+            log(&tx, format!("Formatted {} as {:?}", device_path, fs));
+            send(&tx, UiProgress::Done);
+        }
+        Err(e) => send(&tx, UiProgress::Error(e)),
+    }
+}
+
+/// Does the formatting of `format` without reporting `Done`, for callers
+/// that go on using the device.
+pub(crate) fn run(
+    device_path: &str,
+    fs: FsType,
+    label: &str,
+    tx: &dyn ProgressSink,
+) -> Result<(), BurnError> {
+    check_label(fs, label)?;
+    if device::is_write_protected(device_path) {
+        return Err(BurnError::WriteProtected);
+    }
+    eject::unmount(device_path, tx).map_err(BurnError::Other)?;
+    let whole_disk = !device::is_partition(device_path);
+    match fs {
+        FsType::Fat32 => format_fat32(device_path, label, whole_disk, tx),
+        _ if cfg!(windows) => format_windows(device_path, fs, label, whole_disk),
+        _ if cfg!(target_os = "macos") => format_macos(device_path, fs, label, whole_disk),
+        _ if cfg!(target_os = "linux") => format_linux(device_path, fs, label, whole_disk, tx),
+        _ => Err(BurnError::Other(format!(
+            "Formatting as {:?} is not supported on this platform",
+            fs
+        ))),
+    }
+}
+
+/// Refuses a label `fs` can't store.
+fn check_label(fs: FsType, label: &str) -> Result<(), BurnError> {
+    let max = match fs {
+        FsType::Fat32 if !label.is_empty() => return fat::check_label(label).map(|_| ()),
+        FsType::Fat32 => return Ok(()),
+        // exFAT counts UTF-16 units.
+        FsType::ExFat => (label.encode_utf16().count(), LABEL_MAX),
+        FsType::Ntfs => (label.chars().count(), NTFS_LABEL_MAX),
+    };
+    match max {
+        (len, max) if len > max => Err(BurnError::Other(format!(
+            "The volume label '{}' must be at most {} characters for {:?}",
+            label, max, fs
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Writes the table, if any, and the FAT32 volume directly to the device.
+fn format_fat32(
+    device_path: &str,
+    label: &str,
+    whole_disk: bool,
+    tx: &dyn ProgressSink,
+) -> Result<(), BurnError> {
+    let label = match label {
+        "" => None,
+        label => Some(fat::check_label(label)?),
+    };
+    let mut device = open(device_path)?;
+    let size = device
+        .seek(SeekFrom::End(0))
+        .map_err(|e| BurnError::io("read the size of", device_path, e))?;
+    let (start, len, hidden_sectors) = if whole_disk {
+        let len = single_partition_len(size);
+        write_table(&mut device, device_path, len, FsType::Fat32)?;
+        (ALIGN, len, ALIGN / SECTOR)
+    } else {
+        (
+            0,
+            size,
+            device::partition_offset(device_path).unwrap_or(0) / SECTOR,
+        )
+    };
+    log(
+        tx,
+        format!("Writing a {:.1} GB FAT32 volume", len as f64 / 1e9),
+    );
+    fat::format_fat32(&mut device, start, len, hidden_sectors, label.as_ref(), tx)
+        .map_err(|e| BurnError::io("format", device_path, e))?;
+    drop(device);
+    reread_table(device_path);
+    Ok(())
+}
+
+/// Partitions the disk if needed, then runs mkfs.exfat or mkfs.ntfs on the
+/// partition.
+fn format_linux(
+    device_path: &str,
+    fs: FsType,
+    label: &str,
+    whole_disk: bool,
+    tx: &dyn ProgressSink,
+) -> Result<(), BurnError> {
+    let target = if whole_disk {
+        let mut device = open(device_path)?;
+        let size = device
+            .seek(SeekFrom::End(0))
+            .map_err(|e| BurnError::io("read the size of", device_path, e))?;
+        write_table(&mut device, device_path, single_partition_len(size), fs)?;
+        drop(device);
+        reread_table(device_path);
+        wait_for_partition(device_path)?
+    } else {
+        device_path.to_string()
+    };
+    let (program, mut args) = match fs {
+        FsType::ExFat => ("mkfs.exfat", vec![]),
+        // Quick: skip zeroing the whole partition.
+        _ => ("mkfs.ntfs", vec!["-Q"]),
+    };
+    if !label.is_empty() {
+        args.extend(["-L", label]);
+    }
+    args.push(&target);
+    log(tx, format!("Running {} on {}", program, target));
+    run_tool(program, &args).map_err(|e| BurnError::Other(format!("{} failed: {}", program, e)))
+}
+
+/// Lets diskutil partition and format the disk, or format the partition.
+fn format_macos(
+    device_path: &str,
+    fs: FsType,
+    label: &str,
+    whole_disk: bool,
+) -> Result<(), BurnError> {
+    if fs == FsType::Ntfs {
+        return Err(BurnError::Other(
+            "macOS cannot create NTFS volumes".to_string(),
+        ));
+    }
+    // diskutil insists on a name.
+    let name = if label.is_empty() { "UNTITLED" } else { label };
+    let args = if whole_disk {
+        vec!["eraseDisk", "ExFAT", name, "MBR", device_path]
+    } else {
+        vec!["eraseVolume", "ExFAT", name, device_path]
+    };
+    run_tool("diskutil", &args)
+        .map_err(|e| BurnError::Other(format!("diskutil {} failed: {}", args[0], e)))
+}
+
+/// Clears the disk and creates and formats one partition with PowerShell's
+/// Storage cmdlets, which take exFAT and NTFS at any size.
+fn format_windows(
+    device_path: &str,
+    fs: FsType,
+    label: &str,
+    whole_disk: bool,
+) -> Result<(), BurnError> {
+    let number = device_path
+        .trim_start_matches(r"\\.\PhysicalDrive")
+        .parse::<u32>()
+        .ok()
+        .filter(|_| whole_disk)
+        .ok_or_else(|| {
+            BurnError::Other(format!("Select the whole disk to format it as {:?}", fs))
+        })?;
+    let script = format!(
+        "$ErrorActionPreference = 'Stop'; \
+         if ((Get-Disk -Number {n}).PartitionStyle -ne 'RAW') {{ \
+         Clear-Disk -Number {n} -RemoveData -RemoveOEM -Confirm:$false }}; \
+         Initialize-Disk -Number {n} -PartitionStyle MBR; \
+         New-Partition -DiskNumber {n} -UseMaximumSize -AssignDriveLetter | \
+         Format-Volume -FileSystem {fs} -NewFileSystemLabel '{label}' -Confirm:$false",
+        n = number,
+        fs = if fs == FsType::ExFat { "exFAT" } else { "NTFS" },
+        label = label.replace('\'', "''"),
+    );
+    run_tool(
+        "powershell",
+        &["-NoProfile", "-NonInteractive", "-Command", &script],
+    )
+    .map_err(|e| BurnError::Other(format!("Formatting {} failed: {}", device_path, e)))
+}
+
+fn open(device_path: &str) -> Result<File, BurnError> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(device_path)
+        .map_err(|e| BurnError::io("open", device_path, e))
+}
+
+/// The partition from the first MB to the end of a `size` byte disk, as far
+/// as an MBR reaches.
+fn single_partition_len(size: u64) -> u64 {
+    (size.saturating_sub(ALIGN) / SECTOR).min(u32::MAX as u64) * SECTOR
+}
+
+/// Clears the old tables at both ends and writes an MBR holding one
+/// partition of `len` bytes at 1 MB.
+fn write_table(
+    device: &mut File,
+    device_path: &str,
+    len: u64,
+    fs: FsType,
+) -> Result<(), BurnError> {
+    if len == 0 {
+        return Err(BurnError::Other(format!(
+            "{} is too small to format",
+            device_path
+        )));
+    }
+    erase::wipe_tables(device, device_path)?;
+    partition::write_single_mbr(device, ALIGN, len, fs)
+        .map_err(|e| BurnError::io("write the partition table of", device_path, e))
+}
+
+/// Asks the OS to pick up the new table; a busy device picks it up on replug.
+fn reread_table(device_path: &str) {
+    if cfg!(target_os = "linux") {
+        let _ = Command::new("blockdev")
+            .args(["--rereadpt", device_path])
+            .output();
+    } else if cfg!(windows) {
+        if let Some(number) = device_path.strip_prefix(r"\\.\PhysicalDrive") {
+            let _ = Command::new("powershell")
+                .args([
+                    "-NoProfile",
+                    "-NonInteractive",
+                    "-Command",
+                    &format!("Update-Disk -Number {}", number),
+                ])
+                .output();
+        }
+    }
+}
+
+/// The node of the first partition on `device_path` (`sdb1`, `mmcblk0p1`),
+/// once the kernel has created it.
+fn wait_for_partition(device_path: &str) -> Result<String, BurnError> {
+    let separator = if device_path.ends_with(|c: char| c.is_ascii_digit()) {
+        "p"
+    } else {
+        ""
+    };
+    let node = format!("{}{}1", device_path, separator);
+    let mut waited = Duration::ZERO;
+    while !Path::new(&node).exists() {
+        if waited >= PARTITION_NODE_WAIT {
+            return Err(BurnError::Other(format!(
+                "{} did not appear after partitioning; replug the device and format {} instead",
+                node, node
+            )));
+        }
+        thread::sleep(Duration::from_millis(100));
+        waited += Duration::from_millis(100);
+    }
+    Ok(node)
+}
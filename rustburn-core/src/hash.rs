@@ -0,0 +1,276 @@
+// rustburn-core/src/hash.rs
+
+// Streaming SHA-256, SHA-1 and MD5, so images can be checked without pulling
+// in a crypto dependency. None of this is used for security, only integrity.
+use crate::burn::{fraction, read_full};
+use crate::error::BurnError;
+use crate::options::ChecksumAlgo;
+use crate::progress::{send, ProgressSink, UiProgress};
+use std::fs::File;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const BLOCK: usize = 1024 * 1024;
+
+/// An in-progress digest of one of the supported algorithms.
+pub(crate) struct Hasher {
+    algo: ChecksumAlgo,
+    state: [u32; 8],
+    /// Bytes waiting for a full 64-byte block.
+    pending: Vec<u8>,
+    len: u64,
+}
+
+impl Hasher {
+    pub(crate) fn new(algo: ChecksumAlgo) -> Self {
+        let state = match algo {
+            ChecksumAlgo::Sha256 => [
+                0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+                0x5be0cd19,
+            ],
+            ChecksumAlgo::Sha1 => [
+                0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0, 0, 0, 0,
+            ],
+            ChecksumAlgo::Md5 => [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0, 0, 0, 0],
+        };
+        Self {
+            algo,
+            state,
+            pending: Vec::with_capacity(64),
+            len: 0,
+        }
+    }
+
+    pub(crate) fn update(&mut self, mut data: &[u8]) {
+        self.len += data.len() as u64;
+        if !self.pending.is_empty() {
+            let take = (64 - self.pending.len()).min(data.len());
+            self.pending.extend_from_slice(&data[..take]);
+            data = &data[take..];
+            if self.pending.len() < 64 {
+                return;
+            }
+            let block: [u8; 64] = self.pending[..].try_into().unwrap();
+            self.compress(&block);
+            self.pending.clear();
+        }
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks {
+            self.compress(block.try_into().unwrap());
+        }
+        self.pending.extend_from_slice(blocks.remainder());
+    }
+
+    /// Pads, finishes and returns the digest as lowercase hex.
+    pub(crate) fn finish_hex(mut self) -> String {
+        let bit_len = self.len.wrapping_mul(8);
+        let mut tail = std::mem::take(&mut self.pending);
+        tail.push(0x80);
+        while tail.len() % 64 != 56 {
+            tail.push(0);
+        }
+        match self.algo {
+            ChecksumAlgo::Md5 => tail.extend(bit_len.to_le_bytes()),
+            _ => tail.extend(bit_len.to_be_bytes()),
+        }
+        for block in tail.chunks_exact(64) {
+            self.compress(block.try_into().unwrap());
+        }
+        let words = match self.algo {
+            ChecksumAlgo::Sha256 => 8,
+            ChecksumAlgo::Sha1 => 5,
+            ChecksumAlgo::Md5 => 4,
+        };
+        self.state[..words]
+            .iter()
+            .map(|w| match self.algo {
+                ChecksumAlgo::Md5 => format!("{:08x}", w.swap_bytes()),
+                _ => format!("{:08x}", w),
+            })
+            .collect()
+    }
+
+    fn compress(&mut self, block: &[u8; 64]) {
+        match self.algo {
+            ChecksumAlgo::Sha256 => sha256_block(&mut self.state, block),
+            ChecksumAlgo::Sha1 => sha1_block(&mut self.state, block),
+            ChecksumAlgo::Md5 => md5_block(&mut self.state, block),
+        }
+    }
+}
+
+/// Hashes the file at `path` with `algo` and sends its hex digest as
+/// `SourceHash`, ending with `Done` or `Error`.
+pub(crate) fn hash_file(path: String, algo: ChecksumAlgo, tx: impl ProgressSink) {
+    match digest_file(
+        &path,
+        algo,
+        BLOCK,
+        &tx,
+        &AtomicBool::new(false),
+        UiProgress::Writing,
+    ) {
+        Ok(digest) => {
+            send(&tx, UiProgress::SourceHash(digest));
+            send(&tx, UiProgress::Done);
+        }
+        Err(e) => send(&tx, UiProgress::Error(e)),
+    }
+}
+
+/// Streams the file at `path` through a `Hasher` in `block_size` reads and
+/// returns the hex digest. After each block, `progress` turns the fraction
+/// read into the update to send; `cancel` is checked between blocks.
+pub(crate) fn digest_file(
+    path: &str,
+    algo: ChecksumAlgo,
+    block_size: usize,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+    progress: impl Fn(f32) -> UiProgress,
+) -> Result<String, BurnError> {
+    let mut file = File::open(path).map_err(|e| BurnError::io("open", path, e))?;
+    let len = file
+        .metadata()
+        .map_err(|e| BurnError::io("read", path, e))?
+        .len();
+    let mut hasher = Hasher::new(algo);
+    let mut buf = vec![0; block_size.max(512)];
+    let mut hashed = 0u64;
+    loop {
+        if cancel.load(Ordering::SeqCst) {
+            return Err(BurnError::Cancelled);
+        }
+        let n = read_full(&mut file, &mut buf).map_err(|e| BurnError::io("read", path, e))?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        hashed += n as u64;
+        send(tx, progress(fraction(hashed, len)));
+    }
+    Ok(hasher.finish_hex())
+}
+
+const SHA256_K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+fn sha256_block(state: &mut [u32; 8], block: &[u8; 64]) {
+    let mut w = [0u32; 64];
+    for (i, word) in block.chunks_exact(4).enumerate() {
+        w[i] = u32::from_be_bytes(word.try_into().unwrap());
+    }
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for i in 0..64 {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let t1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(SHA256_K[i])
+            .wrapping_add(w[i]);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(maj);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(t1);
+        d = c;
+        c = b;
+        b = a;
+        a = t1.wrapping_add(t2);
+    }
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+fn sha1_block(state: &mut [u32; 8], block: &[u8; 64]) {
+    let mut w = [0u32; 80];
+    for (i, word) in block.chunks_exact(4).enumerate() {
+        w[i] = u32::from_be_bytes(word.try_into().unwrap());
+    }
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, ..] = *state;
+    for (i, &word) in w.iter().enumerate() {
+        let (f, k) = match i {
+            0..=19 => ((b & c) | (!b & d), 0x5a827999),
+            20..=39 => (b ^ c ^ d, 0x6ed9eba1),
+            40..=59 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(word);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+const MD5_K: [u32; 64] = [
+    0xd76aa478, 0xe8c7b756, 0x242070db, 0xc1bdceee, 0xf57c0faf, 0x4787c62a, 0xa8304613, 0xfd469501,
+    0x698098d8, 0x8b44f7af, 0xffff5bb1, 0x895cd7be, 0x6b901122, 0xfd987193, 0xa679438e, 0x49b40821,
+    0xf61e2562, 0xc040b340, 0x265e5a51, 0xe9b6c7aa, 0xd62f105d, 0x02441453, 0xd8a1e681, 0xe7d3fbc8,
+    0x21e1cde6, 0xc33707d6, 0xf4d50d87, 0x455a14ed, 0xa9e3e905, 0xfcefa3f8, 0x676f02d9, 0x8d2a4c8a,
+    0xfffa3942, 0x8771f681, 0x6d9d6122, 0xfde5380c, 0xa4beea44, 0x4bdecfa9, 0xf6bb4b60, 0xbebfbc70,
+    0x289b7ec6, 0xeaa127fa, 0xd4ef3085, 0x04881d05, 0xd9d4d039, 0xe6db99e5, 0x1fa27cf8, 0xc4ac5665,
+    0xf4292244, 0x432aff97, 0xab9423a7, 0xfc93a039, 0x655b59c3, 0x8f0ccc92, 0xffeff47d, 0x85845dd1,
+    0x6fa87e4f, 0xfe2ce6e0, 0xa3014314, 0x4e0811a1, 0xf7537e82, 0xbd3af235, 0x2ad7d2bb, 0xeb86d391,
+];
+
+const MD5_SHIFTS: [u32; 16] = [7, 12, 17, 22, 5, 9, 14, 20, 4, 11, 16, 23, 6, 10, 15, 21];
+
+fn md5_block(state: &mut [u32; 8], block: &[u8; 64]) {
+    let mut m = [0u32; 16];
+    for (i, word) in block.chunks_exact(4).enumerate() {
+        m[i] = u32::from_le_bytes(word.try_into().unwrap());
+    }
+    let [mut a, mut b, mut c, mut d, ..] = *state;
+    for i in 0..64 {
+        let (f, g) = match i / 16 {
+            0 => ((b & c) | (!b & d), i),
+            1 => ((d & b) | (!d & c), (5 * i + 1) % 16),
+            2 => (b ^ c ^ d, (3 * i + 5) % 16),
+            _ => (c ^ (b | !d), (7 * i) % 16),
+        };
+        let rotated = a
+            .wrapping_add(f)
+            .wrapping_add(MD5_K[i])
+            .wrapping_add(m[g])
+            .rotate_left(MD5_SHIFTS[(i / 16) * 4 + i % 4]);
+        a = d;
+        d = c;
+        c = b;
+        b = b.wrapping_add(rotated);
+    }
+    for (s, v) in state.iter_mut().zip([a, b, c, d]) {
+        *s = s.wrapping_add(v);
+    }
+}
@@ -0,0 +1,99 @@
+// rustburn-core/src/hook.rs
+
+// The user's command run after a successful burn, e.g. to play a sound or
+// send a notification. It runs with RustBurn's own privileges.
+use crate::options::BurnOptions;
+use crate::progress::{log, warn, ProgressSink};
+use std::io::Read;
+use std::process::{Command, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+/// How long the command may run before it is killed, so a hung one can't
+/// keep the burn from finishing.
+const TIMEOUT: Duration = Duration::from_secs(120);
+const POLL: Duration = Duration::from_millis(100);
+
+/// Runs `options.on_complete_command` through the platform's shell with
+/// RUSTBURN_DEVICE, RUSTBURN_ISO and RUSTBURN_RESULT (`verified`, `written`
+/// or `dry-run`) set, and logs its output. Its failure is only a warning.
+pub(crate) fn on_complete(options: &BurnOptions, result: &str, tx: &dyn ProgressSink) {
+    let command = match options.on_complete_command.as_deref().map(str::trim) {
+        Some(command) if !command.is_empty() => command,
+        _ => return,
+    };
+    log(tx, format!("Running the completion command: {}", command));
+    let mut shell = if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    };
+    let child = shell
+        .env("RUSTBURN_DEVICE", &options.device_path)
+        .env("RUSTBURN_ISO", &options.iso_path)
+        .env("RUSTBURN_RESULT", result)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            warn(
+                tx,
+                format!("WARNING: Could not run the completion command: {}", e),
+            );
+            return;
+        }
+    };
+    // Read both pipes while waiting, so a chatty command can't fill one and stall.
+    let stdout = collect(child.stdout.take());
+    let stderr = collect(child.stderr.take());
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break Some(status),
+            Ok(None) if started.elapsed() < TIMEOUT => thread::sleep(POLL),
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                break None;
+            }
+        }
+    };
+    for line in stdout.join().unwrap_or_default().lines() {
+        log(tx, format!("[command] {}", line));
+    }
+    for line in stderr.join().unwrap_or_default().lines() {
+        warn(tx, format!("[command] {}", line));
+    }
+    match status {
+        Some(status) if status.success() => {}
+        Some(status) => warn(
+            tx,
+            format!("WARNING: The completion command exited with {}", status),
+        ),
+        None => warn(
+            tx,
+            format!(
+                "WARNING: The completion command was stopped after {} seconds",
+                TIMEOUT.as_secs()
+            ),
+        ),
+    }
+}
+
+/// Reads `pipe` to its end on a thread of its own.
+fn collect(pipe: Option<impl Read + Send + 'static>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut text = String::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_string(&mut text);
+        }
+        text
+    })
+}
@@ -0,0 +1,310 @@
+// rustburn-core/src/inspect.rs
+
+// A quick look inside an image before it is written: the ISO 9660 volume
+// label, the El Torito boot entries, whether it carries a partition table for
+// USB boot, and which OS and CPU it is for from the names inside it.
+use crate::burn::read_full;
+use crate::options::WriteMode;
+use crate::partition::Table;
+use crate::source::Compression;
+use std::fs::File;
+use std::io::{self, Seek, SeekFrom};
+use std::path::Path;
+
+const SECTOR: u64 = 2048;
+/// Sector of the first volume descriptor, after the 32 KB system area.
+const FIRST_DESCRIPTOR: u64 = 16;
+/// Volume descriptors end at this sector at the latest.
+const LAST_DESCRIPTOR: u64 = 64;
+const DESCRIPTOR_BOOT_RECORD: u8 = 0;
+const DESCRIPTOR_PRIMARY: u8 = 1;
+const DESCRIPTOR_TERMINATOR: u8 = 255;
+/// El Torito platform IDs.
+const PLATFORM_X86: u8 = 0x00;
+const PLATFORM_EFI: u8 = 0xEF;
+/// El Torito section header IDs; 0x91 marks the last section.
+const SECTION_HEADER: u8 = 0x90;
+const SECTION_HEADER_LAST: u8 = 0x91;
+/// Root directory names only Windows setup media has.
+const WINDOWS_MARKERS: [&str; 3] = ["SOURCES", "BOOTMGR", "BOOTMGR.EFI"];
+/// Root directory names of the common Linux live and install media.
+const LINUX_MARKERS: [&str; 10] = [
+    "CASPER",
+    "LIVE",
+    "LIVEOS",
+    "ISOLINUX",
+    "SYSLINUX",
+    "ARCH",
+    "DISTS",
+    "POOL",
+    "IMAGES",
+    "BOOT.CATALOG",
+];
+
+/// Names of the UEFI removable-media loaders in /EFI/BOOT, by CPU.
+const EFI_LOADERS: [(&str, IsoArch); 3] = [
+    ("BOOTAA64.EFI", IsoArch::Arm64),
+    ("BOOTX64.EFI", IsoArch::X86_64),
+    ("BOOTIA32.EFI", IsoArch::X86),
+];
+/// How labels and file names spell each CPU, upper-cased. Windows labels
+/// read like `CCCOMA_A64FRE_EN-US_DV9`. 64-bit x86 is checked before x86.
+const ARCH_NAMES: [(&[&str], IsoArch); 3] = [
+    (&["AARCH64", "ARM64", "_A64FRE"], IsoArch::Arm64),
+    (&["X86_64", "X86-64", "AMD64", "X64"], IsoArch::X86_64),
+    (&["I386", "I686", "X86"], IsoArch::X86),
+];
+
+/// The CPU an image is built for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IsoArch {
+    X86,
+    X86_64,
+    Arm64,
+    #[default]
+    Unknown,
+}
+
+/// The operating system an image is from, as far as its layout tells.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OsFamily {
+    Windows,
+    Linux,
+    #[default]
+    Unknown,
+}
+
+/// What `RustBurn::inspect_iso` found in an image's headers.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IsoInfo {
+    /// The ISO 9660 volume label; empty when there is none.
+    pub volume_label: String,
+    pub os_family: OsFamily,
+    /// From the image's UEFI loaders, else its label or file name.
+    pub arch: IsoArch,
+    /// Whether El Torito lists a BIOS boot entry.
+    pub bios_bootable: bool,
+    /// Whether El Torito lists a UEFI boot entry, or the image brings an EFI
+    /// System Partition.
+    pub uefi_bootable: bool,
+    /// Whether the image carries a partition table, so it boots from USB and
+    /// not only from optical media.
+    pub is_hybrid: bool,
+    /// The write mode that suits the image: file copy for a Windows
+    /// installer that is not hybrid, a raw copy for everything else.
+    pub write_mode: WriteMode,
+}
+
+/// Reads what it can from the image at `path`. Compressed and unreadable
+/// images, and anything that is not ISO 9660, give a default `IsoInfo` with
+/// whatever was found.
+pub(crate) fn inspect_iso(path: &str) -> IsoInfo {
+    let mut info = IsoInfo::default();
+    if Compression::from_path(path).is_some() {
+        return info;
+    }
+    if let Ok(mut file) = File::open(path) {
+        info.is_hybrid = match Table::read(&mut file) {
+            Ok(Some(table)) => {
+                info.uefi_bootable = table.has_esp();
+                !table.partition_starts().is_empty()
+            }
+            _ => false,
+        };
+        let _ = read_iso9660(&mut file, &mut info);
+    }
+    if info.os_family == OsFamily::Windows && !info.is_hybrid {
+        info.write_mode = WriteMode::FileCopy;
+    }
+    if info.arch == IsoArch::Unknown {
+        let file_name = Path::new(path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        info.arch = arch_from_name(&info.volume_label)
+            .or_else(|| arch_from_name(&file_name))
+            .unwrap_or_default();
+    }
+    info
+}
+
+/// The CPU named in a label or file name, if any.
+fn arch_from_name(name: &str) -> Option<IsoArch> {
+    let name = name.to_uppercase();
+    ARCH_NAMES
+        .iter()
+        .find(|(spellings, _)| spellings.iter().any(|s| name.contains(s)))
+        .map(|&(_, arch)| arch)
+}
+
+/// Where the meaningful content of the plain image at `path` ends: the ISO
+/// 9660 volume size, or the end of its last partition if that is further.
+/// None when neither is known or the image is compressed.
+pub(crate) fn content_len(path: &str) -> Option<u64> {
+    if Compression::from_path(path).is_some() {
+        return None;
+    }
+    let mut file = File::open(path).ok()?;
+    let mut pvd = [0u8; SECTOR as usize];
+    let volume = match read_sector(&mut file, FIRST_DESCRIPTOR, &mut pvd) {
+        Ok(n) if n == pvd.len() && pvd[0] == DESCRIPTOR_PRIMARY && &pvd[1..6] == b"CD001" => {
+            let block_size = u16::from_le_bytes([pvd[128], pvd[129]]) as u64;
+            Some(u32_at(&pvd, 80) as u64 * block_size)
+        }
+        _ => None,
+    };
+    let partitions = Table::read(&mut file)
+        .ok()
+        .flatten()
+        .and_then(|table| table.used_length());
+    volume.max(partitions).filter(|&len| len > 0)
+}
+
+/// Walks the volume descriptors, filling in the label, OS family and El
+/// Torito boot entries.
+fn read_iso9660(file: &mut File, info: &mut IsoInfo) -> io::Result<()> {
+    let mut sector = [0u8; SECTOR as usize];
+    for index in FIRST_DESCRIPTOR..LAST_DESCRIPTOR {
+        if read_sector(file, index, &mut sector)? < 7 || &sector[1..6] != b"CD001" {
+            break;
+        }
+        match sector[0] {
+            DESCRIPTOR_PRIMARY => {
+                info.volume_label = String::from_utf8_lossy(&sector[40..72]).trim().to_string();
+                // The root directory's record: extent at 2, length at 10.
+                let root = &sector[156..190];
+                let entries = dir_entries(file, u32_at(root, 2) as u64, u32_at(root, 10) as u64)?;
+                let has = |markers: &[&str]| {
+                    entries
+                        .iter()
+                        .any(|entry| markers.contains(&entry.name.as_str()))
+                };
+                info.os_family = if has(&WINDOWS_MARKERS) {
+                    OsFamily::Windows
+                } else if has(&LINUX_MARKERS) {
+                    OsFamily::Linux
+                } else {
+                    OsFamily::Unknown
+                };
+                info.arch = efi_loader_arch(file, &entries)?;
+            }
+            DESCRIPTOR_BOOT_RECORD if sector[7..].starts_with(b"EL TORITO SPECIFICATION") => {
+                read_boot_catalog(file, u32_at(&sector, 71) as u64, info)?;
+            }
+            DESCRIPTOR_TERMINATOR => break,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Notes which platforms the boot catalog at `lba` has entries for.
+fn read_boot_catalog(file: &mut File, lba: u64, info: &mut IsoInfo) -> io::Result<()> {
+    let mut catalog = [0u8; SECTOR as usize];
+    read_sector(file, lba, &mut catalog)?;
+    // The validation entry: header ID 1, then the default entry's platform.
+    if catalog[0] != 1 || catalog[30..32] != [0x55, 0xAA] {
+        return Ok(());
+    }
+    let mut platform = catalog[1];
+    // The default entry, then any sections of further entries.
+    let mut remaining = 1u16;
+    for entry in catalog.chunks_exact(32).skip(1) {
+        if remaining > 0 {
+            remaining -= 1;
+            if entry[0] == 0x88 {
+                match platform {
+                    PLATFORM_X86 => info.bios_bootable = true,
+                    PLATFORM_EFI => info.uefi_bootable = true,
+                    _ => {}
+                }
+            }
+        } else if [SECTION_HEADER, SECTION_HEADER_LAST].contains(&entry[0]) {
+            platform = entry[1];
+            remaining = u16::from_le_bytes([entry[2], entry[3]]);
+            if entry[0] == SECTION_HEADER_LAST && remaining == 0 {
+                break;
+            }
+        } else {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// A directory record, as far as the lookups here need it.
+struct DirEntry {
+    /// Uppercased, without the ";1" version or a trailing dot.
+    name: String,
+    lba: u64,
+    len: u64,
+    is_dir: bool,
+}
+
+/// The CPU of the first loader in /EFI/BOOT, given the root directory.
+fn efi_loader_arch(file: &mut File, root: &[DirEntry]) -> io::Result<IsoArch> {
+    let subdir = |entries: &[DirEntry], name: &str| {
+        entries
+            .iter()
+            .find(|entry| entry.is_dir && entry.name == name)
+            .map(|entry| (entry.lba, entry.len))
+    };
+    let efi = match subdir(root, "EFI") {
+        Some((lba, len)) => dir_entries(file, lba, len)?,
+        None => return Ok(IsoArch::Unknown),
+    };
+    let dir = match subdir(&efi, "BOOT") {
+        Some((lba, len)) => dir_entries(file, lba, len)?,
+        None => return Ok(IsoArch::Unknown),
+    };
+    Ok(EFI_LOADERS
+        .iter()
+        .find(|(loader, _)| dir.iter().any(|entry| entry.name == *loader))
+        .map_or(IsoArch::Unknown, |&(_, arch)| arch))
+}
+
+/// The entries of the directory at `lba`, without "." and "..".
+fn dir_entries(file: &mut File, lba: u64, len: u64) -> io::Result<Vec<DirEntry>> {
+    // A few sectors hold any directory worth looking at.
+    let mut dir = vec![0u8; len.min(8 * SECTOR) as usize];
+    file.seek(SeekFrom::Start(lba * SECTOR))?;
+    let filled = read_full(file, &mut dir)?;
+    dir.truncate(filled);
+    let mut entries = Vec::new();
+    let mut at = 0;
+    while at + 33 < dir.len() {
+        let record_len = dir[at] as usize;
+        if record_len == 0 {
+            // Records don't cross sectors; the rest of this one is padding.
+            at = (at / SECTOR as usize + 1) * SECTOR as usize;
+            continue;
+        }
+        let name_len = dir[at + 32] as usize;
+        if let Some(name) = dir.get(at + 33..at + 33 + name_len) {
+            // Single bytes 0 and 1 are the "." and ".." entries.
+            if name_len > 1 || name[0] > 1 {
+                let name = String::from_utf8_lossy(name).to_uppercase();
+                let name = name.split(';').next().unwrap_or("").trim_end_matches('.');
+                let record = &dir[at..];
+                entries.push(DirEntry {
+                    name: name.to_string(),
+                    lba: u32_at(record, 2) as u64,
+                    len: u32_at(record, 10) as u64,
+                    // File flags at 25; bit 1 marks a directory.
+                    is_dir: record[25] & 2 != 0,
+                });
+            }
+        }
+        at += record_len;
+    }
+    Ok(entries)
+}
+
+fn read_sector(file: &mut File, index: u64, buf: &mut [u8]) -> io::Result<usize> {
+    file.seek(SeekFrom::Start(index * SECTOR))?;
+    read_full(file, buf)
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
@@ -0,0 +1,1001 @@
+// rustburn-core/src/iso_writer.rs
+
+// Builds ISO 9660 images with Joliet names and El Torito boot entries, from
+// Windows install media or any other folder.
+use crate::burn::fraction;
+use crate::error::BurnError;
+use crate::options::BootType;
+use crate::progress::{debug, log, send, warn, ProgressSink, SpeedMeter, UiProgress};
+use crate::space;
+use crate::wim;
+use std::collections::VecDeque;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const SECTOR: usize = 2048;
+/// Largest single extent; bigger files are split across several records.
+const MAX_EXTENT: u64 = 0xFFFF_F800;
+/// Sector of the first volume descriptor, after the 32 KB system area.
+const FIRST_DESCRIPTOR: u32 = 16;
+/// El Torito platform IDs.
+const PLATFORM_X86: u8 = 0x00;
+const PLATFORM_EFI: u8 = 0xEF;
+/// Windows' BIOS boot sector loader.
+const WIN_BIOS_BOOT: &str = "boot/etfsboot.com";
+/// Windows' UEFI boot floppy image.
+const WIN_EFI_BOOT: &str = "efi/microsoft/boot/efisys.bin";
+/// The setup image, split into .swm parts when FAT32 can't hold it.
+const WIN_INSTALL_WIM: &str = "sources/install.wim";
+/// BIOS loaders of common live media, in order of preference. All but
+/// Windows' need a boot info table patched in.
+const BIOS_LOADERS: [&str; 5] = [
+    "isolinux/isolinux.bin",
+    "boot/isolinux/isolinux.bin",
+    "syslinux/isolinux.bin",
+    "boot/grub/i386-pc/eltorito.img",
+    WIN_BIOS_BOOT,
+];
+/// El Torito EFI images (small FAT filesystems) of common live media.
+const EFI_IMAGES: [&str; 7] = [
+    "boot/grub/efi.img",
+    "images/efiboot.img",
+    "isolinux/efiboot.img",
+    "efi/boot/efiboot.img",
+    "efiboot.img",
+    "efi.img",
+    WIN_EFI_BOOT,
+];
+
+/// Builds a bootable ISO at `output` from the Windows setup folder `source`,
+/// splitting install.wim under `work_dir` if needed.
+pub(crate) fn create_win_iso(
+    source: String,
+    output: String,
+    work_dir: String,
+    tx: impl ProgressSink,
+) {
+    send(&tx, UiProgress::StartingCreateWinIso);
+    let source = Path::new(&source);
+    let work_dir = Path::new(&work_dir).join(format!("rustburn-swm-{}", std::process::id()));
+    let result = check_windows_media(source)
+        .and_then(|()| {
+            scan_tree(source).map_err(|e| format!("Could not read {}: {}", source.display(), e))
+        })
+        .and_then(|mut tree| {
+            split_large_wim(&mut tree, &work_dir, &tx)?;
+            let boot = [(WIN_BIOS_BOOT, PLATFORM_X86), (WIN_EFI_BOOT, PLATFORM_EFI)]
+                .iter()
+                .filter_map(|&(path, platform)| {
+                    tree.find_file(path).map(|file| BootImage {
+                        file,
+                        platform,
+                        info_table: false,
+                    })
+                })
+                .collect::<Vec<_>>();
+            if boot.is_empty() {
+                return Err(format!(
+                    "{} has neither {} nor {}; it is not a Windows setup folder",
+                    source.display(),
+                    WIN_BIOS_BOOT,
+                    WIN_EFI_BOOT
+                ));
+            }
+            let label = volume_label(source, "WINDOWS");
+            write_iso(tree, &boot, &label, Path::new(&output), &tx)
+                .map_err(|e| format!("Could not write {}: {}", output, e))
+        });
+    let _ = fs::remove_dir_all(&work_dir);
+    match result {
+        Ok(()) => {
+            log(&tx, format!("Created {}", output));
+            send(&tx, UiProgress::Done);
+        }
+        Err(e) => send(&tx, UiProgress::Error(BurnError::Other(e))),
+    }
+}
+
+/// Builds an ISO at `output` from any folder. `label` names the volume,
+/// falling back to the folder name; with `boot`, the folder's BIOS loader
+/// and EFI image for that boot type get El Torito entries. The image boots
+/// from optical media; it gets no MBR for booting from a USB stick.
+pub(crate) fn create_iso(
+    source: String,
+    output: String,
+    label: String,
+    boot: Option<BootType>,
+    tx: impl ProgressSink,
+) {
+    let source = Path::new(&source);
+    let result = scan_tree(source)
+        .map_err(|e| format!("Could not read {}: {}", source.display(), e))
+        .and_then(|tree| {
+            let boot = match boot {
+                Some(boot_type) => find_boot_images(&tree, boot_type, source, &tx)?,
+                None => Vec::new(),
+            };
+            let label = match label.trim() {
+                "" => volume_label(source, "CDROM"),
+                label => label.to_string(),
+            };
+            log(
+                &tx,
+                format!("Packing {} as volume {}", source.display(), label),
+            );
+            write_iso(tree, &boot, &label, Path::new(&output), &tx)
+                .map_err(|e| format!("Could not write {}: {}", output, e))
+        });
+    match result {
+        Ok(()) => {
+            log(&tx, format!("Created {}", output));
+            send(&tx, UiProgress::Done);
+        }
+        Err(e) => send(&tx, UiProgress::Error(BurnError::Other(e))),
+    }
+}
+
+/// The first of `BIOS_LOADERS` and of `EFI_IMAGES` in the tree, as far as
+/// `boot_type` wants them. Fails when nothing usable is there; a hybrid
+/// build missing one half only warns.
+fn find_boot_images(
+    tree: &Tree,
+    boot_type: BootType,
+    source: &Path,
+    tx: &dyn ProgressSink,
+) -> Result<Vec<BootImage>, String> {
+    let bios = BIOS_LOADERS.iter().find_map(|&path| {
+        tree.find_file(path).map(|file| BootImage {
+            file,
+            platform: PLATFORM_X86,
+            info_table: path != WIN_BIOS_BOOT,
+        })
+    });
+    let efi = EFI_IMAGES.iter().find_map(|&path| {
+        tree.find_file(path).map(|file| BootImage {
+            file,
+            platform: PLATFORM_EFI,
+            info_table: false,
+        })
+    });
+    let missing = |what: &str, paths: &[&str]| {
+        format!(
+            "{} has no {} (looked for {})",
+            source.display(),
+            what,
+            paths.join(", ")
+        )
+    };
+    let mut boot = Vec::new();
+    if boot_type.wants_legacy() {
+        match bios {
+            Some(image) => boot.push(image),
+            None if boot_type == BootType::Hybrid => warn(
+                tx,
+                format!(
+                    "WARNING: {}; the image won't boot on BIOS",
+                    missing("BIOS boot loader", &BIOS_LOADERS)
+                ),
+            ),
+            None => return Err(missing("BIOS boot loader", &BIOS_LOADERS)),
+        }
+    }
+    if boot_type.wants_uefi() {
+        match efi {
+            Some(image) => boot.push(image),
+            None if boot_type == BootType::Hybrid && !boot.is_empty() => warn(
+                tx,
+                format!(
+                    "WARNING: {}; the image won't boot on UEFI",
+                    missing("EFI boot image", &EFI_IMAGES)
+                ),
+            ),
+            None if boot.is_empty() => {
+                return Err(format!(
+                    "{}, nor a BIOS boot loader",
+                    missing("EFI boot image", &EFI_IMAGES)
+                ))
+            }
+            None => return Err(missing("EFI boot image", &EFI_IMAGES)),
+        }
+    }
+    for image in &boot {
+        let file = tree.file(image.file);
+        debug(
+            tx,
+            format!("Boot image {} ({} bytes)", file.path.display(), file.size),
+        );
+    }
+    Ok(boot)
+}
+
+/// Fails unless `source` has the layout of extracted Windows media:
+/// sources/install.wim or install.esd, boot/ and efi/. Names are matched
+/// without regard to case, as copies off a disc may be uppercase.
+fn check_windows_media(source: &Path) -> Result<(), String> {
+    let sources = find_entry(source, "sources");
+    let has_image = sources.as_deref().is_some_and(|sources| {
+        ["install.wim", "install.esd"]
+            .iter()
+            .any(|name| find_entry(sources, name).is_some_and(|path| path.is_file()))
+    });
+    let has_dirs = ["boot", "efi"]
+        .iter()
+        .all(|name| find_entry(source, name).is_some_and(|path| path.is_dir()));
+    if has_image && has_dirs {
+        Ok(())
+    } else {
+        Err(format!(
+            "Selected folder doesn't look like Windows installation media: {} needs \
+             sources/install.wim (or install.esd), boot/ and efi/",
+            source.display()
+        ))
+    }
+}
+
+/// The entry of `dir` named `name`, ignoring case.
+fn find_entry(dir: &Path, name: &str) -> Option<PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .find(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .eq_ignore_ascii_case(name)
+        })
+        .map(|entry| entry.path())
+}
+
+/// Replaces an install.wim too big for a FAT32 stick with install.swm parts
+/// written to `work_dir`, so the files can later be copied onto one.
+fn split_large_wim(tree: &mut Tree, work_dir: &Path, tx: &dyn ProgressSink) -> Result<(), String> {
+    let wim_ref = match tree.find_file(WIN_INSTALL_WIM) {
+        Some(file) if tree.file(file).size > wim::FAT32_MAX_FILE => file,
+        _ => return Ok(()),
+    };
+    let wim_path = tree.file(wim_ref).path.clone();
+    log(
+        tx,
+        format!(
+            "install.wim is {:.1} GB, over the FAT32 file size limit; splitting it",
+            tree.file(wim_ref).size as f64 / 1e9
+        ),
+    );
+    fs::create_dir_all(work_dir)
+        .map_err(|e| format!("Could not create {}: {}", work_dir.display(), e))?;
+    // The parts add up to the whole file; better to say so now than to fail
+    // gigabytes in.
+    let needed = tree.file(wim_ref).size;
+    if let Ok(free) = space::free_space(&work_dir.display().to_string()) {
+        if free < needed {
+            return Err(format!(
+                "Splitting install.wim needs {:.1} GB in {}, which has {:.1} GB free; pick \
+                 another temp directory",
+                needed as f64 / 1e9,
+                work_dir.display(),
+                free as f64 / 1e9
+            ));
+        }
+    }
+    let parts = wim::split_wim(&wim_path, work_dir, wim::SWM_PART_SIZE)
+        .map_err(|e| format!("Could not split {}: {}", wim_path.display(), e))?;
+    let dir = &mut tree.dirs[wim_ref.0];
+    dir.files.remove(wim_ref.1);
+    for path in &parts {
+        let size = fs::metadata(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?
+            .len();
+        dir.files.push(FileNode {
+            name: path.file_name().unwrap().to_string_lossy().into_owned(),
+            path: path.clone(),
+            size,
+            lba: 0,
+        });
+    }
+    log(
+        tx,
+        format!(
+            "Split install.wim into {} parts; Windows Setup reads install.swm the same way",
+            parts.len()
+        ),
+    );
+    Ok(())
+}
+
+/// A file to lay out in the image.
+struct FileNode {
+    name: String,
+    path: PathBuf,
+    size: u64,
+    /// First sector of its data, once laid out.
+    lba: u32,
+}
+
+/// A directory, kept in a flat list where children refer to it by index.
+struct DirNode {
+    name: String,
+    parent: usize,
+    subdirs: Vec<usize>,
+    files: Vec<FileNode>,
+    /// Where the ISO 9660 and Joliet directory extents ended up, and their sizes.
+    iso: (u32, u32),
+    joliet: (u32, u32),
+}
+
+struct Tree {
+    dirs: Vec<DirNode>,
+}
+
+/// Identifies a file in the tree: the directory index and the file index in it.
+#[derive(Clone, Copy)]
+struct FileRef(usize, usize);
+
+struct BootImage {
+    file: FileRef,
+    platform: u8,
+    /// Whether the loader expects the El Torito boot info table (where the
+    /// volume and the loader itself are) at byte 8, as isolinux and GRUB do.
+    info_table: bool,
+}
+
+/// Which of the two name spaces a directory extent or path table is for.
+#[derive(Clone, Copy, PartialEq)]
+enum Flavor {
+    Iso,
+    Joliet,
+}
+
+fn scan_tree(root: &Path) -> io::Result<Tree> {
+    let mut dirs = vec![DirNode::new(String::new(), 0)];
+    let mut queue = VecDeque::from([(root.to_path_buf(), 0)]);
+    while let Some((path, index)) = queue.pop_front() {
+        let mut entries = fs::read_dir(&path)?.collect::<io::Result<Vec<_>>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+        for entry in entries {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let meta = fs::metadata(entry.path())?;
+            if meta.is_dir() {
+                let child = dirs.len();
+                dirs.push(DirNode::new(name, index));
+                dirs[index].subdirs.push(child);
+                queue.push_back((entry.path(), child));
+            } else {
+                dirs[index].files.push(FileNode {
+                    name,
+                    path: entry.path(),
+                    size: meta.len(),
+                    lba: 0,
+                });
+            }
+        }
+    }
+    Ok(Tree { dirs })
+}
+
+impl DirNode {
+    fn new(name: String, parent: usize) -> Self {
+        Self {
+            name,
+            parent,
+            subdirs: Vec::new(),
+            files: Vec::new(),
+            iso: (0, 0),
+            joliet: (0, 0),
+        }
+    }
+}
+
+impl Tree {
+    /// Looks up a `/`-separated path case-insensitively.
+    fn find_file(&self, path: &str) -> Option<FileRef> {
+        let mut parts: Vec<&str> = path.split('/').collect();
+        let file = parts.pop()?;
+        let mut dir = 0;
+        for part in parts {
+            dir = *self.dirs[dir]
+                .subdirs
+                .iter()
+                .find(|&&d| self.dirs[d].name.eq_ignore_ascii_case(part))?;
+        }
+        let index = self.dirs[dir]
+            .files
+            .iter()
+            .position(|f| f.name.eq_ignore_ascii_case(file))?;
+        Some(FileRef(dir, index))
+    }
+
+    fn file(&self, file: FileRef) -> &FileNode {
+        &self.dirs[file.0].files[file.1]
+    }
+
+    /// Identifiers of every entry in directory `dir` for `flavor`, as
+    /// (identifier, entry), sorted the way the directory must list them.
+    fn entries(&self, dir: usize, flavor: Flavor) -> Vec<(Vec<u8>, Entry)> {
+        let node = &self.dirs[dir];
+        let mut taken = Vec::new();
+        let mut entries = Vec::new();
+        for &sub in &node.subdirs {
+            let id = identifier(&self.dirs[sub].name, true, flavor, &taken);
+            taken.push(id.clone());
+            entries.push((id, Entry::Dir(sub)));
+        }
+        for (index, file) in node.files.iter().enumerate() {
+            let id = identifier(&file.name, false, flavor, &taken);
+            taken.push(id.clone());
+            entries.push((id, Entry::File(index)));
+        }
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+
+    /// Directory indices in path table order: by depth, then parent, then name.
+    fn path_table_order(&self, flavor: Flavor) -> Vec<usize> {
+        let mut order = vec![0];
+        let mut next = 0;
+        while next < order.len() {
+            let dir = order[next];
+            for (_, entry) in self.entries(dir, flavor) {
+                if let Entry::Dir(sub) = entry {
+                    order.push(sub);
+                }
+            }
+            next += 1;
+        }
+        order
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Entry {
+    Dir(usize),
+    File(usize),
+}
+
+/// The on-disc identifier for `name`. ISO 9660 gets upper-case d-characters
+/// with a `;1` version on files; Joliet keeps the name in UCS-2.
+fn identifier(name: &str, is_dir: bool, flavor: Flavor, taken: &[Vec<u8>]) -> Vec<u8> {
+    match flavor {
+        Flavor::Joliet => {
+            let units: Vec<u16> = name.encode_utf16().take(64).collect();
+            units.iter().flat_map(|u| u.to_be_bytes()).collect()
+        }
+        Flavor::Iso => {
+            let clean = |s: &str, max: usize| -> String {
+                s.chars()
+                    .map(|c| match c.to_ascii_uppercase() {
+                        c @ ('A'..='Z' | '0'..='9' | '_') => c,
+                        _ => '_',
+                    })
+                    .take(max)
+                    .collect()
+            };
+            let (base, ext) = match name.rsplit_once('.') {
+                Some((base, ext)) if !is_dir && !base.is_empty() => (base, Some(ext)),
+                _ => (name, None),
+            };
+            let ext = ext.map(|e| clean(e, 8));
+            let room = 30 - ext.as_ref().map_or(0, |e| e.len() + 1);
+            let base = clean(base, room);
+            let build = |base: &str| -> Vec<u8> {
+                let mut id = base.to_string();
+                if let Some(ext) = &ext {
+                    id.push('.');
+                    id.push_str(ext);
+                }
+                if !is_dir {
+                    id.push_str(";1");
+                }
+                id.into_bytes()
+            };
+            let mut id = build(&base);
+            let mut n = 1;
+            while taken.contains(&id) {
+                let suffix = format!("~{}", n);
+                let keep = base.len().min(room - suffix.len());
+                id = build(&format!("{}{}", &base[..keep], suffix));
+                n += 1;
+            }
+            id
+        }
+    }
+}
+
+/// ISO 9660 volume identifier from the folder name, or `fallback` when the
+/// folder has none.
+fn volume_label(source: &Path, fallback: &str) -> String {
+    let name = source
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let label = d_characters(&name);
+    if label.is_empty() {
+        fallback.to_string()
+    } else {
+        label
+    }
+}
+
+/// `label` as an ISO 9660 volume identifier: at most 32 upper-case letters,
+/// digits and underscores. Joliet keeps the label as given.
+fn d_characters(label: &str) -> String {
+    label
+        .to_uppercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .take(32)
+        .collect()
+}
+
+/// Length of a directory record with a `name_len`-byte identifier.
+fn record_len(name_len: usize) -> usize {
+    33 + name_len + (name_len + 1) % 2
+}
+
+/// Number of records a file needs, one per extent.
+fn extent_count(size: u64) -> usize {
+    size.div_ceil(MAX_EXTENT).max(1) as usize
+}
+
+fn sectors(bytes: u64) -> u32 {
+    bytes.div_ceil(SECTOR as u64) as u32
+}
+
+/// Bytes a directory extent takes, given records may not cross sectors.
+fn directory_size(tree: &Tree, dir: usize, flavor: Flavor) -> u32 {
+    let mut lens = vec![record_len(1), record_len(1)];
+    for (id, entry) in tree.entries(dir, flavor) {
+        let count = match entry {
+            Entry::Dir(_) => 1,
+            Entry::File(index) => extent_count(tree.dirs[dir].files[index].size),
+        };
+        lens.extend(std::iter::repeat_n(record_len(id.len()), count));
+    }
+    let mut used = 0;
+    for len in lens {
+        if used % SECTOR + len > SECTOR {
+            used += SECTOR - used % SECTOR;
+        }
+        used += len;
+    }
+    (sectors(used as u64) as usize * SECTOR) as u32
+}
+
+fn path_table(tree: &Tree, flavor: Flavor, big_endian: bool) -> Vec<u8> {
+    let order = tree.path_table_order(flavor);
+    let mut number = vec![0u16; tree.dirs.len()];
+    for (i, &dir) in order.iter().enumerate() {
+        number[dir] = i as u16 + 1;
+    }
+    let mut table = Vec::new();
+    for &dir in &order {
+        let node = &tree.dirs[dir];
+        let id = if dir == 0 {
+            vec![0]
+        } else {
+            let parent_entries = tree.entries(node.parent, flavor);
+            parent_entries
+                .into_iter()
+                .find(|(_, e)| matches!(e, Entry::Dir(d) if *d == dir))
+                .map(|(id, _)| id)
+                .unwrap_or_default()
+        };
+        let extent = match flavor {
+            Flavor::Iso => node.iso.0,
+            Flavor::Joliet => node.joliet.0,
+        };
+        table.push(id.len() as u8);
+        table.push(0);
+        if big_endian {
+            table.extend(extent.to_be_bytes());
+            table.extend(number[node.parent].to_be_bytes());
+        } else {
+            table.extend(extent.to_le_bytes());
+            table.extend(number[node.parent].to_le_bytes());
+        }
+        table.extend(&id);
+        if id.len() % 2 == 1 {
+            table.push(0);
+        }
+    }
+    table
+}
+
+fn both_u16(value: u16) -> [u8; 4] {
+    let (le, be) = (value.to_le_bytes(), value.to_be_bytes());
+    [le[0], le[1], be[0], be[1]]
+}
+
+fn both_u32(value: u32) -> [u8; 8] {
+    let (le, be) = (value.to_le_bytes(), value.to_be_bytes());
+    [le[0], le[1], le[2], le[3], be[0], be[1], be[2], be[3]]
+}
+
+fn dir_record(id: &[u8], lba: u32, size: u32, flags: u8, date: &[u8; 7]) -> Vec<u8> {
+    let mut record = vec![0u8; record_len(id.len())];
+    record[0] = record.len() as u8;
+    record[2..10].copy_from_slice(&both_u32(lba));
+    record[10..18].copy_from_slice(&both_u32(size));
+    record[18..25].copy_from_slice(date);
+    record[25] = flags;
+    record[28..32].copy_from_slice(&both_u16(1));
+    record[32] = id.len() as u8;
+    record[33..33 + id.len()].copy_from_slice(id);
+    record
+}
+
+/// Writes the extent of directory `dir` into `image` at its assigned sector.
+fn write_directory(tree: &Tree, dir: usize, flavor: Flavor, date: &[u8; 7], image: &mut [u8]) {
+    let node = &tree.dirs[dir];
+    let place = |node: &DirNode| match flavor {
+        Flavor::Iso => node.iso,
+        Flavor::Joliet => node.joliet,
+    };
+    let (lba, size) = place(node);
+    let (parent_lba, parent_size) = place(&tree.dirs[node.parent]);
+    let mut records = vec![
+        dir_record(&[0], lba, size, 2, date),
+        dir_record(&[1], parent_lba, parent_size, 2, date),
+    ];
+    for (id, entry) in tree.entries(dir, flavor) {
+        match entry {
+            Entry::Dir(sub) => {
+                let (sub_lba, sub_size) = place(&tree.dirs[sub]);
+                records.push(dir_record(&id, sub_lba, sub_size, 2, date));
+            }
+            Entry::File(index) => {
+                let file = &node.files[index];
+                let count = extent_count(file.size);
+                for part in 0..count as u64 {
+                    let offset = part * MAX_EXTENT;
+                    let len = (file.size - offset.min(file.size)).min(MAX_EXTENT) as u32;
+                    let lba = if file.size == 0 {
+                        0
+                    } else {
+                        file.lba + (offset / SECTOR as u64) as u32
+                    };
+                    // Every extent but the last carries the multi-extent flag.
+                    let flags = if part + 1 < count as u64 { 0x80 } else { 0 };
+                    records.push(dir_record(&id, lba, len, flags, date));
+                }
+            }
+        }
+    }
+    let mut pos = lba as usize * SECTOR;
+    for record in records {
+        if pos % SECTOR + record.len() > SECTOR {
+            pos += SECTOR - pos % SECTOR;
+        }
+        image[pos..pos + record.len()].copy_from_slice(&record);
+        pos += record.len();
+    }
+}
+
+/// A primary (`Flavor::Iso`) or Joliet supplementary volume descriptor.
+fn volume_descriptor(
+    tree: &Tree,
+    flavor: Flavor,
+    label: &str,
+    total_sectors: u32,
+    path_tables: (u32, u32, u32),
+    dates: (&[u8; 7], &[u8; 17]),
+) -> Vec<u8> {
+    let text = |s: &str, len: usize| -> Vec<u8> {
+        match flavor {
+            Flavor::Iso => {
+                let mut bytes: Vec<u8> = s.bytes().take(len).collect();
+                bytes.resize(len, b' ');
+                bytes
+            }
+            Flavor::Joliet => {
+                let mut bytes: Vec<u8> = s
+                    .encode_utf16()
+                    .take(len / 2)
+                    .flat_map(|u| u.to_be_bytes())
+                    .collect();
+                while bytes.len() < len {
+                    bytes.extend([0, b' ']);
+                }
+                bytes.truncate(len);
+                bytes
+            }
+        }
+    };
+    let (table_size, l_table, m_table) = path_tables;
+    let root = match flavor {
+        Flavor::Iso => tree.dirs[0].iso,
+        Flavor::Joliet => tree.dirs[0].joliet,
+    };
+    let mut vd = vec![0u8; SECTOR];
+    vd[0] = if flavor == Flavor::Iso { 1 } else { 2 };
+    vd[1..6].copy_from_slice(b"CD001");
+    vd[6] = 1;
+    vd[8..40].copy_from_slice(&text("", 32));
+    vd[40..72].copy_from_slice(&text(label, 32));
+    vd[80..88].copy_from_slice(&both_u32(total_sectors));
+    if flavor == Flavor::Joliet {
+        // UCS-2 level 3.
+        vd[88..91].copy_from_slice(b"%/E");
+    }
+    vd[120..124].copy_from_slice(&both_u16(1));
+    vd[124..128].copy_from_slice(&both_u16(1));
+    vd[128..132].copy_from_slice(&both_u16(SECTOR as u16));
+    vd[132..140].copy_from_slice(&both_u32(table_size));
+    vd[140..144].copy_from_slice(&l_table.to_le_bytes());
+    vd[148..152].copy_from_slice(&m_table.to_be_bytes());
+    vd[156..190].copy_from_slice(&dir_record(&[0], root.0, root.1, 2, dates.0));
+    vd[190..318].copy_from_slice(&text("", 128));
+    vd[318..446].copy_from_slice(&text("", 128));
+    vd[446..574].copy_from_slice(&text("", 128));
+    vd[574..702].copy_from_slice(&text("RUSTBURN", 128));
+    vd[702..813].copy_from_slice(&text("", 111));
+    vd[813..830].copy_from_slice(dates.1);
+    vd[830..847].copy_from_slice(dates.1);
+    vd[847..863].copy_from_slice(b"0000000000000000");
+    vd[864..880].copy_from_slice(b"0000000000000000");
+    vd[881] = 1;
+    vd
+}
+
+/// The El Torito boot catalog: a validation entry, the default entry for the
+/// first image and a section for each further platform.
+fn boot_catalog(tree: &Tree, boot: &[BootImage]) -> Vec<u8> {
+    let mut catalog = vec![0u8; SECTOR];
+    catalog[0] = 1;
+    catalog[1] = boot[0].platform;
+    catalog[30] = 0x55;
+    catalog[31] = 0xAA;
+    let sum = catalog[..32].chunks(2).fold(0u16, |sum, w| {
+        sum.wrapping_add(u16::from_le_bytes([w[0], w[1]]))
+    });
+    catalog[28..30].copy_from_slice(&0u16.wrapping_sub(sum).to_le_bytes());
+
+    let entry = |image: &BootImage| -> [u8; 32] {
+        let file = tree.file(image.file);
+        let mut entry = [0u8; 32];
+        entry[0] = 0x88;
+        let count = file.size.div_ceil(512).min(u16::MAX as u64) as u16;
+        entry[6..8].copy_from_slice(&count.to_le_bytes());
+        entry[8..12].copy_from_slice(&file.lba.to_le_bytes());
+        entry
+    };
+    catalog[32..64].copy_from_slice(&entry(&boot[0]));
+    let mut pos = 64;
+    for (i, image) in boot.iter().enumerate().skip(1) {
+        catalog[pos] = if i + 1 == boot.len() { 0x91 } else { 0x90 };
+        catalog[pos + 1] = image.platform;
+        catalog[pos + 2..pos + 4].copy_from_slice(&1u16.to_le_bytes());
+        catalog[pos + 32..pos + 64].copy_from_slice(&entry(image));
+        pos += 64;
+    }
+    catalog
+}
+
+/// Lays the tree out, writes every descriptor and table, then streams the file data.
+fn write_iso(
+    mut tree: Tree,
+    boot: &[BootImage],
+    label: &str,
+    output: &Path,
+    tx: &dyn ProgressSink,
+) -> io::Result<()> {
+    let dates = now_dates();
+    // Primary, then the El Torito boot record if booting, Joliet and the terminator.
+    let has_boot = !boot.is_empty();
+    let joliet_lba = FIRST_DESCRIPTOR + 1 + has_boot as u32;
+    let terminator_lba = joliet_lba + 1;
+    let catalog_lba = terminator_lba + 1;
+    let mut next = catalog_lba + has_boot as u32;
+
+    // Path table sizes don't depend on where things land, so size them first.
+    let iso_table_len = path_table(&tree, Flavor::Iso, false).len() as u32;
+    let joliet_table_len = path_table(&tree, Flavor::Joliet, false).len() as u32;
+    let mut tables = [0u32; 4];
+    for (i, len) in [
+        iso_table_len,
+        iso_table_len,
+        joliet_table_len,
+        joliet_table_len,
+    ]
+    .iter()
+    .enumerate()
+    {
+        tables[i] = next;
+        next += sectors(*len as u64);
+    }
+    for flavor in [Flavor::Iso, Flavor::Joliet] {
+        for dir in tree.path_table_order(flavor) {
+            let size = directory_size(&tree, dir, flavor);
+            let place = (next, size);
+            match flavor {
+                Flavor::Iso => tree.dirs[dir].iso = place,
+                Flavor::Joliet => tree.dirs[dir].joliet = place,
+            }
+            next += size / SECTOR as u32;
+        }
+    }
+    let metadata_sectors = next;
+    let mut data_bytes = 0u64;
+    for dir in &mut tree.dirs {
+        for file in &mut dir.files {
+            if file.size > 0 {
+                file.lba = next;
+                next = next
+                    .checked_add(sectors(file.size))
+                    .ok_or_else(|| io::Error::other("the image would exceed 8 TB"))?;
+                data_bytes += file.size;
+            }
+        }
+    }
+    let total_sectors = next;
+
+    let mut head = vec![0u8; metadata_sectors as usize * SECTOR];
+    let at = |lba: u32| lba as usize * SECTOR;
+    let pvd = volume_descriptor(
+        &tree,
+        Flavor::Iso,
+        &d_characters(label),
+        total_sectors,
+        (iso_table_len, tables[0], tables[1]),
+        (&dates.0, &dates.1),
+    );
+    head[at(FIRST_DESCRIPTOR)..at(FIRST_DESCRIPTOR + 1)].copy_from_slice(&pvd);
+    if has_boot {
+        let boot_record = at(FIRST_DESCRIPTOR + 1);
+        head[boot_record] = 0;
+        head[boot_record + 1..boot_record + 6].copy_from_slice(b"CD001");
+        head[boot_record + 6] = 1;
+        head[boot_record + 7..boot_record + 30].copy_from_slice(b"EL TORITO SPECIFICATION");
+        head[boot_record + 71..boot_record + 75].copy_from_slice(&catalog_lba.to_le_bytes());
+        head[at(catalog_lba)..at(catalog_lba + 1)].copy_from_slice(&boot_catalog(&tree, boot));
+    }
+    let svd = volume_descriptor(
+        &tree,
+        Flavor::Joliet,
+        label,
+        total_sectors,
+        (joliet_table_len, tables[2], tables[3]),
+        (&dates.0, &dates.1),
+    );
+    head[at(joliet_lba)..at(joliet_lba + 1)].copy_from_slice(&svd);
+    let terminator = at(terminator_lba);
+    head[terminator] = 255;
+    head[terminator + 1..terminator + 6].copy_from_slice(b"CD001");
+    head[terminator + 6] = 1;
+    let table_flavors = [
+        (Flavor::Iso, false),
+        (Flavor::Iso, true),
+        (Flavor::Joliet, false),
+        (Flavor::Joliet, true),
+    ];
+    for (i, (flavor, big_endian)) in table_flavors.into_iter().enumerate() {
+        let table = path_table(&tree, flavor, big_endian);
+        head[at(tables[i])..at(tables[i]) + table.len()].copy_from_slice(&table);
+    }
+    for flavor in [Flavor::Iso, Flavor::Joliet] {
+        for dir in 0..tree.dirs.len() {
+            write_directory(&tree, dir, flavor, &dates.0, &mut head);
+        }
+    }
+
+    let mut out = BufWriter::new(File::create(output)?);
+    out.write_all(&head)?;
+    let mut copied = 0u64;
+    let mut meter = SpeedMeter::new();
+    let mut buf = vec![0u8; 1024 * 1024];
+    let patched: Vec<FileRef> = boot
+        .iter()
+        .filter(|image| image.info_table)
+        .map(|image| image.file)
+        .collect();
+    for (dir_index, dir) in tree.dirs.iter().enumerate() {
+        for (file_index, file) in dir.files.iter().enumerate() {
+            if file.size == 0 {
+                continue;
+            }
+            debug(tx, format!("Adding {}", file.path.display()));
+            let is_patched = patched
+                .iter()
+                .any(|r| r.0 == dir_index && r.1 == file_index);
+            if is_patched {
+                // Loaders are small; patch a copy in memory.
+                let mut data = fs::read(&file.path)?;
+                data.resize(file.size as usize, 0);
+                patch_boot_info_table(&mut data, file.lba);
+                data.resize(sectors(file.size) as usize * SECTOR, 0);
+                out.write_all(&data)?;
+                copied += file.size;
+                continue;
+            }
+            let mut input = File::open(&file.path)?.take(file.size);
+            let mut left = file.size;
+            while left > 0 {
+                let n = input.read(&mut buf)?;
+                if n == 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        format!("{} shrank while being copied", file.path.display()),
+                    ));
+                }
+                out.write_all(&buf[..n])?;
+                left -= n as u64;
+                copied += n as u64;
+                send(
+                    tx,
+                    UiProgress::WritingDetailed {
+                        fraction: fraction(copied, data_bytes),
+                        bytes_done: copied,
+                        total: data_bytes,
+                        bytes_per_sec: meter.update(copied),
+                    },
+                );
+            }
+            let pad = (SECTOR - (file.size % SECTOR as u64) as usize) % SECTOR;
+            out.write_all(&vec![0u8; pad])?;
+        }
+    }
+    out.flush()?;
+    out.into_inner().map_err(|e| e.into_error())?.sync_all()
+}
+
+/// Fills in the 56-byte El Torito boot info table at offset 8 of a loader
+/// that sits at sector `lba`: where the primary volume descriptor and the
+/// loader are, its length, and a checksum of everything after the table.
+fn patch_boot_info_table(data: &mut [u8], lba: u32) {
+    if data.len() < 64 {
+        return;
+    }
+    let checksum = data[64..].chunks(4).fold(0u32, |sum, word| {
+        let mut bytes = [0u8; 4];
+        bytes[..word.len()].copy_from_slice(word);
+        sum.wrapping_add(u32::from_le_bytes(bytes))
+    });
+    let len = data.len() as u32;
+    data[8..12].copy_from_slice(&FIRST_DESCRIPTOR.to_le_bytes());
+    data[12..16].copy_from_slice(&lba.to_le_bytes());
+    data[16..20].copy_from_slice(&len.to_le_bytes());
+    data[20..24].copy_from_slice(&checksum.to_le_bytes());
+    data[24..64].fill(0);
+}
+
+/// The current UTC time as a 7-byte directory record date and a 17-byte
+/// volume descriptor date.
+fn now_dates() -> ([u8; 7], [u8; 17]) {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    let (hour, minute, second) = ((secs / 3600) % 24, (secs / 60) % 60, secs % 60);
+    let record = [
+        (year - 1900) as u8,
+        month as u8,
+        day as u8,
+        hour as u8,
+        minute as u8,
+        second as u8,
+        0,
+    ];
+    let mut long = [0u8; 17];
+    let text = format!(
+        "{:04}{:02}{:02}{:02}{:02}{:02}00",
+        year, month, day, hour, minute, second
+    );
+    long[..16].copy_from_slice(text.as_bytes());
+    (record, long)
+}
+
+/// Converts days since 1970-01-01 to a (year, month, day) date.
+pub(crate) fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
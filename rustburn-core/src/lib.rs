@@ -0,0 +1,212 @@
+// rustburn-core/src/lib.rs
+
+// The engine behind the RustBurn front ends: device discovery, raw image writes,
+// read-back verification and Windows install media. Everything reports through
+// a `UiProgress` channel so a GUI or a terminal can draw it.
+mod badblocks;
+mod benchmark;
+mod bootable;
+mod burn;
+mod checkpoint;
+mod clone;
+mod compare;
+mod device;
+mod direct;
+mod eject;
+mod erase;
+mod error;
+mod extras;
+mod fat;
+mod fetch;
+mod filecopy;
+mod format;
+mod hash;
+mod hook;
+mod inspect;
+mod iso_writer;
+mod multi;
+mod options;
+mod partition;
+mod persistence;
+mod preserve;
+mod progress;
+mod recheck;
+mod sidecar;
+mod source;
+mod space;
+mod vm;
+mod watch;
+mod wim;
+
+pub use burn::BurnReport;
+pub use device::UsbDevice;
+pub use error::BurnError;
+pub use inspect::{IsoArch, IsoInfo, OsFamily};
+pub use options::{
+    BootType, BurnOptions, ChecksumAlgo, EraseMode, FsType, PartitionScheme, VerifyMode, WriteMode,
+    AUTO_BLOCK_SIZE,
+};
+pub use progress::{LogLevel, NoopSink, ProgressSink, UiProgress};
+pub use watch::DeviceEvent;
+
+use std::sync::atomic::AtomicBool;
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+/// Entry point for every operation; all methods are blocking and meant to run
+/// on a worker thread.
+pub struct RustBurn;
+
+impl RustBurn {
+    /// Lists removable USB disks that can be written.
+    pub fn scan_devices() -> Result<Vec<UsbDevice>, BurnError> {
+        device::scan_devices()
+    }
+
+    /// Like `scan_devices`, but waits at most `timeout` for each disk to
+    /// answer. One that doesn't is still listed, with
+    /// `UsbDevice::unresponsive` set, and its reader thread is left behind.
+    pub fn scan_devices_with_timeout(timeout: Duration) -> Result<Vec<UsbDevice>, BurnError> {
+        device::scan_devices_with_timeout(timeout)
+    }
+
+    /// Makes the next scan query the disk at `device_path` (or the disk
+    /// holding that partition) in full instead of reusing what earlier scans
+    /// found. Call it when the device is unplugged.
+    pub fn forget_device(device_path: &str) {
+        device::forget_device(device_path)
+    }
+
+    /// Watches for disks being plugged in or out and sends each change on
+    /// `tx` from a background thread, until the receiver is dropped.
+    pub fn watch_devices(tx: mpsc::Sender<DeviceEvent>) {
+        watch::watch_devices(tx)
+    }
+
+    /// Writes `options.iso_path` to `options.device_path`, then verifies and
+    /// sets up booting as requested. Setting `cancel` stops the write after
+    /// the current block and returns `BurnError::Cancelled`. While `pause` is
+    /// set the writer waits between blocks, keeping the device open. The
+    /// outcome is also sent on `tx` as `Done` or `Error`.
+    pub fn burn_iso(
+        options: BurnOptions,
+        tx: impl ProgressSink,
+        cancel: Arc<AtomicBool>,
+        pause: Arc<AtomicBool>,
+    ) -> Result<BurnReport, BurnError> {
+        burn::burn_iso(options, tx, cancel, pause)
+    }
+
+    /// Burns the image in `options` to every device in `devices`, a few at a
+    /// time, sending each update tagged with its device path. Each device
+    /// ends with its own `Done` or `Error`; the results come back in the
+    /// order of `devices`.
+    pub fn burn_iso_multi(
+        options: BurnOptions,
+        devices: Vec<String>,
+        tx: mpsc::Sender<(String, UiProgress)>,
+    ) -> Vec<Result<BurnReport, BurnError>> {
+        multi::burn_iso_multi(options, devices, tx)
+    }
+
+    /// Reads the volume label, boot entries and OS family from the image at
+    /// `path`. Only the headers are read, so this returns quickly.
+    pub fn inspect_iso(path: &str) -> IsoInfo {
+        inspect::inspect_iso(path)
+    }
+
+    /// Where a burn with `BurnOptions::write_sidecar` records the image at
+    /// `iso_path`: `<iso_path>.burned.sha256`.
+    pub fn sidecar_path(iso_path: &str) -> String {
+        sidecar::path(iso_path)
+    }
+
+    /// Bytes free for the current user on the filesystem holding `dir`.
+    pub fn free_space(dir: &str) -> Result<u64, String> {
+        space::free_space(dir)
+    }
+
+    /// Where an interrupted burn of the image at `iso_path` to `device` got
+    /// to, if it left a checkpoint. Pass it as `BurnOptions::resume_from`.
+    pub fn find_checkpoint(iso_path: &str, device: &str) -> Option<u64> {
+        checkpoint::find(iso_path, device)
+    }
+
+    /// Overwrites a few hundred MB at the start of `device` and logs its
+    /// sustained write and read speeds. Ends with `Done` or `Error`.
+    pub fn benchmark(device: String, tx: impl ProgressSink) {
+        benchmark::benchmark(device, tx)
+    }
+
+    /// Hashes the file at `path` with `algo`, reporting `Writing` progress as
+    /// it is read. The hex digest arrives as `SourceHash`, then `Done`.
+    pub fn hash_file(path: String, algo: ChecksumAlgo, tx: impl ProgressSink) {
+        hash::hash_file(path, algo, tx)
+    }
+
+    /// Reads `device` into the image file `output`, gzipped when the name ends
+    /// in `.gz`. Ends with `Done` or `Error`.
+    pub fn clone_to_image(device: String, output: String, tx: impl ProgressSink) {
+        clone::clone_to_image(device, output, tx)
+    }
+
+    /// Checks, without writing, whether `device` still holds the image at
+    /// `iso_path`: both are hashed over the image's length and the result is
+    /// logged. Ends with `Done` on a match and `Error` otherwise.
+    pub fn compare(device: String, iso_path: String, tx: impl ProgressSink) {
+        compare::compare(device, iso_path, tx)
+    }
+
+    /// Flushes, unmounts and powers down `device` so it can be unplugged.
+    /// Ends with `Done`, or `Error` when the device is busy.
+    pub fn eject(device: String, tx: impl ProgressSink) {
+        eject::eject(device, tx)
+    }
+
+    /// Reformats `device` as one `fs` volume named `label` (none when
+    /// empty): a whole disk gets an MBR with a single partition, a partition
+    /// is formatted in place. FAT32 works past 32 GB. Ends with `Done` or
+    /// `Error`.
+    pub fn format(device: String, fs: FsType, label: String, tx: impl ProgressSink) {
+        format::format(device, fs, label, tx)
+    }
+
+    /// Starts `target`, a device or an image, in a QEMU virtual machine with
+    /// UEFI or BIOS firmware as `boot_type` asks, to see it boot. Returns once
+    /// QEMU is running; the error says why it could not start.
+    pub fn test_boot(target: &str, boot_type: &BootType) -> Result<(), String> {
+        vm::test_boot(target, boot_type)
+    }
+
+    /// Wipes `device` as `mode` says, ending with `Done` or `Error`.
+    pub fn erase(device: String, mode: EraseMode, tx: impl ProgressSink) {
+        erase::erase(device, mode, tx)
+    }
+
+    /// Downloads `url` to `dest` with `WritingDetailed` progress, resuming a
+    /// partial `dest` and dropped connections. Ends with `Done` or `Error`.
+    pub fn fetch_iso(url: String, dest: String, tx: impl ProgressSink) {
+        fetch::fetch_iso(url, dest, tx)
+    }
+
+    /// Builds a bootable ISO at `output` from an extracted Windows installation
+    /// folder at `source`. Scratch files, such as a split install.wim, go
+    /// under `work_dir` and are removed afterwards.
+    pub fn create_win_iso(source: String, output: String, work_dir: String, tx: impl ProgressSink) {
+        iso_writer::create_win_iso(source, output, work_dir, tx)
+    }
+
+    /// Packs the folder at `source` into an ISO 9660/Joliet image at `output`
+    /// named `label` (the folder name when empty). With `boot`, the isolinux,
+    /// GRUB or Windows loader and EFI image found in the folder are made
+    /// bootable for that boot type. Ends with `Done` or `Error`.
+    pub fn create_iso(
+        source: String,
+        output: String,
+        label: String,
+        boot: Option<BootType>,
+        tx: impl ProgressSink,
+    ) {
+        iso_writer::create_iso(source, output, label, boot, tx)
+    }
+}
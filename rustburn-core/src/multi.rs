@@ -0,0 +1,132 @@
+// rustburn-core/src/multi.rs
+
+// One image onto several devices at once. Each device gets an ordinary burn
+// on its own thread; their updates are tagged with the device path and
+// merged onto one channel.
+use crate::burn::{self, BurnReport};
+use crate::error::BurnError;
+use crate::options::BurnOptions;
+use crate::progress::{self, UiProgress};
+use std::collections::VecDeque;
+use std::sync::atomic::AtomicBool;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+
+/// Most devices written at the same time; more would only split the USB
+/// bandwidth and the source disk's reads further.
+const MAX_CONCURRENT: usize = 4;
+
+/// Burns `options.iso_path` to every path in `devices`, at most
+/// `MAX_CONCURRENT` at a time. An expected checksum is checked once up front
+/// instead of once per device. Returns one result per device, in the order
+/// given.
+pub(crate) fn burn_iso_multi(
+    options: BurnOptions,
+    devices: Vec<String>,
+    tx: mpsc::Sender<(String, UiProgress)>,
+) -> Vec<Result<BurnReport, BurnError>> {
+    if let Some(expected) = options.checksum.clone() {
+        if let Err(e) = check_once(&options, &expected, &devices, &tx) {
+            for device in &devices {
+                let _ = tx.send((device.clone(), UiProgress::Error(e.clone())));
+            }
+            return devices.iter().map(|_| Err(e.clone())).collect();
+        }
+    }
+    let options = BurnOptions {
+        checksum: None,
+        ..options
+    };
+    let queue: Mutex<VecDeque<(usize, String)>> =
+        Mutex::new(devices.iter().cloned().enumerate().collect());
+    let results: Mutex<Vec<Option<Result<BurnReport, BurnError>>>> =
+        Mutex::new(devices.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..devices.len().min(MAX_CONCURRENT) {
+            scope.spawn(|| {
+                while let Some((index, device)) = next(&queue) {
+                    let result = burn_one(&options, device, &tx);
+                    results.lock().unwrap()[index] = Some(result);
+                }
+            });
+        }
+    });
+    results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| {
+            result.unwrap_or_else(|| Err(BurnError::Other("The burn did not run".to_string())))
+        })
+        .collect()
+}
+
+fn next(queue: &Mutex<VecDeque<(usize, String)>>) -> Option<(usize, String)> {
+    queue.lock().unwrap().pop_front()
+}
+
+/// Runs a normal burn of `device`, forwarding its updates tagged with the
+/// device path.
+fn burn_one(
+    options: &BurnOptions,
+    device: String,
+    tx: &mpsc::Sender<(String, UiProgress)>,
+) -> Result<BurnReport, BurnError> {
+    let (device_tx, device_rx) = mpsc::channel();
+    let forward_tx = tx.clone();
+    let name = device.clone();
+    let forwarder = thread::spawn(move || {
+        for update in device_rx {
+            let _ = forward_tx.send((name.clone(), update));
+        }
+    });
+    // One stable ID could only name one of the devices.
+    let options = BurnOptions {
+        device_path: device,
+        device_id: None,
+        ..options.clone()
+    };
+    let result = burn::burn_iso(
+        options,
+        device_tx,
+        Arc::new(AtomicBool::new(false)),
+        Arc::new(AtomicBool::new(false)),
+    );
+    let _ = forwarder.join();
+    result
+}
+
+/// Checks the image against `expected` once, showing the progress on every
+/// device.
+fn check_once(
+    options: &BurnOptions,
+    expected: &str,
+    devices: &[String],
+    tx: &mpsc::Sender<(String, UiProgress)>,
+) -> Result<(), BurnError> {
+    let (check_tx, check_rx) = mpsc::channel();
+    let broadcast = |update: UiProgress| {
+        for device in devices {
+            let _ = tx.send((device.clone(), update.clone()));
+        }
+    };
+    broadcast(UiProgress::StartingHashCheck);
+    progress::log(
+        &check_tx,
+        format!("Checking the image's {:?} checksum", options.checksum_algo),
+    );
+    thread::scope(|scope| {
+        let check = scope.spawn(move || {
+            burn::check_checksum(options, expected, &check_tx, &AtomicBool::new(false))
+        });
+        for update in check_rx {
+            broadcast(update);
+        }
+        check.join().unwrap()
+    })?;
+    broadcast(UiProgress::Log(
+        progress::LogLevel::Info,
+        "Image checksum matches".to_string(),
+    ));
+    Ok(())
+}
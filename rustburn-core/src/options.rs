@@ -0,0 +1,227 @@
+// rustburn-core/src/options.rs
+
+// What a burn should do, as chosen in the front end.
+use crate::direct;
+use std::path::PathBuf;
+
+/// `BurnOptions::block_size` value that has the burn time a few candidate
+/// sizes on the device first and write with the fastest.
+pub const AUTO_BLOCK_SIZE: usize = 0;
+/// The block size used before, or instead of, the automatic probe.
+pub(crate) const DEFAULT_BLOCK_SIZE: usize = 1024 * 1024;
+
+/// How the written device should start up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BootType {
+    UEFI,
+    Legacy,
+    /// Both UEFI and legacy BIOS.
+    Hybrid,
+}
+
+impl BootType {
+    /// Whether BIOS firmware should be able to boot the device.
+    pub fn wants_legacy(&self) -> bool {
+        matches!(self, BootType::Legacy | BootType::Hybrid)
+    }
+
+    /// Whether UEFI firmware should be able to boot the device.
+    pub fn wants_uefi(&self) -> bool {
+        matches!(self, BootType::UEFI | BootType::Hybrid)
+    }
+}
+
+/// The partition table the device ends up with after the boot setup.
+///
+/// Valid with every boot type except one: legacy BIOS firmware only reads an
+/// MBR, so `Gpt` with `BootType::Legacy` is refused. `Gpt` with
+/// `BootType::Hybrid` keeps the image's BIOS partitions in a hybrid MBR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartitionScheme {
+    Mbr,
+    Gpt,
+}
+
+impl PartitionScheme {
+    /// Whether a device with this table can boot the way `boot_type` asks.
+    pub fn supports(&self, boot_type: &BootType) -> bool {
+        !matches!((self, boot_type), (PartitionScheme::Gpt, BootType::Legacy))
+    }
+}
+
+/// Digest used to check the image against a published checksum.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumAlgo {
+    Sha256,
+    Sha1,
+    Md5,
+}
+
+/// How the written device is checked against the image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VerifyMode {
+    /// Read the image and the device back side by side.
+    ByteCompare,
+    /// Hash the image while writing, then hash the device once; halves the reads.
+    HashCompare,
+    /// Byte-compare only this share (0.0 to 1.0) of the blocks, spread over
+    /// the device and always including the first and last. The same image
+    /// samples the same blocks every time.
+    Sample { fraction: f32 },
+}
+
+/// How the image gets onto the device.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WriteMode {
+    /// Copy the image block for block ("DD"); right for hybrid ISOs and
+    /// disk images, which bring their own partition layout.
+    #[default]
+    RawDd,
+    /// Format the device as FAT32 and copy the ISO's files onto it; right
+    /// for Windows installers, whose ISOs don't boot from USB as they are.
+    /// The result boots on UEFI only.
+    FileCopy,
+}
+
+/// Filesystem `RustBurn::format` puts on a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FsType {
+    /// Written by RustBurn itself, so volumes past 32 GB work too.
+    Fat32,
+    ExFat,
+    Ntfs,
+}
+
+/// How much of a device `RustBurn::erase` clears.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EraseMode {
+    /// Zero the partition tables and the first and last few MB.
+    QuickZeroTable,
+    /// Zero every byte; slow on large devices.
+    FullZero,
+}
+
+/// Settings for a single `RustBurn::burn_iso` run.
+#[derive(Debug, Clone)]
+pub struct BurnOptions {
+    pub iso_path: String,
+    pub device_path: String,
+    /// `UsbDevice::stable_id` of the target. When set, the burn looks the
+    /// drive up again before opening it, follows it to a new node if the OS
+    /// renumbered it, and refuses to start if it is gone.
+    pub device_id: Option<String>,
+    /// How to write the image; None uses `IsoInfo::write_mode`, the mode
+    /// `RustBurn::inspect_iso` infers for it.
+    pub write_mode: Option<WriteMode>,
+    /// How many blocks may be read ahead of the writer.
+    pub threads: usize,
+    pub make_bootable: bool,
+    pub boot_type: BootType,
+    /// Table to convert the image's partitions to when making it bootable.
+    pub partition_scheme: PartitionScheme,
+    /// Label for the image's FAT volume when making it bootable; FAT rules
+    /// apply, so it is uppercased and at most 11 characters.
+    pub volume_label: Option<String>,
+    /// Read the device back after writing and compare it with the image.
+    pub verify: bool,
+    pub verify_mode: VerifyMode,
+    /// Stop verifying where the image's content ends (the ISO's declared
+    /// volume size, or its last partition if that is further), instead of
+    /// also checking the padding after it.
+    pub verify_skip_trailing_zeros: bool,
+    /// Read the device back past the OS cache (O_DIRECT on Linux, unbuffered
+    /// on Windows), so the verify sees the device's data and not what the
+    /// write left in memory. Falls back to cached reads, after dropping the
+    /// cache, where that is not possible.
+    pub direct_verify: bool,
+    /// Pattern-test the whole device before writing, to catch bad blocks and
+    /// fake capacity.
+    pub bad_block_check: bool,
+    /// Zero the first MB and the backup GPT at the end of the device before
+    /// writing, so no partitions of an earlier, larger image are left.
+    pub wipe_table_first: bool,
+    /// Bytes per write, or `AUTO_BLOCK_SIZE` to measure the device's best.
+    pub block_size: usize,
+    /// How often a failed block write is retried before the burn fails.
+    pub max_retries: u32,
+    /// Flush the device after every this many MB written, so a crash or
+    /// power cut loses less. Resume checkpoints are taken at these points.
+    pub sync_every_mb: u64,
+    /// Expected hex digest of the image; the burn is refused when it differs.
+    pub checksum: Option<String>,
+    pub checksum_algo: ChecksumAlgo,
+    /// Unmount the target's filesystems before writing. Without it a mounted
+    /// target stops the burn, since writing under a live filesystem
+    /// corrupts it.
+    pub auto_unmount: bool,
+    /// Eject the device once the burn has finished.
+    pub auto_eject: bool,
+    /// With `auto_eject`, wait for the device to be unplugged and plugged
+    /// back in, then read the image back from it once more. Catches sticks
+    /// whose controller answered the first verify from its cache.
+    pub reverify_after_eject: bool,
+    /// After a successful burn, append the image's SHA-256, the device's
+    /// serial number and the time to `<image>.burned.sha256`.
+    pub write_sidecar: bool,
+    /// Shell command run after a successful burn (never after a failed
+    /// one), with RUSTBURN_DEVICE, RUSTBURN_ISO and RUSTBURN_RESULT set; its
+    /// output goes to the log. It runs with RustBurn's own privileges,
+    /// often root or Administrator, so only take it from a trusted source.
+    pub on_complete_command: Option<String>,
+    /// Cap on the write rate, so other devices on a shared hub keep working.
+    pub max_write_bytes_per_sec: Option<u64>,
+    /// Run every check, then stop short of writing anything.
+    pub dry_run: bool,
+    /// Continue an interrupted burn from this byte offset, as found by
+    /// `RustBurn::find_checkpoint`. The part before it is re-verified first;
+    /// if it no longer matches, the burn starts over.
+    pub resume_from: Option<u64>,
+    /// Files to copy onto the image's FAT volume during bootable setup, as
+    /// (source, destination path on the volume) pairs: an autounattend.xml,
+    /// preseed or kickstart file for an unattended install.
+    pub extra_files: Vec<(PathBuf, String)>,
+    /// Size of an ext4 persistence partition to add after a live image.
+    pub persistence_mb: Option<u64>,
+    /// A partition of the target (Linux only) to copy aside before the burn
+    /// and put back, in a new partition after the image, once it is done.
+    /// The burn is refused when it won't fit there.
+    pub preserve_partition: Option<String>,
+}
+
+impl Default for BurnOptions {
+    fn default() -> Self {
+        Self {
+            iso_path: String::new(),
+            device_path: String::new(),
+            device_id: None,
+            write_mode: None,
+            threads: 1,
+            make_bootable: false,
+            boot_type: BootType::Hybrid,
+            partition_scheme: PartitionScheme::Mbr,
+            volume_label: None,
+            verify: false,
+            verify_mode: VerifyMode::ByteCompare,
+            verify_skip_trailing_zeros: false,
+            direct_verify: direct::SUPPORTED,
+            bad_block_check: false,
+            wipe_table_first: false,
+            block_size: DEFAULT_BLOCK_SIZE,
+            max_retries: 3,
+            sync_every_mb: 256,
+            checksum: None,
+            checksum_algo: ChecksumAlgo::Sha256,
+            auto_unmount: false,
+            auto_eject: false,
+            reverify_after_eject: false,
+            write_sidecar: false,
+            on_complete_command: None,
+            max_write_bytes_per_sec: None,
+            dry_run: false,
+            resume_from: None,
+            extra_files: Vec::new(),
+            persistence_mb: None,
+            preserve_partition: None,
+        }
+    }
+}
@@ -0,0 +1,518 @@
+// rustburn-core/src/partition.rs
+
+// Reading, extending and converting the MBR or GPT of a written device in
+// place, for extra partitions added after the image and the table the user
+// asked for, and the one-partition MBR of a freshly formatted device.
+use crate::burn::read_full;
+use crate::hash::Hasher;
+use crate::options::{ChecksumAlgo, FsType};
+use std::fs::File;
+use std::io::{self, Seek, SeekFrom, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub(crate) const SECTOR: u64 = 512;
+/// Partitions start on 1 MiB boundaries, as every modern tool aligns them.
+pub(crate) const ALIGN: u64 = 1024 * 1024;
+/// What a backup GPT takes at the end of the device: 32 sectors of entries
+/// and the header.
+pub(crate) const GPT_BACKUP_LEN: u64 = 33 * SECTOR;
+
+const MBR_TABLE: usize = 446;
+const MBR_TYPE_GPT_PROTECTIVE: u8 = 0xEE;
+/// The Linux filesystem GUID 0FC63DAF-8483-4772-8E79-3D69D8477DE4, as stored.
+const GPT_TYPE_LINUX: [u8; 16] = [
+    0xAF, 0x3D, 0xC6, 0x0F, 0x83, 0x84, 0x72, 0x47, 0x8E, 0x79, 0x3D, 0x69, 0xD8, 0x47, 0x7D, 0xE4,
+];
+/// The EFI System Partition GUID C12A7328-F81F-11D2-BA4B-00A0C93EC93B, as stored.
+const GPT_TYPE_ESP: [u8; 16] = [
+    0x28, 0x73, 0x2A, 0xC1, 0x1F, 0xF8, 0xD2, 0x11, 0xBA, 0x4B, 0x00, 0xA0, 0xC9, 0x3E, 0xC9, 0x3B,
+];
+/// The Basic Data GUID EBD0A0A2-B9E5-4433-87C0-68B6B72699C7, as stored.
+const GPT_TYPE_BASIC_DATA: [u8; 16] = [
+    0xA2, 0xA0, 0xD0, 0xEB, 0xE5, 0xB9, 0x33, 0x44, 0x87, 0xC0, 0x68, 0xB6, 0xB7, 0x26, 0x99, 0xC7,
+];
+/// MBR types of FAT32 (LBA), exFAT and NTFS (which share one), Linux, and
+/// the EFI System Partition.
+const MBR_TYPE_FAT32: u8 = 0x0C;
+const MBR_TYPE_NTFS: u8 = 0x07;
+const MBR_TYPE_LINUX: u8 = 0x83;
+const MBR_TYPE_EFI: u8 = 0xEF;
+/// A fresh GPT: 128 entries of 128 bytes, in the 32 sectors after the header.
+const GPT_ENTRY_COUNT: usize = 128;
+const GPT_ENTRY_SIZE: usize = 128;
+const GPT_FIRST_USABLE: u64 = 34;
+
+/// What a partition added after the image holds, which decides its type in
+/// the table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum PartitionType {
+    Linux,
+    Data(FsType),
+}
+
+/// A device's partition table as read from its first sectors.
+pub(crate) enum Table {
+    Mbr([u8; 512]),
+    Gpt {
+        mbr: [u8; 512],
+        header: Box<[u8; 512]>,
+        entries: Vec<u8>,
+    },
+}
+
+impl Table {
+    /// Reads the table, or None when the device has no boot signature.
+    pub(crate) fn read(device: &mut File) -> io::Result<Option<Table>> {
+        let mut mbr = [0u8; 512];
+        device.seek(SeekFrom::Start(0))?;
+        if read_full(device, &mut mbr)? < 512 || mbr[510..512] != [0x55, 0xAA] {
+            return Ok(None);
+        }
+        if !mbr_entries(&mbr).any(|e| e[4] == MBR_TYPE_GPT_PROTECTIVE) {
+            return Ok(Some(Table::Mbr(mbr)));
+        }
+        let mut header = [0u8; 512];
+        device.seek(SeekFrom::Start(SECTOR))?;
+        read_full(device, &mut header)?;
+        let (count, size) = (u32_at(&header, 80) as usize, u32_at(&header, 84) as usize);
+        if &header[..8] != b"EFI PART" || !(128..=4096).contains(&size) || count > 1024 {
+            return Ok(Some(Table::Mbr(mbr)));
+        }
+        let mut entries = vec![0u8; count * size];
+        device.seek(SeekFrom::Start(u64_at(&header, 72) * SECTOR))?;
+        read_full(device, &mut entries)?;
+        Ok(Some(Table::Gpt {
+            mbr,
+            header: Box::new(header),
+            entries,
+        }))
+    }
+
+    /// Where the last partition ends, in bytes.
+    pub(crate) fn used_length(&self) -> Option<u64> {
+        match self {
+            Table::Mbr(mbr) => mbr_entries(mbr)
+                .filter(|e| e[4] != 0)
+                .map(|e| (u32_at(e, 8) as u64 + u32_at(e, 12) as u64) * SECTOR)
+                .max(),
+            Table::Gpt {
+                header, entries, ..
+            } => gpt_entries(&header[..], entries)
+                .filter(|e| e[..16].iter().any(|&b| b != 0))
+                .map(|e| (u64_at(e, 40) + 1) * SECTOR)
+                .max(),
+        }
+    }
+
+    /// Byte offsets where the partitions start, in table order.
+    pub(crate) fn partition_starts(&self) -> Vec<u64> {
+        match self {
+            Table::Mbr(mbr) => mbr_entries(mbr)
+                .filter(|e| ![0, MBR_TYPE_GPT_PROTECTIVE].contains(&e[4]))
+                .map(|e| u32_at(e, 8) as u64 * SECTOR)
+                .collect(),
+            Table::Gpt {
+                header, entries, ..
+            } => gpt_entries(&header[..], entries)
+                .filter(|e| e[..16].iter().any(|&b| b != 0))
+                .map(|e| u64_at(e, 32) * SECTOR)
+                .collect(),
+        }
+    }
+
+    /// Whether the table lists an EFI System Partition.
+    pub(crate) fn has_esp(&self) -> bool {
+        match self {
+            Table::Mbr(mbr) => mbr_entries(mbr).any(|e| e[4] == MBR_TYPE_EFI),
+            Table::Gpt {
+                header, entries, ..
+            } => gpt_entries(&header[..], entries).any(|e| e[..16] == GPT_TYPE_ESP),
+        }
+    }
+
+    /// Adds a partition of type `kind` covering `start..start + len` (bytes,
+    /// sector aligned) and writes the table back. A GPT also gets its backup
+    /// moved to the real end of the device, which a smaller image left
+    /// elsewhere.
+    pub(crate) fn add_partition(
+        mut self,
+        device: &mut File,
+        disk_len: u64,
+        start: u64,
+        len: u64,
+        kind: PartitionType,
+        name: &str,
+    ) -> io::Result<()> {
+        let first = start / SECTOR;
+        let last = (start + len) / SECTOR - 1;
+        match &mut self {
+            Table::Mbr(mbr) => {
+                let slot = (0..4)
+                    .find(|&i| mbr[MBR_TABLE + i * 16 + 4] == 0)
+                    .ok_or_else(|| io::Error::other("all four MBR partition slots are in use"))?;
+                let (first, count) = match (u32::try_from(first), u32::try_from(last - first + 1)) {
+                    (Ok(first), Ok(count)) => (first, count),
+                    _ => return Err(io::Error::other("an MBR can't address past 2 TB")),
+                };
+                let entry = &mut mbr[MBR_TABLE + slot * 16..MBR_TABLE + slot * 16 + 16];
+                // Status, then dummy CHS values; firmware uses the LBA fields.
+                entry[..4].copy_from_slice(&[0x00, 0xFE, 0xFF, 0xFF]);
+                entry[4] = match kind {
+                    PartitionType::Linux => MBR_TYPE_LINUX,
+                    PartitionType::Data(fs) => mbr_type(fs),
+                };
+                entry[5..8].copy_from_slice(&[0xFE, 0xFF, 0xFF]);
+                entry[8..12].copy_from_slice(&first.to_le_bytes());
+                entry[12..16].copy_from_slice(&count.to_le_bytes());
+                device.seek(SeekFrom::Start(0))?;
+                device.write_all(mbr)?;
+            }
+            Table::Gpt {
+                mbr,
+                header,
+                entries,
+            } => {
+                let header: &mut [u8; 512] = header;
+                let size = u32_at(header, 84) as usize;
+                let disk_sectors = disk_len / SECTOR;
+                let entry_sectors = (entries.len() as u64).div_ceil(SECTOR);
+                let backup_entries = disk_sectors - 1 - entry_sectors;
+                if last >= backup_entries {
+                    return Err(io::Error::other(
+                        "the partition would overlap the backup GPT",
+                    ));
+                }
+                let entry = entries
+                    .chunks_exact_mut(size)
+                    .find(|e| e[..16].iter().all(|&b| b == 0))
+                    .ok_or_else(|| io::Error::other("the GPT has no free partition entry"))?;
+                entry[..16].copy_from_slice(match kind {
+                    PartitionType::Linux => &GPT_TYPE_LINUX,
+                    PartitionType::Data(_) => &GPT_TYPE_BASIC_DATA,
+                });
+                entry[16..32].copy_from_slice(&new_guid(start));
+                entry[32..40].copy_from_slice(&first.to_le_bytes());
+                entry[40..48].copy_from_slice(&last.to_le_bytes());
+                entry[48..56].fill(0);
+                entry[56..128].fill(0);
+                for (i, unit) in name.encode_utf16().take(36).enumerate() {
+                    entry[56 + i * 2..58 + i * 2].copy_from_slice(&unit.to_le_bytes());
+                }
+
+                header[32..40].copy_from_slice(&(disk_sectors - 1).to_le_bytes());
+                header[48..56].copy_from_slice(&(backup_entries - 1).to_le_bytes());
+                header[88..92].copy_from_slice(&crc32(entries).to_le_bytes());
+                seal_gpt_header(header);
+                let mut backup = *header;
+                backup[24..32].copy_from_slice(&(disk_sectors - 1).to_le_bytes());
+                backup[32..40].copy_from_slice(&1u64.to_le_bytes());
+                backup[72..80].copy_from_slice(&backup_entries.to_le_bytes());
+                seal_gpt_header(&mut backup);
+                // A lone protective entry now spans the whole device. In a
+                // hybrid MBR it only covers the GPT, next to the BIOS entries.
+                let hybrid =
+                    mbr_entries(mbr).any(|e| ![0, MBR_TYPE_GPT_PROTECTIVE].contains(&e[4]));
+                let protective_len = u32::try_from(disk_sectors - 1).unwrap_or(u32::MAX);
+                for i in (0..4).filter(|_| !hybrid) {
+                    let at = MBR_TABLE + i * 16;
+                    if mbr[at + 4] == MBR_TYPE_GPT_PROTECTIVE {
+                        mbr[at + 12..at + 16].copy_from_slice(&protective_len.to_le_bytes());
+                    }
+                }
+
+                device.seek(SeekFrom::Start(u64_at(header, 72) * SECTOR))?;
+                device.write_all(entries)?;
+                device.seek(SeekFrom::Start(SECTOR))?;
+                device.write_all(header)?;
+                device.seek(SeekFrom::Start(backup_entries * SECTOR))?;
+                device.write_all(entries)?;
+                device.seek(SeekFrom::Start((disk_sectors - 1) * SECTOR))?;
+                device.write_all(&backup)?;
+                device.seek(SeekFrom::Start(0))?;
+                device.write_all(mbr)?;
+            }
+        }
+        device.sync_all()
+    }
+
+    /// Rewrites a GPT as a plain MBR holding the same partitions, and clears
+    /// both GPT headers so nothing reads the old table. Fails, leaving the
+    /// device untouched, when the partitions don't fit in an MBR.
+    pub(crate) fn into_mbr(self, device: &mut File) -> io::Result<()> {
+        let (mut mbr, header, entries) = match self {
+            Table::Gpt {
+                mbr,
+                header,
+                entries,
+            } => (mbr, header, entries),
+            Table::Mbr(_) => return Ok(()),
+        };
+        let used: Vec<&[u8]> = gpt_entries(&header[..], &entries)
+            .filter(|e| e[..16].iter().any(|&b| b != 0))
+            .collect();
+        if used.len() > 4 {
+            return Err(io::Error::other(format!(
+                "the image has {} partitions; an MBR holds 4",
+                used.len()
+            )));
+        }
+        mbr[MBR_TABLE..MBR_TABLE + 64].fill(0);
+        for (slot, gpt_entry) in used.iter().enumerate() {
+            let first = u64_at(gpt_entry, 32);
+            let count = u64_at(gpt_entry, 40) + 1 - first;
+            let (first, count) = match (u32::try_from(first), u32::try_from(count)) {
+                (Ok(first), Ok(count)) => (first, count),
+                _ => return Err(io::Error::other("an MBR can't address past 2 TB")),
+            };
+            let kind = if gpt_entry[..16] == GPT_TYPE_ESP {
+                MBR_TYPE_EFI
+            } else if gpt_entry[..16] == GPT_TYPE_LINUX {
+                MBR_TYPE_LINUX
+            } else {
+                MBR_TYPE_FAT32
+            };
+            let entry = &mut mbr[MBR_TABLE + slot * 16..MBR_TABLE + slot * 16 + 16];
+            entry[..4].copy_from_slice(&[0x00, 0xFE, 0xFF, 0xFF]);
+            entry[4] = kind;
+            entry[5..8].copy_from_slice(&[0xFE, 0xFF, 0xFF]);
+            entry[8..12].copy_from_slice(&first.to_le_bytes());
+            entry[12..16].copy_from_slice(&count.to_le_bytes());
+        }
+
+        let disk_len = device.seek(SeekFrom::End(0))?;
+        let backup = u64_at(&header[..], 32);
+        if backup > 1 && (backup + 1) * SECTOR <= disk_len {
+            device.seek(SeekFrom::Start(backup * SECTOR))?;
+            device.write_all(&[0u8; 512])?;
+        }
+        device.seek(SeekFrom::Start(SECTOR))?;
+        device.write_all(&[0u8; 512])?;
+        device.seek(SeekFrom::Start(0))?;
+        device.write_all(&mbr)?;
+        device.sync_all()
+    }
+
+    /// Adds a GPT describing the MBR's partitions. With `hybrid` the MBR
+    /// entries stay for BIOS firmware and the protective entry takes a free
+    /// slot; otherwise the MBR becomes purely protective. Returns false,
+    /// leaving the device untouched, when the image uses the sectors a GPT
+    /// needs or its layout can't be described that way.
+    pub(crate) fn into_gpt(
+        self,
+        device: &mut File,
+        disk_len: u64,
+        hybrid: bool,
+    ) -> io::Result<bool> {
+        let mut mbr = match self {
+            Table::Mbr(mbr) => mbr,
+            Table::Gpt { .. } => return Ok(true),
+        };
+        let disk_sectors = disk_len / SECTOR;
+        let last_usable = match disk_sectors.checked_sub(GPT_FIRST_USABLE) {
+            Some(last) if last > GPT_FIRST_USABLE => last,
+            _ => return Ok(false),
+        };
+        // Extended partitions hold logical ones a GPT would have to list too.
+        let used: Vec<&[u8]> = mbr_entries(&mbr).filter(|e| e[4] != 0).collect();
+        if used.is_empty() || used.iter().any(|e| [0x05, 0x0F, 0x85].contains(&e[4])) {
+            return Ok(false);
+        }
+        let spans: Vec<(u64, u64)> = used
+            .iter()
+            .map(|e| {
+                (
+                    u32_at(e, 8) as u64,
+                    u32_at(e, 8) as u64 + u32_at(e, 12) as u64,
+                )
+            })
+            .collect();
+        if spans
+            .iter()
+            .any(|&(first, end)| first < GPT_FIRST_USABLE || end - 1 > last_usable)
+        {
+            return Ok(false);
+        }
+        let mut reserved = vec![0u8; (GPT_FIRST_USABLE - 1) as usize * SECTOR as usize];
+        device.seek(SeekFrom::Start(SECTOR))?;
+        read_full(device, &mut reserved)?;
+        if reserved.iter().any(|&b| b != 0) {
+            return Ok(false);
+        }
+        let free_slot = (0..4).find(|&i| mbr[MBR_TABLE + i * 16 + 4] == 0);
+        if hybrid && free_slot.is_none() {
+            return Ok(false);
+        }
+
+        let mut entries = vec![0u8; GPT_ENTRY_COUNT * GPT_ENTRY_SIZE];
+        for (i, (mbr_entry, &(first, end))) in used.iter().zip(&spans).enumerate() {
+            let entry = &mut entries[i * GPT_ENTRY_SIZE..(i + 1) * GPT_ENTRY_SIZE];
+            let kind = match mbr_entry[4] {
+                MBR_TYPE_EFI => GPT_TYPE_ESP,
+                MBR_TYPE_LINUX => GPT_TYPE_LINUX,
+                _ => GPT_TYPE_BASIC_DATA,
+            };
+            entry[..16].copy_from_slice(&kind);
+            entry[16..32].copy_from_slice(&new_guid(first));
+            entry[32..40].copy_from_slice(&first.to_le_bytes());
+            entry[40..48].copy_from_slice(&(end - 1).to_le_bytes());
+            // Bit 2 is "legacy BIOS bootable", the GPT twin of the active flag.
+            if mbr_entry[0] == 0x80 {
+                entry[48] = 0x04;
+            }
+        }
+        let entry_sectors = (entries.len() as u64).div_ceil(SECTOR);
+        let mut header = [0u8; 512];
+        header[..8].copy_from_slice(b"EFI PART");
+        header[8..12].copy_from_slice(&0x0001_0000u32.to_le_bytes());
+        header[12..16].copy_from_slice(&92u32.to_le_bytes());
+        header[24..32].copy_from_slice(&1u64.to_le_bytes());
+        header[32..40].copy_from_slice(&(disk_sectors - 1).to_le_bytes());
+        header[40..48].copy_from_slice(&GPT_FIRST_USABLE.to_le_bytes());
+        header[48..56].copy_from_slice(&last_usable.to_le_bytes());
+        header[56..72].copy_from_slice(&new_guid(disk_len));
+        header[72..80].copy_from_slice(&2u64.to_le_bytes());
+        header[80..84].copy_from_slice(&(GPT_ENTRY_COUNT as u32).to_le_bytes());
+        header[84..88].copy_from_slice(&(GPT_ENTRY_SIZE as u32).to_le_bytes());
+        header[88..92].copy_from_slice(&crc32(&entries).to_le_bytes());
+        seal_gpt_header(&mut header);
+        let backup_entries = disk_sectors - 1 - entry_sectors;
+        let mut backup = header;
+        backup[24..32].copy_from_slice(&(disk_sectors - 1).to_le_bytes());
+        backup[32..40].copy_from_slice(&1u64.to_le_bytes());
+        backup[72..80].copy_from_slice(&backup_entries.to_le_bytes());
+        seal_gpt_header(&mut backup);
+
+        // The protective entry covers the GPT itself, plus everything when the
+        // MBR no longer lists partitions of its own.
+        let (slot, protective_len) = match free_slot.filter(|_| hybrid) {
+            Some(slot) => {
+                let first_used = spans.iter().map(|&(first, _)| first).min().unwrap_or(2);
+                (slot, first_used - 1)
+            }
+            None => {
+                mbr[MBR_TABLE..MBR_TABLE + 64].fill(0);
+                (0, disk_sectors - 1)
+            }
+        };
+        let protective_len = u32::try_from(protective_len).unwrap_or(u32::MAX);
+        let entry = &mut mbr[MBR_TABLE + slot * 16..MBR_TABLE + slot * 16 + 16];
+        entry[..4].copy_from_slice(&[0x00, 0x00, 0x02, 0x00]);
+        entry[4] = MBR_TYPE_GPT_PROTECTIVE;
+        entry[5..8].copy_from_slice(&[0xFE, 0xFF, 0xFF]);
+        entry[8..12].copy_from_slice(&1u32.to_le_bytes());
+        entry[12..16].copy_from_slice(&protective_len.to_le_bytes());
+
+        device.seek(SeekFrom::Start(2 * SECTOR))?;
+        device.write_all(&entries)?;
+        device.seek(SeekFrom::Start(SECTOR))?;
+        device.write_all(&header)?;
+        device.seek(SeekFrom::Start(backup_entries * SECTOR))?;
+        device.write_all(&entries)?;
+        device.seek(SeekFrom::Start((disk_sectors - 1) * SECTOR))?;
+        device.write_all(&backup)?;
+        device.seek(SeekFrom::Start(0))?;
+        device.write_all(&mbr)?;
+        device.sync_all()?;
+        Ok(true)
+    }
+}
+
+/// Writes a new MBR whose only partition, typed for `fs`, covers `len` bytes
+/// from `start`. Whatever table was there before is replaced.
+pub(crate) fn write_single_mbr(
+    device: &mut File,
+    start: u64,
+    len: u64,
+    fs: FsType,
+) -> io::Result<()> {
+    let (first, count) = match (u32::try_from(start / SECTOR), u32::try_from(len / SECTOR)) {
+        (Ok(first), Ok(count)) => (first, count),
+        _ => return Err(io::Error::other("an MBR can't address past 2 TB")),
+    };
+    let mut mbr = [0u8; 512];
+    // The disk signature Windows tells disks apart by.
+    mbr[440..444].copy_from_slice(&new_guid(start)[..4]);
+    let entry = &mut mbr[MBR_TABLE..MBR_TABLE + 16];
+    entry[..4].copy_from_slice(&[0x00, 0xFE, 0xFF, 0xFF]);
+    entry[4] = mbr_type(fs);
+    entry[5..8].copy_from_slice(&[0xFE, 0xFF, 0xFF]);
+    entry[8..12].copy_from_slice(&first.to_le_bytes());
+    entry[12..16].copy_from_slice(&count.to_le_bytes());
+    mbr[510..512].copy_from_slice(&[0x55, 0xAA]);
+    device.seek(SeekFrom::Start(0))?;
+    device.write_all(&mbr)?;
+    device.sync_all()
+}
+
+/// Rounds `offset` up to the next partition boundary.
+pub(crate) fn align_up(offset: u64) -> u64 {
+    offset.div_ceil(ALIGN) * ALIGN
+}
+
+/// The MBR type of a partition holding `fs`.
+fn mbr_type(fs: FsType) -> u8 {
+    match fs {
+        FsType::Fat32 => MBR_TYPE_FAT32,
+        FsType::ExFat | FsType::Ntfs => MBR_TYPE_NTFS,
+    }
+}
+
+fn mbr_entries(mbr: &[u8; 512]) -> impl Iterator<Item = &[u8]> + Clone {
+    (0..4).map(move |i| &mbr[MBR_TABLE + i * 16..MBR_TABLE + i * 16 + 16])
+}
+
+fn gpt_entries<'a>(header: &[u8], entries: &'a [u8]) -> impl Iterator<Item = &'a [u8]> {
+    entries.chunks_exact(u32_at(header, 84) as usize)
+}
+
+/// Recomputes a GPT header's own CRC.
+fn seal_gpt_header(header: &mut [u8; 512]) {
+    let len = (u32_at(header, 12) as usize).clamp(92, 512);
+    header[16..20].fill(0);
+    let crc = crc32(&header[..len]);
+    header[16..20].copy_from_slice(&crc.to_le_bytes());
+}
+
+/// A random-enough version 4 GUID for a new partition.
+fn new_guid(seed: u64) -> [u8; 16] {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let mut hasher = Hasher::new(ChecksumAlgo::Sha256);
+    hasher.update(&nanos.to_le_bytes());
+    hasher.update(&std::process::id().to_le_bytes());
+    hasher.update(&seed.to_le_bytes());
+    let digest = hasher.finish_hex();
+    let mut guid = [0u8; 16];
+    for (i, byte) in guid.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&digest[i * 2..i * 2 + 2], 16).unwrap_or(0);
+    }
+    guid[7] = (guid[7] & 0x0F) | 0x40;
+    guid[8] = (guid[8] & 0x3F) | 0x80;
+    guid
+}
+
+/// CRC-32 (IEEE), as GPT headers and entry arrays use.
+pub(crate) fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+fn u64_at(bytes: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(bytes[at..at + 8].try_into().unwrap())
+}
@@ -0,0 +1,130 @@
+// rustburn-core/src/persistence.rs
+
+// A writable partition after a live image, so changes made while running the
+// live system survive a reboot.
+use crate::error::BurnError;
+use crate::options::BurnOptions;
+use crate::partition::{self, PartitionType, Table, GPT_BACKUP_LEN};
+use crate::progress::{log, ProgressSink};
+use std::fs::{self, OpenOptions};
+use std::io::{Seek, SeekFrom};
+use std::path::Path;
+use std::process::Command;
+
+/// Live images whose boot scripts (casper) look for a `casper-rw` partition.
+/// Other Debian-family images use live-boot and a `persistence` partition.
+const CASPER_HINTS: &[&str] = &["ubuntu", "mint", "pop-os", "zorin", "elementary"];
+
+/// Adds an ext4 persistence partition of `options.persistence_mb` after the
+/// `image_len` bytes just written.
+pub(crate) fn add_partition(
+    options: &BurnOptions,
+    image_len: u64,
+    tx: &dyn ProgressSink,
+) -> Result<(), BurnError> {
+    let mb = match options.persistence_mb {
+        Some(mb) => mb,
+        None => return Ok(()),
+    };
+    if !cfg!(target_os = "linux") {
+        return Err(BurnError::Other(
+            "Persistence partitions need mkfs.ext4 and are only supported on Linux".to_string(),
+        ));
+    }
+    let path = &options.device_path;
+    let mut device = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(|e| BurnError::io("open", path, e))?;
+    let disk_len = device
+        .seek(SeekFrom::End(0))
+        .map_err(|e| BurnError::io("read the size of", path, e))?;
+    let table = Table::read(&mut device)
+        .map_err(|e| BurnError::io("read the partition table of", path, e))?
+        .ok_or_else(|| {
+            BurnError::Other(
+                "The image has no partition table, so no persistence partition can be added"
+                    .to_string(),
+            )
+        })?;
+    let start = partition::align_up(table.used_length().unwrap_or(0).max(image_len));
+    let len = mb * 1024 * 1024;
+    let room = disk_len.saturating_sub(GPT_BACKUP_LEN);
+    if start + len > room {
+        return Err(BurnError::Other(format!(
+            "A {} MB persistence partition does not fit; {} MB are free after the image",
+            mb,
+            room.saturating_sub(start) / (1024 * 1024)
+        )));
+    }
+
+    let casper = is_casper_image(&options.iso_path);
+    let label = if casper { "casper-rw" } else { "persistence" };
+    log(tx, format!("Adding a {} MB {} partition", mb, label));
+    table
+        .add_partition(
+            &mut device,
+            disk_len,
+            start,
+            len,
+            PartitionType::Linux,
+            label,
+        )
+        .map_err(|e| BurnError::Other(format!("Could not add the partition: {}", e)))?;
+    drop(device);
+    format_ext4(path, start, len, label, !casper)?;
+    // Let the kernel see the new partition; a busy device picks it up on replug.
+    let _ = Command::new("blockdev").args(["--rereadpt", path]).output();
+    Ok(())
+}
+
+fn is_casper_image(iso_path: &str) -> bool {
+    let name = Path::new(iso_path)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    CASPER_HINTS.iter().any(|hint| name.contains(hint))
+}
+
+/// Formats `len` bytes at `start` of the device as ext4. live-boot also wants
+/// a persistence.conf at the root saying what to keep.
+fn format_ext4(
+    device_path: &str,
+    start: u64,
+    len: u64,
+    label: &str,
+    with_conf: bool,
+) -> Result<(), BurnError> {
+    let root = std::env::temp_dir().join(format!("rustburn-persistence-{}", std::process::id()));
+    let mut args = vec![
+        "-F".to_string(),
+        "-q".to_string(),
+        "-t".to_string(),
+        "ext4".to_string(),
+        "-L".to_string(),
+        label.to_string(),
+        "-E".to_string(),
+        format!("offset={}", start),
+    ];
+    if with_conf {
+        fs::create_dir_all(&root)
+            .and_then(|_| fs::write(root.join("persistence.conf"), "/ union\n"))
+            .map_err(|e| BurnError::io("create", &root.display().to_string(), e))?;
+        args.push("-d".to_string());
+        args.push(root.display().to_string());
+    }
+    args.push(device_path.to_string());
+    args.push(format!("{}k", len / 1024));
+    let output = Command::new("mke2fs").args(&args).output();
+    let _ = fs::remove_dir_all(&root);
+    let output = output.map_err(|e| BurnError::Other(format!("Could not run mke2fs: {}", e)))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(BurnError::Other(format!(
+            "mke2fs failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
@@ -0,0 +1,264 @@
+// rustburn-core/src/preserve.rs
+
+// Keeping one partition of the target across a burn: it is copied to a temp
+// image before the write and put back, in a new partition after the image,
+// once the bootable setup is done.
+use crate::burn::read_full;
+use crate::device;
+use crate::error::BurnError;
+use crate::options::{BurnOptions, FsType};
+use crate::partition::{self, PartitionType, Table, GPT_BACKUP_LEN};
+use crate::progress::{log, ProgressSink};
+use crate::space;
+use std::fs::{self, File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+const BLOCK: usize = 1024 * 1024;
+/// Name of the re-created partition in a GPT.
+const GPT_NAME: &str = "Data";
+
+/// A partition copied off the target, waiting to be put back.
+pub(crate) struct Backup {
+    partition: String,
+    path: PathBuf,
+    len: u64,
+    kind: PartitionType,
+}
+
+/// Checks that `options.preserve_partition` is a partition of the target and
+/// that it fits on the device after an image of `image_len` bytes (and the
+/// persistence partition, if any); an image of unknown length is refused.
+/// Returns its size.
+pub(crate) fn check(
+    options: &BurnOptions,
+    partition: &str,
+    image_len: Option<u64>,
+    device_len: u64,
+) -> Result<u64, BurnError> {
+    if !cfg!(target_os = "linux") {
+        return Err(BurnError::Other(
+            "Keeping a partition across a burn is only supported on Linux".to_string(),
+        ));
+    }
+    if !device::is_partition_on(partition, &options.device_path) {
+        return Err(BurnError::Other(format!(
+            "{} is not a partition of {}",
+            partition, options.device_path
+        )));
+    }
+    let len = File::open(partition)
+        .and_then(|mut f| f.seek(SeekFrom::End(0)))
+        .map_err(|e| BurnError::io("read the size of", partition, e))?;
+    // A compressed image's length is only known once it is written, too
+    // late to find that the partition doesn't fit.
+    let image_len = image_len.ok_or_else(|| {
+        BurnError::Other("A partition can't be kept when burning a compressed image".to_string())
+    })?;
+    let persistence = match options.persistence_mb {
+        Some(mb) if options.make_bootable => partition::align_up(mb * 1024 * 1024),
+        _ => 0,
+    };
+    let start = partition::align_up(image_len) + persistence;
+    let room = device_len.saturating_sub(GPT_BACKUP_LEN);
+    if start + len > room {
+        return Err(BurnError::Other(format!(
+            "{} ({:.1} MB) won't fit in the {:.1} MB left after the image",
+            partition,
+            len as f64 / 1e6,
+            room.saturating_sub(start) as f64 / 1e6
+        )));
+    }
+    Ok(len)
+}
+
+/// Copies the `len` bytes of `partition` to a file in the temp directory.
+/// The file is left there when the burn fails, so nothing is lost.
+pub(crate) fn back_up(
+    partition: &str,
+    len: u64,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+) -> Result<Backup, BurnError> {
+    let dir = std::env::temp_dir();
+    let name = Path::new(partition)
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let path = dir.join(format!("rustburn-keep-{}-{}.img", name, std::process::id()));
+    let display = path.display().to_string();
+    if let Ok(free) = space::free_space(&dir.display().to_string()) {
+        if free < len {
+            return Err(BurnError::Other(format!(
+                "Backing up {} needs {:.1} GB in {}, which has {:.1} GB free",
+                partition,
+                len as f64 / 1e9,
+                dir.display(),
+                free as f64 / 1e9
+            )));
+        }
+    }
+    log(
+        tx,
+        format!(
+            "Backing up {} ({:.1} MB) to {}",
+            partition,
+            len as f64 / 1e6,
+            display
+        ),
+    );
+    let mut from = File::open(partition).map_err(|e| BurnError::io("open", partition, e))?;
+    let mut to = File::create(&path).map_err(|e| BurnError::io("create", &display, e))?;
+    if let Err(e) = copy(&mut from, partition, &mut to, &display, len, Some(cancel)) {
+        drop(to);
+        let _ = fs::remove_file(&path);
+        return Err(e);
+    }
+    to.sync_all()
+        .map_err(|e| BurnError::io("flush", &display, e))?;
+    let kind = filesystem_of(&path)
+        .map(PartitionType::Data)
+        .unwrap_or(PartitionType::Linux);
+    log(
+        tx,
+        format!(
+            "Backed up {}; if the burn fails, its contents stay in {}",
+            partition, display
+        ),
+    );
+    Ok(Backup {
+        partition: partition.to_string(),
+        path,
+        len,
+        kind,
+    })
+}
+
+impl Backup {
+    /// Adds a partition after the `image_len` bytes just written, and
+    /// whatever the bootable setup added after them, and copies the backup
+    /// into it. The temp file is removed once it is back.
+    pub(crate) fn restore(
+        self,
+        device_path: &str,
+        image_len: u64,
+        tx: &dyn ProgressSink,
+    ) -> Result<(), BurnError> {
+        let display = self.path.display().to_string();
+        let kept = |message: String| {
+            BurnError::Other(format!(
+                "{}; the contents of {} are still in {}",
+                message, self.partition, display
+            ))
+        };
+        let mut device = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(device_path)
+            .map_err(|e| BurnError::io("open", device_path, e))?;
+        let disk_len = device
+            .seek(SeekFrom::End(0))
+            .map_err(|e| BurnError::io("read the size of", device_path, e))?;
+        let table = Table::read(&mut device)
+            .map_err(|e| BurnError::io("read the partition table of", device_path, e))?
+            .ok_or_else(|| kept("The image has no partition table to add it to".to_string()))?;
+        let start = partition::align_up(table.used_length().unwrap_or(0).max(image_len));
+        let room = disk_len.saturating_sub(GPT_BACKUP_LEN);
+        if start + self.len > room {
+            return Err(kept(format!(
+                "It needs {:.1} MB but only {:.1} MB are free after the image",
+                self.len as f64 / 1e6,
+                room.saturating_sub(start) as f64 / 1e6
+            )));
+        }
+        log(
+            tx,
+            format!(
+                "Re-creating {} at {:.1} MB of {}",
+                self.partition,
+                start as f64 / 1e6,
+                device_path
+            ),
+        );
+        table
+            .add_partition(&mut device, disk_len, start, self.len, self.kind, GPT_NAME)
+            .map_err(|e| kept(format!("Could not add the partition: {}", e)))?;
+        log(tx, format!("Restoring the contents of {}", self.partition));
+        let mut from = File::open(&self.path).map_err(|e| BurnError::io("open", &display, e))?;
+        device
+            .seek(SeekFrom::Start(start))
+            .map_err(|e| BurnError::io("seek", device_path, e))?;
+        copy(
+            &mut from,
+            &display,
+            &mut device,
+            device_path,
+            self.len,
+            None,
+        )
+        .map_err(|e| kept(e.to_string()))?;
+        device
+            .sync_all()
+            .map_err(|e| BurnError::io("flush", device_path, e))?;
+        drop(device);
+        // Let the kernel see the new partition; a busy device picks it up on replug.
+        let _ = Command::new("blockdev")
+            .args(["--rereadpt", device_path])
+            .output();
+        let _ = fs::remove_file(&self.path);
+        log(tx, format!("Kept the data of {}", self.partition));
+        Ok(())
+    }
+}
+
+/// Copies `len` bytes from the current positions, stopping early when
+/// `cancel` is set.
+fn copy(
+    from: &mut File,
+    from_path: &str,
+    to: &mut File,
+    to_path: &str,
+    len: u64,
+    cancel: Option<&AtomicBool>,
+) -> Result<(), BurnError> {
+    let mut buf = vec![0u8; BLOCK];
+    let mut left = len;
+    while left > 0 {
+        if cancel.is_some_and(|c| c.load(Ordering::SeqCst)) {
+            return Err(BurnError::Cancelled);
+        }
+        let want = left.min(BLOCK as u64) as usize;
+        let n =
+            read_full(from, &mut buf[..want]).map_err(|e| BurnError::io("read", from_path, e))?;
+        if n < want {
+            return Err(BurnError::Other(format!(
+                "{} ended {} bytes early",
+                from_path,
+                left - n as u64
+            )));
+        }
+        to.write_all(&buf[..n])
+            .map_err(|e| BurnError::io("write", to_path, e))?;
+        left -= n as u64;
+    }
+    Ok(())
+}
+
+/// The Windows filesystem the backup holds, from its boot sector; anything
+/// else is typed as a Linux partition.
+fn filesystem_of(path: &Path) -> Option<FsType> {
+    let mut boot = [0u8; 512];
+    let mut file = File::open(path).ok()?;
+    if read_full(&mut file, &mut boot).ok()? < boot.len() {
+        return None;
+    }
+    match (&boot[3..11], &boot[54..57], &boot[82..87]) {
+        (b"NTFS    ", _, _) => Some(FsType::Ntfs),
+        (b"EXFAT   ", _, _) => Some(FsType::ExFat),
+        // FAT32, or FAT12/16, which the same LBA type serves.
+        (_, b"FAT", _) | (_, _, b"FAT32") => Some(FsType::Fat32),
+        _ => None,
+    }
+}
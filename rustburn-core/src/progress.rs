@@ -0,0 +1,171 @@
+// rustburn-core/src/progress.rs
+
+// Messages a running operation sends back to its front end.
+use crate::error::BurnError;
+use std::collections::VecDeque;
+use std::sync::{mpsc, Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// How much a log line matters; front ends hide the lower levels by default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    /// Per-block detail for troubleshooting.
+    Debug,
+    Info,
+    /// Something the user should know about, though the operation goes on.
+    Warn,
+    Error,
+}
+
+/// One update from a worker thread. Every operation ends with `Done` or
+/// `Error`, except a cancelled burn, which just stops sending.
+#[derive(Debug, Clone)]
+pub enum UiProgress {
+    Log(LogLevel, String),
+    StartingBurn,
+    /// Fraction of the image written (or of the file read, for `hash_file`),
+    /// from 0.0 to 1.0.
+    Writing(f32),
+    /// Like `Writing`, with the numbers behind the fraction. `bytes_per_sec`
+    /// is averaged over the last second.
+    WritingDetailed {
+        fraction: f32,
+        bytes_done: u64,
+        total: u64,
+        bytes_per_sec: f64,
+    },
+    /// SHA-256 of the image as written, taken while it streamed to the device;
+    /// from `hash_file`, the digest it was asked for.
+    SourceHash(String),
+    /// The image is being checked against the expected checksum, before
+    /// anything is written.
+    StartingHashCheck,
+    /// Fraction of the image hashed for that check.
+    Hashing(f32),
+    StartingVerification,
+    /// Fraction of the image read back and compared.
+    Verifying(f32),
+    StartingBootableSetup,
+    StartingCreateWinIso,
+    StartingEject,
+    StartingErase,
+    /// What a successful burn did, sent before its `Done`. `elapsed`
+    /// covers the whole burn, checks and verification included, and
+    /// `avg_mbps` is `bytes` over it in MB/s.
+    Summary {
+        bytes: u64,
+        elapsed: Duration,
+        avg_mbps: f64,
+        verified: bool,
+    },
+    Done,
+    /// Why the operation failed; `to_string()` gives the text to show.
+    Error(BurnError),
+}
+
+/// Where an operation's updates go. Every `RustBurn` method that reports
+/// progress takes one; a front end usually passes the `mpsc::Sender` its UI
+/// thread polls.
+pub trait ProgressSink: Send + Sync {
+    /// Takes one update. A sink whose reader has gone away drops it.
+    fn send(&self, update: UiProgress);
+}
+
+impl ProgressSink for mpsc::Sender<UiProgress> {
+    fn send(&self, update: UiProgress) {
+        let _ = mpsc::Sender::send(self, update);
+    }
+}
+
+/// Collects every update, e.g. to look at them after a test run.
+impl ProgressSink for Mutex<Vec<UiProgress>> {
+    fn send(&self, update: UiProgress) {
+        self.lock().unwrap_or_else(|e| e.into_inner()).push(update);
+    }
+}
+
+impl<T: ProgressSink + ?Sized> ProgressSink for &T {
+    fn send(&self, update: UiProgress) {
+        (**self).send(update)
+    }
+}
+
+impl<T: ProgressSink + ?Sized> ProgressSink for Arc<T> {
+    fn send(&self, update: UiProgress) {
+        (**self).send(update)
+    }
+}
+
+/// Discards every update, for callers that only want the result.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopSink;
+
+impl ProgressSink for NoopSink {
+    fn send(&self, _update: UiProgress) {}
+}
+
+/// Passes `update` on to `tx`.
+pub(crate) fn send(tx: &dyn ProgressSink, update: UiProgress) {
+    tx.send(update);
+}
+
+/// Throughput over a sliding one-second window.
+pub(crate) struct SpeedMeter {
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl SpeedMeter {
+    const WINDOW: Duration = Duration::from_secs(1);
+
+    pub(crate) fn new() -> Self {
+        Self {
+            samples: VecDeque::from([(Instant::now(), 0)]),
+        }
+    }
+
+    /// A meter for work that resumes with `bytes_done` already finished.
+    pub(crate) fn starting_at(bytes_done: u64) -> Self {
+        Self {
+            samples: VecDeque::from([(Instant::now(), bytes_done)]),
+        }
+    }
+
+    /// Records that `bytes_done` bytes are finished and returns the current rate.
+    pub(crate) fn update(&mut self, bytes_done: u64) -> f64 {
+        let now = Instant::now();
+        self.samples.push_back((now, bytes_done));
+        // Keep one sample older than the window so there is always a span to measure.
+        while self.samples.len() > 2 && now - self.samples[1].0 >= Self::WINDOW {
+            self.samples.pop_front();
+        }
+        let (start, start_bytes) = self.samples[0];
+        let secs = (now - start).as_secs_f64();
+        if secs > 0.0 {
+            (bytes_done - start_bytes) as f64 / secs
+        } else {
+            0.0
+        }
+    }
+
+    /// Leaves `paused` out of the rate, as if the samples were taken that much later.
+    pub(crate) fn skip(&mut self, paused: Duration) {
+        for (at, _) in self.samples.iter_mut() {
+            *at += paused;
+        }
+    }
+}
+
+/// Sends an Info log line.
+pub(crate) fn log(tx: &dyn ProgressSink, msg: impl Into<String>) {
+    send(tx, UiProgress::Log(LogLevel::Info, msg.into()));
+}
+
+/// Sends a Warn log line.
+pub(crate) fn warn(tx: &dyn ProgressSink, msg: impl Into<String>) {
+    send(tx, UiProgress::Log(LogLevel::Warn, msg.into()));
+}
+
+/// Sends a Debug log line.
+pub(crate) fn debug(tx: &dyn ProgressSink, msg: impl Into<String>) {
+    send(tx, UiProgress::Log(LogLevel::Debug, msg.into()));
+}
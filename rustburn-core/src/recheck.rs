@@ -0,0 +1,133 @@
+// rustburn-core/src/recheck.rs
+
+// A last read-back after the device has been ejected and plugged in again.
+// Some USB bridges answer a verify straight after the write from their own
+// cache, so only a fresh connection shows what the flash really holds.
+use crate::badblocks;
+use crate::burn::{self, BurnReport};
+use crate::device::{self, UsbDevice};
+use crate::eject;
+use crate::error::BurnError;
+use crate::options::BurnOptions;
+use crate::progress::{log, send, warn, ProgressSink, UiProgress};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long the user gets to unplug the device and plug it back in.
+const REPLUG_TIMEOUT: Duration = Duration::from_secs(300);
+/// How often the device list is scanned while waiting.
+const SCAN_POLL: Duration = Duration::from_secs(1);
+
+/// Ejects the device once the burn is done and, with
+/// `reverify_after_eject`, waits for it to come back and reads the image
+/// back from it once more.
+pub(crate) fn eject_and_recheck(
+    options: &BurnOptions,
+    report: BurnReport,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+) -> Result<BurnReport, BurnError> {
+    // Found before it goes away, to know it again when it is back.
+    let ejected = if options.reverify_after_eject {
+        device::scan_devices().ok().and_then(|devices| {
+            devices
+                .into_iter()
+                .find(|d| d.device == options.device_path)
+        })
+    } else {
+        None
+    };
+    // The data is already safe; a busy device only needs a manual eject.
+    if let Err(e) = eject::run(&options.device_path, tx) {
+        warn(tx, format!("WARNING: Could not eject: {}", e));
+        if options.reverify_after_eject {
+            warn(tx, "WARNING: Skipping the re-check after eject");
+        }
+        return Ok(report);
+    }
+    log(tx, format!("Ejected {}", options.device_path));
+    match ejected {
+        Some(ejected) => {
+            recheck(options, &ejected, report.bytes_written, tx, cancel)?;
+            Ok(report)
+        }
+        None if options.reverify_after_eject => {
+            warn(
+                tx,
+                format!(
+                    "WARNING: Skipping the re-check: {} is not a device RustBurn can find again",
+                    options.device_path
+                ),
+            );
+            Ok(report)
+        }
+        None => Ok(report),
+    }
+}
+
+/// Waits for `ejected` to disappear and show up again, possibly under a new
+/// path, then compares its first `image_len` bytes with the image.
+fn recheck(
+    options: &BurnOptions,
+    ejected: &UsbDevice,
+    image_len: u64,
+    tx: &dyn ProgressSink,
+    cancel: &AtomicBool,
+) -> Result<(), BurnError> {
+    log(
+        tx,
+        format!(
+            "Unplug {} and plug it back in to check it once more",
+            ejected.device
+        ),
+    );
+    let started = Instant::now();
+    let mut gone = false;
+    let device_path = loop {
+        if cancel.load(Ordering::SeqCst) {
+            return Err(BurnError::Cancelled);
+        }
+        if started.elapsed() >= REPLUG_TIMEOUT {
+            return Err(BurnError::Other(format!(
+                "{} was not plugged back in within {} minutes, so it was not checked again",
+                ejected.device,
+                REPLUG_TIMEOUT.as_secs() / 60
+            )));
+        }
+        let found = device::scan_devices()
+            .ok()
+            .and_then(|devices| devices.into_iter().find(|d| same_drive(d, ejected)));
+        match found {
+            Some(d) if gone => break d.device,
+            Some(_) => {}
+            None => gone = true,
+        }
+        thread::sleep(SCAN_POLL);
+    };
+    log(
+        tx,
+        format!(
+            "{} is back as {}; reading it back",
+            ejected.device, device_path
+        ),
+    );
+    send(tx, UiProgress::StartingVerification);
+    badblocks::drop_cache(&device_path);
+    let mut options = options.clone();
+    options.device_path = device_path;
+    burn::verify(&options, image_len, tx)?;
+    log(tx, "The re-check after eject passed");
+    Ok(())
+}
+
+/// Whether `a` and `b` are the same drive: by serial number where there is
+/// one, else by the USB port it sits in. The size must match either way.
+fn same_drive(a: &UsbDevice, b: &UsbDevice) -> bool {
+    a.size == b.size
+        && if b.serial.is_empty() {
+            a.bus_path == b.bus_path
+        } else {
+            a.serial == b.serial
+        }
+}
@@ -0,0 +1,79 @@
+// rustburn-core/src/sidecar.rs
+
+// The audit record a burn can leave next to its image: what was written, to
+// which device and when. The hash line is in sha256sum's format, so
+// `sha256sum -c` checks the image against it; the other lines are comments.
+use crate::device;
+use crate::iso_writer::civil_from_days;
+use crate::source::Compression;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Where the record for the image at `iso_path` goes.
+pub(crate) fn path(iso_path: &str) -> String {
+    format!("{}.burned.sha256", iso_path)
+}
+
+/// Appends one burn's record, so burns of the same image to several devices
+/// all stay on file.
+pub(crate) fn write(iso_path: &str, device_path: &str, sha256: &str) -> io::Result<String> {
+    let serial = serial_of(device_path).unwrap_or_else(|| "unknown".to_string());
+    let name = Path::new(iso_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| iso_path.to_string());
+    // A compressed image was hashed as written, decompressed, so sha256sum
+    // could not check the file against it.
+    let hash_line = if Compression::from_path(iso_path).is_some() {
+        format!("# sha256 of the decompressed image: {}", sha256)
+    } else {
+        format!("{}  {}", sha256, name)
+    };
+    let record = format!(
+        "# RustBurn burn record\n# device: {} (serial {})\n# burned: {}\n{}\n",
+        device_path,
+        serial,
+        utc_now(),
+        hash_line
+    );
+    let path = path(iso_path);
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?
+        .write_all(record.as_bytes())?;
+    Ok(path)
+}
+
+/// The serial number of the disk at `device_path`, or of the disk holding
+/// that partition.
+fn serial_of(device_path: &str) -> Option<String> {
+    device::scan_devices()
+        .ok()?
+        .into_iter()
+        .find(|disk| {
+            disk.device == device_path || disk.partitions.iter().any(|p| p.device == device_path)
+        })
+        .map(|disk| disk.serial)
+        .filter(|serial| !serial.is_empty())
+}
+
+/// The current time as "2024-05-01 13:37:00 UTC".
+fn utc_now() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        (secs / 3600) % 24,
+        (secs / 60) % 60,
+        secs % 60
+    )
+}
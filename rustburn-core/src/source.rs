@@ -0,0 +1,130 @@
+// rustburn-core/src/source.rs
+
+// Opening the image to write. Compressed images are expanded on the fly by the
+// system's own decompressor, so a 6 GB image never has to be unpacked to disk.
+use crate::error::BurnError;
+use std::fs::File;
+use std::io::{self, Read};
+use std::process::{Child, ChildStdout, Command, Stdio};
+
+/// A compression format recognised by its file suffix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Compression {
+    Gzip,
+    Xz,
+    Zstd,
+}
+
+impl Compression {
+    pub(crate) fn from_path(path: &str) -> Option<Self> {
+        let lower = path.to_ascii_lowercase();
+        if lower.ends_with(".gz") {
+            Some(Compression::Gzip)
+        } else if lower.ends_with(".xz") {
+            Some(Compression::Xz)
+        } else if lower.ends_with(".zst") {
+            Some(Compression::Zstd)
+        } else {
+            None
+        }
+    }
+
+    /// The decompressor that streams this format to stdout.
+    fn program(self) -> &'static str {
+        match self {
+            Compression::Gzip => "gzip",
+            Compression::Xz => "xz",
+            Compression::Zstd => "zstd",
+        }
+    }
+}
+
+/// Whether `path` is a raw disk image (`.img`, possibly compressed). These
+/// carry their own partition layout, so there is no boot setup to do.
+pub(crate) fn is_disk_image(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    let inner = match lower.rsplit_once('.') {
+        Some((stem, _)) if Compression::from_path(&lower).is_some() => stem,
+        _ => &lower,
+    };
+    inner.ends_with(".img")
+}
+
+/// The image's bytes, decompressed if needed.
+pub(crate) struct Source {
+    pub(crate) reader: Box<dyn Read + Send>,
+    /// The size once decompressed, when it is known up front.
+    pub(crate) len: Option<u64>,
+}
+
+/// Opens `path` for reading, decompressing `.gz`, `.xz` and `.zst` images.
+pub(crate) fn open_image(path: &str) -> Result<Source, BurnError> {
+    let file = File::open(path).map_err(|e| BurnError::io("open", path, e))?;
+    let compression = match Compression::from_path(path) {
+        Some(compression) => compression,
+        None => {
+            let len = file
+                .metadata()
+                .map_err(|e| BurnError::io("read", path, e))?
+                .len();
+            return Ok(Source {
+                reader: Box::new(file),
+                len: Some(len),
+            });
+        }
+    };
+    let program = compression.program();
+    let mut child = Command::new(program)
+        .arg("-dc")
+        .stdin(file)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| {
+            BurnError::Other(format!(
+                "Could not start {} to decompress {}: {}. Install {} or decompress the image first.",
+                program, path, e, program
+            ))
+        })?;
+    let stdout = child.stdout.take().expect("stdout is piped");
+    Ok(Source {
+        reader: Box::new(ChildReader {
+            child,
+            stdout,
+            program,
+        }),
+        len: None,
+    })
+}
+
+/// Reads a decompressor's output and turns a failed exit into a read error,
+/// so a truncated archive can't pass for a short image.
+struct ChildReader {
+    child: Child,
+    stdout: ChildStdout,
+    program: &'static str,
+}
+
+impl Read for ChildReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.stdout.read(buf)?;
+        if n == 0 && !buf.is_empty() {
+            let status = self.child.wait()?;
+            if !status.success() {
+                return Err(io::Error::other(format!(
+                    "{} could not decompress the image ({}); it may be corrupt or truncated",
+                    self.program, status
+                )));
+            }
+        }
+        Ok(n)
+    }
+}
+
+impl Drop for ChildReader {
+    fn drop(&mut self) {
+        // Stopped early (cancel or a write error): don't leave the process behind.
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,46 @@
+// rustburn-core/src/space.rs
+
+// Free space on the filesystem holding a directory, asked of the platform's
+// own tools: df on Linux and macOS, PowerShell on Windows.
+use std::process::Command;
+
+/// Bytes available to the current user on the filesystem holding `dir`.
+pub(crate) fn free_space(dir: &str) -> Result<u64, String> {
+    if cfg!(windows) {
+        // PowerShell single-quoted strings escape a quote by doubling it.
+        let script = format!(
+            "(Get-Item -LiteralPath '{}').PSDrive.Free",
+            dir.replace('\'', "''")
+        );
+        let text = run(
+            "powershell",
+            &["-NoProfile", "-NonInteractive", "-Command", &script],
+        )?;
+        text.trim()
+            .parse()
+            .map_err(|_| format!("Unexpected PowerShell output: {}", text.trim()))
+    } else {
+        // POSIX output: a header, then "fs 1024-blocks used available capacity mount".
+        let text = run("df", &["-Pk", dir])?;
+        text.lines()
+            .nth(1)
+            .and_then(|line| line.split_whitespace().nth(3)?.parse::<u64>().ok())
+            .map(|kb| kb * 1024)
+            .ok_or_else(|| format!("Unexpected df output: {}", text.trim()))
+    }
+}
+
+fn run(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| format!("Could not run {}: {}", program, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,109 @@
+// rustburn-core/src/vm.rs
+
+// A quick boot test: start the written device, or the image itself, in a
+// QEMU virtual machine before taking it to real hardware.
+use crate::options::BootType;
+use crate::source::Compression;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::thread;
+use std::time::Duration;
+
+const QEMU: &str = "qemu-system-x86_64";
+/// Where distributions and QEMU's own packages install the x86-64 UEFI
+/// firmware. Code-only builds need no variable store to boot once.
+const UEFI_FIRMWARE: [&str; 9] = [
+    "/usr/share/OVMF/OVMF_CODE.fd",
+    "/usr/share/OVMF/OVMF.fd",
+    "/usr/share/ovmf/OVMF.fd",
+    "/usr/share/edk2/ovmf/OVMF_CODE.fd",
+    "/usr/share/edk2-ovmf/x64/OVMF_CODE.fd",
+    "/usr/share/qemu/edk2-x86_64-code.fd",
+    "/opt/homebrew/share/qemu/edk2-x86_64-code.fd",
+    "/usr/local/share/qemu/edk2-x86_64-code.fd",
+    r"C:\Program Files\qemu\share\edk2-x86_64-code.fd",
+];
+/// How long QEMU gets to fail on bad arguments or an unreadable target
+/// before it counts as started.
+const STARTUP_GRACE: Duration = Duration::from_millis(750);
+
+/// Starts QEMU booting `target`, a device or a plain image, the way
+/// `boot_type` asks; Hybrid boots with UEFI when its firmware is installed,
+/// else with BIOS. The VM runs on in its own window; an error means it did
+/// not start.
+pub(crate) fn test_boot(target: &str, boot_type: &BootType) -> Result<(), String> {
+    if target.is_empty() {
+        return Err("Nothing to boot; select a device or an image".to_string());
+    }
+    if Compression::from_path(target).is_some() {
+        return Err("QEMU cannot boot a compressed image; decompress it first".to_string());
+    }
+    let firmware = UEFI_FIRMWARE.iter().find(|path| Path::new(path).is_file());
+    let uefi = match (boot_type, firmware) {
+        (BootType::Legacy, _) => None,
+        (BootType::Hybrid, firmware) => firmware,
+        (BootType::UEFI, Some(firmware)) => Some(firmware),
+        (BootType::UEFI, None) => {
+            return Err(
+                "No UEFI firmware (OVMF) found; install it or test with legacy boot".to_string(),
+            );
+        }
+    };
+    let mut command = Command::new(QEMU);
+    command.args(["-m", "2048", "-machine", accelerators()]);
+    if let Some(firmware) = uefi {
+        command.args([
+            "-drive",
+            &format!("if=pflash,format=raw,readonly=on,file={}", firmware),
+        ]);
+    }
+    // An image is attached as a disk too, so it boots only if it would from
+    // a stick. snapshot keeps the guest's writes in a temporary overlay, and
+    // ',' separates QEMU's drive options, so one in a path is doubled.
+    command.args([
+        "-drive",
+        &format!("file={},format=raw,snapshot=on", target.replace(',', ",,")),
+    ]);
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Could not run {} (is QEMU installed?): {}", QEMU, e))?;
+    thread::sleep(STARTUP_GRACE);
+    let mut stderr = child.stderr.take();
+    match child.try_wait() {
+        Ok(Some(status)) if !status.success() => {
+            let mut output = String::new();
+            if let Some(stderr) = stderr.as_mut() {
+                let _ = stderr.read_to_string(&mut output);
+            }
+            Err(format!("{} exited ({}): {}", QEMU, status, output.trim()))
+        }
+        _ => {
+            // Drain its messages and reap it once the window is closed.
+            thread::spawn(move || {
+                if let Some(mut stderr) = stderr {
+                    let _ = std::io::copy(&mut stderr, &mut std::io::sink());
+                }
+                let _ = child.wait();
+            });
+            Ok(())
+        }
+    }
+}
+
+/// QEMU's `-machine` value: the platform's hypervisor, falling back to
+/// emulation where it is missing or not permitted.
+fn accelerators() -> &'static str {
+    if cfg!(target_os = "linux") {
+        "accel=kvm:tcg"
+    } else if cfg!(target_os = "macos") {
+        "accel=hvf:tcg"
+    } else if cfg!(windows) {
+        "accel=whpx:tcg"
+    } else {
+        "accel=tcg"
+    }
+}
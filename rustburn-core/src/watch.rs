@@ -0,0 +1,110 @@
+// rustburn-core/src/watch.rs
+
+// Following devices as they are plugged in and out. The list is rescanned
+// whenever the OS reports a block device event (udev on Linux), and every few
+// seconds where there is no such feed; the difference goes out as events.
+use crate::device::{self, UsbDevice};
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// Rescan interval without an event feed.
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Rescan interval with udev, in case an event was missed.
+const FALLBACK_INTERVAL: Duration = Duration::from_secs(30);
+/// Plugging a stick in fires a burst of events (disk, then partitions);
+/// waiting this long after the first one catches the rest in one rescan.
+const SETTLE: Duration = Duration::from_millis(500);
+
+/// A change in the set of disks.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeviceEvent {
+    /// A disk appeared, or its details changed (e.g. a card was inserted).
+    Added(UsbDevice),
+    /// The disk at this path went away.
+    Removed(String),
+}
+
+/// Starts the watcher thread. It stops once `tx` is dropped on the other side.
+pub(crate) fn watch_devices(tx: mpsc::Sender<DeviceEvent>) {
+    thread::spawn(move || {
+        let mut known = device::scan_devices().unwrap_or_default();
+        let (wake_tx, wake_rx) = mpsc::channel();
+        let mut monitor = spawn_udev_monitor(wake_tx);
+        let mut interval = if monitor.is_some() {
+            FALLBACK_INTERVAL
+        } else {
+            POLL_INTERVAL
+        };
+        loop {
+            match wake_rx.recv_timeout(interval) {
+                Ok(()) => {
+                    thread::sleep(SETTLE);
+                    while wake_rx.try_recv().is_ok() {}
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                // No monitor, or it exited: poll from now on.
+                Err(RecvTimeoutError::Disconnected) => {
+                    interval = POLL_INTERVAL;
+                    thread::sleep(POLL_INTERVAL);
+                }
+            }
+            // A failed scan (e.g. a busy PowerShell) is retried next time.
+            let current = match device::scan_devices() {
+                Ok(current) => current,
+                Err(_) => continue,
+            };
+            if !send_changes(&known, &current, &tx) {
+                break;
+            }
+            known = current;
+        }
+        if let Some(child) = monitor.as_mut() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    });
+}
+
+/// Sends what differs between two scans. Returns false once the receiver is
+/// gone.
+fn send_changes(old: &[UsbDevice], new: &[UsbDevice], tx: &mpsc::Sender<DeviceEvent>) -> bool {
+    let removed = old
+        .iter()
+        .filter(|d| !new.iter().any(|n| n.device == d.device))
+        .map(|d| DeviceEvent::Removed(d.device.clone()));
+    let added = new
+        .iter()
+        .filter(|d| !old.contains(d))
+        .map(|d| DeviceEvent::Added(d.clone()));
+    removed.chain(added).all(|event| tx.send(event).is_ok())
+}
+
+/// Runs `udevadm monitor` for block devices and signals `wake` on every
+/// event. None where udevadm is missing, so the caller falls back to polling.
+fn spawn_udev_monitor(wake: mpsc::Sender<()>) -> Option<Child> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+    let mut child = Command::new("udevadm")
+        .args(["monitor", "--udev", "--subsystem-match=block"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let stdout = child.stdout.take()?;
+    thread::spawn(move || {
+        // Event lines look like "UDEV  [1234.5] add /devices/... (block)".
+        for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+            let is_event = [" add ", " remove ", " change "]
+                .iter()
+                .any(|action| line.contains(action));
+            if is_event && wake.send(()).is_err() {
+                break;
+            }
+        }
+    });
+    Some(child)
+}
@@ -0,0 +1,226 @@
+// rustburn-core/src/wim.rs
+
+// Splitting a Windows image (WIM) into .swm parts. FAT32 can't hold a file of
+// 4 GB or more, and recent install.wim files are bigger than that. The
+// resources are copied as they are, so nothing is recompressed.
+use std::fs::File;
+use std::io::{self, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Largest file FAT32 can store.
+pub(crate) const FAT32_MAX_FILE: u64 = 0xFFFF_FFFF;
+/// Target size of each part, leaving headroom under the FAT32 limit.
+pub(crate) const SWM_PART_SIZE: u64 = 4000 * 1024 * 1024;
+
+const HEADER_LEN: usize = 208;
+const MAGIC: &[u8; 8] = b"MSWIM\0\0\0";
+const VERSION: u32 = 0x10D00;
+const HDR_FLAG_SPANNED: u32 = 0x08;
+const RES_FLAG_FREE: u8 = 0x01;
+const RES_FLAG_METADATA: u8 = 0x02;
+const RES_FLAG_SOLID: u8 = 0x10;
+const ENTRY_LEN: usize = 50;
+
+/// Header field offsets.
+const OFF_FLAGS: usize = 16;
+const OFF_PART_NUMBER: usize = 40;
+const OFF_TOTAL_PARTS: usize = 42;
+const OFF_LOOKUP_TABLE: usize = 48;
+const OFF_XML: usize = 72;
+const OFF_BOOT_METADATA: usize = 96;
+const OFF_BOOT_INDEX: usize = 120;
+const OFF_INTEGRITY: usize = 124;
+
+/// Where a resource sits in a WIM file and how big it is there.
+#[derive(Clone, Copy)]
+struct ResHeader {
+    stored_size: u64,
+    flags: u8,
+    offset: u64,
+    original_size: u64,
+}
+
+impl ResHeader {
+    fn parse(bytes: &[u8]) -> Self {
+        let mut size = [0u8; 8];
+        size[..7].copy_from_slice(&bytes[..7]);
+        Self {
+            stored_size: u64::from_le_bytes(size),
+            flags: bytes[7],
+            offset: u64::from_le_bytes(bytes[8..16].try_into().unwrap()),
+            original_size: u64::from_le_bytes(bytes[16..24].try_into().unwrap()),
+        }
+    }
+
+    fn write(&self, out: &mut [u8]) {
+        out[..7].copy_from_slice(&self.stored_size.to_le_bytes()[..7]);
+        out[7] = self.flags;
+        out[8..16].copy_from_slice(&self.offset.to_le_bytes());
+        out[16..24].copy_from_slice(&self.original_size.to_le_bytes());
+    }
+}
+
+/// One lookup table row: the resource plus its part number, reference count
+/// and SHA-1, kept as raw bytes so they are copied unchanged.
+struct Entry {
+    res: ResHeader,
+    raw: [u8; ENTRY_LEN],
+}
+
+/// Splits the WIM at `wim` into parts of at most about `part_size` bytes,
+/// written to `out_dir` as `<stem>.swm`, `<stem>2.swm`, ... Returns the part
+/// paths in order.
+pub(crate) fn split_wim(wim: &Path, out_dir: &Path, part_size: u64) -> io::Result<Vec<PathBuf>> {
+    let mut input = File::open(wim)?;
+    let mut header = [0u8; HEADER_LEN];
+    input.read_exact(&mut header)?;
+    if &header[..8] != MAGIC {
+        return Err(invalid(format!("{} is not a WIM file", wim.display())));
+    }
+    if u32_at(&header, 12) != VERSION {
+        return Err(invalid(format!(
+            "{} uses a WIM version that can't be split (solid or ESD image)",
+            wim.display()
+        )));
+    }
+    if u32_at(&header, OFF_FLAGS) & HDR_FLAG_SPANNED != 0 {
+        return Err(invalid(format!("{} is already split", wim.display())));
+    }
+    let table = ResHeader::parse(&header[OFF_LOOKUP_TABLE..]);
+    let xml = ResHeader::parse(&header[OFF_XML..]);
+    let table_bytes = read_resource(&mut input, table)?;
+    let xml_bytes = read_resource(&mut input, xml)?;
+
+    let mut entries = Vec::new();
+    for raw in table_bytes.chunks_exact(ENTRY_LEN) {
+        let res = ResHeader::parse(raw);
+        if res.flags & RES_FLAG_FREE != 0 {
+            continue;
+        }
+        if res.flags & RES_FLAG_SOLID != 0 {
+            return Err(invalid(format!(
+                "{} holds solid resources, which can't be split",
+                wim.display()
+            )));
+        }
+        entries.push(Entry {
+            res,
+            raw: raw.try_into().unwrap(),
+        });
+    }
+
+    // Image metadata must be in the first part; the rest fill parts in turn.
+    let overhead = HEADER_LEN as u64 + xml_bytes.len() as u64;
+    let mut parts: Vec<Vec<usize>> = vec![Vec::new()];
+    let mut used = overhead;
+    let metadata_first = (0..entries.len())
+        .filter(|&i| entries[i].res.flags & RES_FLAG_METADATA != 0)
+        .chain((0..entries.len()).filter(|&i| entries[i].res.flags & RES_FLAG_METADATA == 0));
+    for i in metadata_first {
+        let cost = entries[i].res.stored_size + ENTRY_LEN as u64;
+        let is_metadata = entries[i].res.flags & RES_FLAG_METADATA != 0;
+        if !is_metadata && used + cost > part_size && !parts.last().unwrap().is_empty() {
+            parts.push(Vec::new());
+            used = overhead;
+        }
+        parts.last_mut().unwrap().push(i);
+        used += cost;
+    }
+
+    let stem = wim
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "install".to_string());
+    let total = parts.len() as u16;
+    let mut paths = Vec::new();
+    for (index, members) in parts.iter().enumerate() {
+        let number = index as u16 + 1;
+        let name = if number == 1 {
+            format!("{}.swm", stem)
+        } else {
+            format!("{}{}.swm", stem, number)
+        };
+        let path = out_dir.join(name);
+        let mut out = BufWriter::new(File::create(&path)?);
+        out.write_all(&[0u8; HEADER_LEN])?;
+        let mut offset = HEADER_LEN as u64;
+        let mut part_table = Vec::with_capacity(members.len() * ENTRY_LEN);
+        // (old offset, new offset) of each resource, to repoint the boot image.
+        let mut moved = Vec::with_capacity(members.len());
+        for &i in members {
+            let entry = &entries[i];
+            input.seek(SeekFrom::Start(entry.res.offset))?;
+            let copied = io::copy(&mut (&mut input).take(entry.res.stored_size), &mut out)?;
+            if copied != entry.res.stored_size {
+                return Err(invalid(format!("{} is truncated", wim.display())));
+            }
+            let mut raw = entry.raw;
+            ResHeader {
+                offset,
+                ..entry.res
+            }
+            .write(&mut raw[..24]);
+            raw[24..26].copy_from_slice(&number.to_le_bytes());
+            part_table.extend_from_slice(&raw);
+            moved.push((entry.res.offset, offset));
+            offset += copied;
+        }
+        let part_table_res = ResHeader {
+            stored_size: part_table.len() as u64,
+            flags: table.flags,
+            offset,
+            original_size: part_table.len() as u64,
+        };
+        out.write_all(&part_table)?;
+        offset += part_table.len() as u64;
+        let xml_res = ResHeader {
+            stored_size: xml_bytes.len() as u64,
+            flags: xml.flags,
+            offset,
+            original_size: xml_bytes.len() as u64,
+        };
+        out.write_all(&xml_bytes)?;
+
+        let mut part_header = header;
+        let flags = u32_at(&header, OFF_FLAGS) | HDR_FLAG_SPANNED;
+        part_header[OFF_FLAGS..OFF_FLAGS + 4].copy_from_slice(&flags.to_le_bytes());
+        part_header[OFF_PART_NUMBER..OFF_PART_NUMBER + 2].copy_from_slice(&number.to_le_bytes());
+        part_header[OFF_TOTAL_PARTS..OFF_TOTAL_PARTS + 2].copy_from_slice(&total.to_le_bytes());
+        part_table_res.write(&mut part_header[OFF_LOOKUP_TABLE..]);
+        xml_res.write(&mut part_header[OFF_XML..]);
+        // The integrity table covers the original layout, so it is dropped.
+        part_header[OFF_INTEGRITY..OFF_INTEGRITY + 24].fill(0);
+        let boot = ResHeader::parse(&header[OFF_BOOT_METADATA..]);
+        let boot_moved = moved.iter().find(|&&(old, _)| old == boot.offset);
+        if let (1, Some(&(_, new))) = (number, boot_moved) {
+            ResHeader {
+                offset: new,
+                ..boot
+            }
+            .write(&mut part_header[OFF_BOOT_METADATA..]);
+        } else {
+            part_header[OFF_BOOT_METADATA..OFF_BOOT_METADATA + 24].fill(0);
+            part_header[OFF_BOOT_INDEX..OFF_BOOT_INDEX + 4].fill(0);
+        }
+        out.seek(SeekFrom::Start(0))?;
+        out.write_all(&part_header)?;
+        out.flush()?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+fn read_resource(input: &mut File, res: ResHeader) -> io::Result<Vec<u8>> {
+    let mut bytes = vec![0u8; res.stored_size as usize];
+    input.seek(SeekFrom::Start(res.offset))?;
+    input.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+fn u32_at(bytes: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(bytes[at..at + 4].try_into().unwrap())
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}